@@ -0,0 +1,28 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use smart_uuid::serde_with::Base32;
+use smart_uuid::TypedUuid;
+use smart_uuid_fuzz::FuzzType;
+
+#[serde_as]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Wrapper(#[serde_as(as = "Base32")] TypedUuid<FuzzType>);
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(s) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(decoded) = serde_json::from_value::<Wrapper>(serde_json::Value::String(s.to_string())) else {
+        return;
+    };
+
+    // Decoding is lenient (case, Crockford aliases); encoding must be canonical
+    let encoded = serde_json::to_value(&decoded).unwrap();
+    let canonical = encoded.as_str().unwrap();
+    assert_eq!(canonical.len(), 26);
+    assert_eq!(canonical, canonical.to_ascii_lowercase());
+    assert_eq!(serde_json::from_value::<Wrapper>(encoded.clone()).unwrap(), decoded);
+});
@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use smart_uuid::uuid::Uuid;
+use smart_uuid::{TypedUuid, UuidType};
+use smart_uuid_fuzz::FuzzType;
+
+fuzz_target!(|bytes: [u8; 16]| {
+    let uuid = Uuid::from_bytes(bytes);
+    match TypedUuid::<FuzzType>::from_uuid(uuid) {
+        Ok(id) => {
+            assert_eq!(id.as_uuid(), &uuid);
+            assert_eq!(id.variant_type().discriminant(), bytes[0]);
+            assert_eq!(id.to_string().parse::<TypedUuid<FuzzType>>().unwrap(), id);
+        }
+        Err(_) => assert!(FuzzType::from_discriminant(bytes[0]).is_none()),
+    }
+});
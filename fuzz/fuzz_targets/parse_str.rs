@@ -0,0 +1,20 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use smart_uuid::{UserFriendlyUuid, UuidType};
+use smart_uuid_fuzz::FuzzType;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(s) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(id) = UserFriendlyUuid::<FuzzType>::parse_str(s) else {
+        return;
+    };
+
+    // Anything accepted must display in canonical form and parse back to itself
+    let displayed = id.to_string();
+    assert!(displayed.starts_with(id.prefix()));
+    assert_eq!(UserFriendlyUuid::<FuzzType>::parse_str(&displayed).unwrap(), id);
+    assert_eq!(FuzzType::from_prefix(id.prefix()), Some(id.variant_type()));
+});
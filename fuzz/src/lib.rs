@@ -0,0 +1,18 @@
+//! Shared types for the fuzz targets in `fuzz_targets/`.
+//!
+//! Run a target with `cargo +nightly fuzz run <target>` from this directory; the
+//! seeds in `corpus/<target>/` give libFuzzer valid inputs to mutate from.
+
+use smart_uuid::UuidType;
+
+/// A type with gaps in its prefixes and discriminants, so mutated inputs hit both
+/// known and unknown values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+pub enum FuzzType {
+    Retail,
+    Business,
+    #[uuid_type(prefix = "org")]
+    Organization,
+    #[uuid_type(prefix = "usr_gw")]
+    Gateway,
+}
@@ -0,0 +1,43 @@
+//! Parsing benchmarks. Run with: cargo bench -p smart_uuid --bench parse
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use smart_uuid::{TypedUuid, UserFriendlyUuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    Business,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+fn parse(c: &mut Criterion) {
+    let friendly = UserFriendlyUuid::new(UserType::Business).to_string();
+    let typed = TypedUuid::new(UserType::Business).to_string();
+    let wrong_prefix = format!("retail_{}", typed);
+    let bad_body = "business_zzzzzzzz-e29b-41d4-a716-446655440000";
+
+    let mut group = c.benchmark_group("parse");
+    group.bench_function("friendly", |b| {
+        b.iter(|| UserFriendlyUuid::<UserType>::parse_str(black_box(&friendly)))
+    });
+    group.bench_function("friendly_ascii", |b| {
+        b.iter(|| UserFriendlyUuid::<UserType>::try_parse_ascii(black_box(friendly.as_bytes())))
+    });
+    group.bench_function("prefix_authoritative", |b| {
+        b.iter(|| UserFriendlyUuid::<UserType>::parse_prefix_authoritative(black_box(&friendly)))
+    });
+    group.bench_function("typed", |b| {
+        b.iter(|| black_box(&typed).parse::<TypedUuid<UserType>>())
+    });
+    group.bench_function("friendly_wrong_prefix", |b| {
+        b.iter(|| UserFriendlyUuid::<UserType>::parse_str(black_box(&wrong_prefix)))
+    });
+    group.bench_function("friendly_bad_body", |b| {
+        b.iter(|| UserFriendlyUuid::<UserType>::parse_str(black_box(bad_body)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
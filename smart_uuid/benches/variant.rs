@@ -0,0 +1,41 @@
+//! Variant decoding benchmarks. Run with: cargo bench -p smart_uuid --bench variant
+//!
+//! Compares `TypedUuid::variant_type()`, which decodes byte 0 on every call, with
+//! `CachedUuid::variant_type()`, which reads the variant stored next to each ID.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use smart_uuid::{CachedUuid, TypedUuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    Business,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+fn score(variant: UserType) -> u64 {
+    match variant {
+        UserType::Retail => 1,
+        UserType::Business => 2,
+        UserType::Organization => 3,
+    }
+}
+
+fn variant(c: &mut Criterion) {
+    let variants = [UserType::Retail, UserType::Business, UserType::Organization];
+    let ids: Vec<TypedUuid<UserType>> = (0..10_000).map(|i| TypedUuid::new(variants[i % 3])).collect();
+    let cached: Vec<CachedUuid<UserType>> = ids.iter().copied().map(CachedUuid::from).collect();
+
+    let mut group = c.benchmark_group("variant");
+    group.bench_function("decode_10k", |b| {
+        b.iter(|| black_box(&ids).iter().map(|id| score(id.variant_type())).sum::<u64>())
+    });
+    group.bench_function("cached_10k", |b| {
+        b.iter(|| black_box(&cached).iter().map(|id| score(id.variant_type())).sum::<u64>())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, variant);
+criterion_main!(benches);
@@ -110,6 +110,9 @@ fn main() {
         assert_eq!(variant, recovered);
         println!("   {:?} -> discriminant {} -> {:?} (round-trip OK)", variant, disc, recovered);
     }
+    let single = TypedUuid::new(Singleton::Instance);
+    assert_eq!(single.variant_type(), Singleton::Instance);
+    println!("   {:?} -> discriminant {} (single variant)", Singleton::Instance, Singleton::Instance.discriminant());
     println!();
 
     // --- Feature 5: Full workflow ---
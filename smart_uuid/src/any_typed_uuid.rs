@@ -0,0 +1,108 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use uuid::Uuid;
+
+use crate::traits::UuidType;
+use crate::type_info::TypeInfo;
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::UserFriendlyUuid;
+
+/// A typed UUID whose enum type has been erased.
+///
+/// Stores the raw UUID plus the [`TypeInfo`] of its variant, so IDs of different
+/// enums can share one collection, table or channel. Produced by
+/// [`UuidRegistry::parse`](crate::UuidRegistry::parse) or by converting any
+/// `TypedUuid<T>`; use [`downcast`](Self::downcast) to recover the typed form.
+///
+/// Displays in the user-friendly `{prefix}_{uuid}` format.
+#[derive(Clone, Copy)]
+pub struct AnyTypedUuid {
+    inner: Uuid,
+    info: &'static TypeInfo,
+}
+
+impl AnyTypedUuid {
+    /// Returns a reference to the underlying UUID.
+    pub fn as_uuid(&self) -> &Uuid {
+        &self.inner
+    }
+
+    /// Returns the metadata of the variant encoded in this UUID.
+    pub fn type_info(&self) -> &'static TypeInfo {
+        self.info
+    }
+
+    /// Returns the prefix of the variant encoded in this UUID.
+    pub fn prefix(&self) -> &'static str {
+        self.info.prefix()
+    }
+
+    /// Returns the name of the variant encoded in this UUID.
+    pub fn variant_name(&self) -> &'static str {
+        self.info.variant_name()
+    }
+
+    /// Returns the name of the enum type this UUID belongs to.
+    pub fn enum_name(&self) -> &'static str {
+        self.info.enum_name()
+    }
+
+    /// Returns `true` if this UUID belongs to the enum type `T`.
+    pub fn is<T: UuidType + 'static>(&self) -> bool {
+        self.info.is::<T>()
+    }
+
+    /// Recovers the `TypedUuid<T>`, or returns `None` if this UUID belongs to another enum.
+    pub fn downcast<T: UuidType + 'static>(&self) -> Option<TypedUuid<T>> {
+        if !self.is::<T>() {
+            return None;
+        }
+        TypedUuid::from_uuid(self.inner).ok()
+    }
+}
+
+impl PartialEq for AnyTypedUuid {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner && self.info.type_id() == other.info.type_id()
+    }
+}
+
+impl Eq for AnyTypedUuid {}
+
+impl Hash for AnyTypedUuid {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+        self.info.type_id().hash(state);
+    }
+}
+
+impl<T: UuidType + 'static> From<TypedUuid<T>> for AnyTypedUuid {
+    fn from(typed: TypedUuid<T>) -> Self {
+        Self {
+            inner: typed.into_uuid(),
+            info: typed.variant_type().type_info(),
+        }
+    }
+}
+
+impl<T: UuidType + 'static> From<UserFriendlyUuid<T>> for AnyTypedUuid {
+    fn from(friendly: UserFriendlyUuid<T>) -> Self {
+        friendly.into_typed_uuid().into()
+    }
+}
+
+impl fmt::Debug for AnyTypedUuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnyTypedUuid")
+            .field("uuid", &self.inner)
+            .field("enum", &self.info.enum_name())
+            .field("variant", &self.info.variant_name())
+            .finish()
+    }
+}
+
+impl fmt::Display for AnyTypedUuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}_{}", self.info.prefix(), self.inner)
+    }
+}
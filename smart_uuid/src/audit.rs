@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead};
+use std::marker::PhantomData;
+use uuid::Uuid;
+
+use crate::bloom::BloomFilter;
+use crate::traits::UuidType;
+
+/// Streams IDs and reports duplicates and per-variant counts, in bounded memory.
+///
+/// Meant for periodic integrity audits after large imports. Seen IDs are tracked in
+/// a Bloom filter sized from the expected count, so memory stays fixed (about 1.2
+/// bytes per expected ID at a 1% false-positive rate) however many IDs stream past.
+/// The price is that reported duplicates are *candidates*: a few may be false
+/// positives at the configured rate, so confirm them with an exact lookup. A real
+/// duplicate is never missed. IDs are compared byte for byte, as `Uuid` equality does.
+///
+/// # Example
+/// ```text
+/// let mut auditor = UniquenessAuditor::<UserType>::new(10_000_000, 0.001);
+/// auditor.observe_reader(BufReader::new(File::open("ids.txt")?))?;
+/// let report = auditor.report();
+/// ```
+#[derive(Debug, Clone)]
+pub struct UniquenessAuditor<T: UuidType> {
+    seen: BloomFilter,
+    counts: HashMap<u8, u64>,
+    total: u64,
+    invalid: u64,
+    unparseable: u64,
+    duplicates: Vec<Uuid>,
+    duplicate_count: u64,
+    _marker: PhantomData<T>,
+}
+
+/// The result of an audit, from [`UniquenessAuditor::report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditReport<T: UuidType> {
+    /// Every ID observed, valid or not (unparseable lines excluded).
+    pub total: u64,
+    /// How many IDs of each variant were observed, in discriminant order.
+    pub per_variant: Vec<(T, u64)>,
+    /// IDs whose discriminant is not a variant of `T`.
+    pub invalid: u64,
+    /// Lines from [`observe_reader`](UniquenessAuditor::observe_reader) that were not IDs.
+    pub unparseable: u64,
+    /// The first candidate duplicates, in the order their repeats were seen.
+    pub duplicates: Vec<Uuid>,
+    /// How many repeats were seen in total, including those beyond `duplicates`.
+    pub duplicate_count: u64,
+}
+
+/// At most this many duplicates are kept for the report; the rest are only counted.
+const MAX_REPORTED_DUPLICATES: usize = 10_000;
+
+impl<T: UuidType> UniquenessAuditor<T> {
+    /// Creates an auditor for about `expected_ids` IDs with the given false-positive rate.
+    ///
+    /// Auditing more IDs than expected still works but raises the false-positive rate.
+    ///
+    /// # Panics
+    /// Panics unless `false_positive_rate` is strictly between 0 and 1.
+    pub fn new(expected_ids: usize, false_positive_rate: f64) -> Self {
+        Self {
+            seen: BloomFilter::new(expected_ids, false_positive_rate),
+            counts: HashMap::new(),
+            total: 0,
+            invalid: 0,
+            unparseable: 0,
+            duplicates: Vec::new(),
+            duplicate_count: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Records one ID. Returns `true` if it is a (candidate) duplicate.
+    pub fn observe(&mut self, uuid: Uuid) -> bool {
+        self.total += 1;
+        let discriminant = uuid.as_bytes()[0];
+        if T::from_discriminant(discriminant).is_some() {
+            *self.counts.entry(discriminant).or_default() += 1;
+        } else {
+            self.invalid += 1;
+        }
+
+        let seen = !self.seen.insert(&uuid);
+        if seen {
+            self.duplicate_count += 1;
+            if self.duplicates.len() < MAX_REPORTED_DUPLICATES {
+                self.duplicates.push(uuid);
+            }
+        }
+        seen
+    }
+
+    /// Records every ID from an iterator of `Uuid`s or `TypedUuid`s.
+    pub fn observe_all<I>(&mut self, ids: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<Uuid>,
+    {
+        for id in ids {
+            self.observe(id.into());
+        }
+    }
+
+    /// Records one ID per line, plain or user-friendly. Blank lines are skipped and
+    /// other unparseable lines are counted in [`AuditReport::unparseable`].
+    pub fn observe_reader<R: BufRead>(&mut self, reader: R) -> io::Result<()> {
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            match parse_line(line) {
+                Some(uuid) => {
+                    self.observe(uuid);
+                }
+                None => self.unparseable += 1,
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the findings so far. The auditor can keep observing afterwards.
+    pub fn report(&self) -> AuditReport<T> {
+        let mut per_variant: Vec<_> = self
+            .counts
+            .iter()
+            .filter_map(|(&d, &count)| T::from_discriminant(d).map(|v| (d, v, count)))
+            .collect();
+        per_variant.sort_by_key(|(d, _, _)| *d);
+
+        AuditReport {
+            total: self.total,
+            per_variant: per_variant.into_iter().map(|(_, v, count)| (v, count)).collect(),
+            invalid: self.invalid,
+            unparseable: self.unparseable,
+            duplicates: self.duplicates.clone(),
+            duplicate_count: self.duplicate_count,
+        }
+    }
+}
+
+/// Parses a bare UUID, or the UUID part of a `prefix_uuid` string. The prefix is not
+/// checked: the audit is about the bytes, and invalid discriminants are counted anyway.
+fn parse_line(line: &str) -> Option<Uuid> {
+    let body = line.rsplit_once('_').map_or(line, |(_, body)| body);
+    Uuid::parse_str(body).ok()
+}
+
+impl<T: UuidType> AuditReport<T> {
+    /// Returns `true` if no duplicates, invalid discriminants or unparseable lines were found.
+    pub fn is_clean(&self) -> bool {
+        self.duplicate_count == 0 && self.invalid == 0 && self.unparseable == 0
+    }
+
+    /// Returns how many IDs of `variant` were observed.
+    pub fn count(&self, variant: T) -> u64 {
+        self.per_variant
+            .iter()
+            .find(|(v, _)| *v == variant)
+            .map_or(0, |(_, count)| *count)
+    }
+}
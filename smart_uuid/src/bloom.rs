@@ -0,0 +1,103 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use uuid::Uuid;
+
+/// A fixed-size Bloom filter over 128-bit UUID values, shared by [`DuplicateGuard`] and
+/// [`UniquenessAuditor`].
+///
+/// A value reported as new is certainly new; one reported as seen may be a false
+/// positive, at about the rate chosen at construction while no more than
+/// `expected_items` values were inserted. Lock-free, so it can be shared between threads.
+///
+/// [`DuplicateGuard`]: crate::DuplicateGuard
+/// [`UniquenessAuditor`]: crate::UniquenessAuditor
+#[derive(Debug)]
+pub(crate) struct BloomFilter {
+    words: Box<[AtomicU64]>,
+    hashes: u32,
+}
+
+impl BloomFilter {
+    /// Creates a filter sized for `expected_items` values at the given false-positive rate.
+    ///
+    /// # Panics
+    /// Panics if `false_positive_rate` is not strictly between 0 and 1.
+    pub(crate) fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        assert!(
+            false_positive_rate > 0.0 && false_positive_rate < 1.0,
+            "false positive rate must be between 0 and 1, got {}",
+            false_positive_rate
+        );
+
+        // Standard Bloom filter sizing: m = -n ln p / (ln 2)^2, k = (m / n) ln 2
+        let items = expected_items.max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let bits = (-items * false_positive_rate.ln() / (ln2 * ln2)).ceil().max(64.0);
+        let hashes = ((bits / items) * ln2).round().clamp(1.0, 32.0) as u32;
+
+        let words = (bits as usize).div_ceil(64);
+        Self {
+            words: (0..words).map(|_| AtomicU64::new(0)).collect(),
+            hashes,
+        }
+    }
+
+    /// Records `uuid`, returning `true` if it was certainly not recorded before.
+    ///
+    /// Two threads inserting the same value at the same time may both get `true`.
+    pub(crate) fn insert(&self, uuid: &Uuid) -> bool {
+        let mut new = false;
+        for (word, mask) in self.positions(uuid) {
+            new |= self.words[word].fetch_or(mask, Ordering::Relaxed) & mask == 0;
+        }
+        new
+    }
+
+    /// Returns `true` if `uuid` may have been recorded, without recording it.
+    pub(crate) fn contains(&self, uuid: &Uuid) -> bool {
+        self.positions(uuid)
+            .all(|(word, mask)| self.words[word].load(Ordering::Relaxed) & mask != 0)
+    }
+
+    /// Returns the filter's size in bytes.
+    pub(crate) fn memory_bytes(&self) -> usize {
+        self.words.len() * 8
+    }
+
+    /// Forgets every recorded value.
+    pub(crate) fn clear(&self) {
+        for word in self.words.iter() {
+            word.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Yields the word index and bit mask of each of the value's bits, by double hashing.
+    fn positions(&self, uuid: &Uuid) -> impl Iterator<Item = (usize, u64)> {
+        // IDs are not uniformly random (discriminants, timestamps), so mix both halves
+        let (high, low) = uuid.as_u64_pair();
+        let h1 = mix(high ^ mix(low));
+        let h2 = mix(low ^ h1) | 1;
+        let bits = self.words.len() as u64 * 64;
+        (0..self.hashes as u64).map(move |i| {
+            let bit = h1.wrapping_add(i.wrapping_mul(h2)) % bits;
+            ((bit / 64) as usize, 1 << (bit % 64))
+        })
+    }
+}
+
+// Manual impl: atomics are not `Clone`
+impl Clone for BloomFilter {
+    fn clone(&self) -> Self {
+        Self {
+            words: self.words.iter().map(|w| AtomicU64::new(w.load(Ordering::Relaxed))).collect(),
+            hashes: self.hashes,
+        }
+    }
+}
+
+/// The splitmix64 finalizer.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
@@ -0,0 +1,78 @@
+use rand::RngCore;
+
+use crate::error::{BulkParseError, ParseFailure};
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::UserFriendlyUuid;
+
+/// IDs drawn from the RNG per `fill_bytes` call by [`TypedUuid::generate_iter`].
+const CHUNK: usize = 64;
+
+/// Parses every user-friendly string, reporting all failures instead of the first.
+///
+/// Returns the parsed IDs in input order if every input is valid. Otherwise the
+/// [`BulkParseError`] lists each rejected input with its index and reason.
+///
+/// # Example
+/// ```text
+/// let ids = smart_uuid::parse_all::<UserType, _, _>(&request.ids)?;
+/// ```
+pub fn parse_all<T, I, S>(inputs: I) -> Result<Vec<UserFriendlyUuid<T>>, BulkParseError>
+where
+    T: UuidType,
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut parsed = Vec::new();
+    let mut failures = Vec::new();
+    let mut total = 0;
+
+    for (index, input) in inputs.into_iter().enumerate() {
+        total += 1;
+        let input = input.as_ref();
+        match UserFriendlyUuid::parse_str(input) {
+            Ok(id) => parsed.push(id),
+            Err(error) => failures.push(ParseFailure {
+                index,
+                input: input.to_string(),
+                error,
+            }),
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(parsed)
+    } else {
+        Err(BulkParseError::new(failures, total))
+    }
+}
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Creates `n` random IDs of `variant`, like calling [`new`](Self::new) `n` times.
+    ///
+    /// For bulk inserts: the RNG is looked up once and read in chunks of several IDs,
+    /// rather than once per ID.
+    pub fn generate_batch(variant: T, n: usize) -> Vec<Self> {
+        let mut ids = Vec::with_capacity(n);
+        ids.extend(Self::generate_iter(variant).take(n));
+        ids
+    }
+
+    /// Returns an endless iterator of random IDs of `variant`, for streaming the
+    /// batches of [`generate_batch`](Self::generate_batch) without collecting them.
+    ///
+    /// Holds the thread's RNG, so the iterator cannot be sent to another thread.
+    pub fn generate_iter(variant: T) -> impl Iterator<Item = Self> {
+        let mut rng = rand::thread_rng();
+        let mut buffer = [[0u8; 16]; CHUNK];
+        let mut next = CHUNK;
+        std::iter::from_fn(move || {
+            if next == CHUNK {
+                rng.fill_bytes(buffer.as_flattened_mut());
+                next = 0;
+            }
+            next += 1;
+            Some(Self::from_v8_bytes(variant, buffer[next - 1]))
+        })
+    }
+}
@@ -0,0 +1,217 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use uuid::Uuid;
+
+use crate::error::TypedUuidError;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::UserFriendlyUuid;
+
+/// A [`TypedUuid`] that stores its decoded variant next to the UUID.
+///
+/// [`variant_type`](Self::variant_type) is then a field read instead of a `match` on
+/// byte 0, which pays off in loops that branch on the variant of many IDs
+/// (`cargo bench -p smart_uuid --bench variant`). The cost is the extra field:
+/// `TypedUuid` itself stays exactly its 16 bytes, so use this only where the decode
+/// shows up in a profile, and convert back with [`into_typed_uuid`](Self::into_typed_uuid)
+/// for storage.
+///
+/// ```rust
+/// use smart_uuid::{CachedUuid, TypedUuid, UuidType};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+/// enum UserType {
+///     Retail,
+///     Business,
+/// }
+///
+/// let id = CachedUuid::from(TypedUuid::new(UserType::Business));
+/// assert_eq!(id.variant_type(), UserType::Business);
+/// ```
+///
+/// It derefs to `TypedUuid<T>`, and displays, parses, serializes, hashes and orders
+/// exactly like it.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct CachedUuid<T: UuidType> {
+    typed_uuid: TypedUuid<T>,
+    variant: T,
+}
+
+impl<T: UuidType> CachedUuid<T> {
+    /// Creates a new random ID, like [`TypedUuid::new`].
+    pub fn new(variant: T) -> Self {
+        Self {
+            typed_uuid: TypedUuid::new(variant),
+            variant,
+        }
+    }
+
+    /// Wraps a typed ID, decoding its variant once.
+    pub fn from_typed(typed_uuid: TypedUuid<T>) -> Self {
+        Self {
+            variant: typed_uuid.variant_type(),
+            typed_uuid,
+        }
+    }
+
+    /// Creates an ID from an existing UUID, validating the discriminant like
+    /// [`TypedUuid::from_uuid`].
+    pub fn from_uuid(uuid: Uuid) -> Result<Self, TypedUuidError> {
+        TypedUuid::from_uuid(uuid).map(Self::from_typed)
+    }
+
+    /// Returns the enum variant of this ID without decoding the UUID.
+    pub fn variant_type(&self) -> T {
+        self.variant
+    }
+
+    /// Returns `true` if this ID holds `variant`.
+    pub fn is_variant(&self, variant: T) -> bool {
+        self.variant == variant
+    }
+
+    /// Returns a reference to the typed ID.
+    pub fn as_typed_uuid(&self) -> &TypedUuid<T> {
+        &self.typed_uuid
+    }
+
+    /// Consumes self and returns the typed ID.
+    pub fn into_typed_uuid(self) -> TypedUuid<T> {
+        self.typed_uuid
+    }
+}
+
+impl<T: UuidType> Deref for CachedUuid<T> {
+    type Target = TypedUuid<T>;
+
+    fn deref(&self) -> &TypedUuid<T> {
+        &self.typed_uuid
+    }
+}
+
+impl<T: UuidType> AsRef<TypedUuid<T>> for CachedUuid<T> {
+    fn as_ref(&self) -> &TypedUuid<T> {
+        &self.typed_uuid
+    }
+}
+
+impl<T: UuidType> AsRef<Uuid> for CachedUuid<T> {
+    fn as_ref(&self) -> &Uuid {
+        self.typed_uuid.as_uuid()
+    }
+}
+
+// Manual impl: a derive would require `T: Hash`
+impl<T: UuidType> Hash for CachedUuid<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.typed_uuid.hash(state);
+    }
+}
+
+impl<T: UuidType> PartialOrd for CachedUuid<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: UuidType> Ord for CachedUuid<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.typed_uuid.cmp(&other.typed_uuid)
+    }
+}
+
+impl<T: UuidType> PartialEq<TypedUuid<T>> for CachedUuid<T> {
+    fn eq(&self, other: &TypedUuid<T>) -> bool {
+        self.typed_uuid == *other
+    }
+}
+
+impl<T: UuidType> PartialEq<CachedUuid<T>> for TypedUuid<T> {
+    fn eq(&self, other: &CachedUuid<T>) -> bool {
+        *self == other.typed_uuid
+    }
+}
+
+impl<T: UuidType> fmt::Debug for CachedUuid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachedUuid")
+            .field("uuid", self.typed_uuid.as_uuid())
+            .field("variant", &self.variant)
+            .finish()
+    }
+}
+
+impl<T: UuidType> fmt::Display for CachedUuid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.typed_uuid, f)
+    }
+}
+
+impl<T: UuidType> FromStr for CachedUuid<T> {
+    type Err = TypedUuidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<TypedUuid<T>>().map(Self::from_typed)
+    }
+}
+
+impl<T: UuidType> From<TypedUuid<T>> for CachedUuid<T> {
+    fn from(typed_uuid: TypedUuid<T>) -> Self {
+        Self::from_typed(typed_uuid)
+    }
+}
+
+impl<T: UuidType> From<UserFriendlyUuid<T>> for CachedUuid<T> {
+    fn from(friendly: UserFriendlyUuid<T>) -> Self {
+        Self::from_typed(friendly.into_typed_uuid())
+    }
+}
+
+impl<T: UuidType> TryFrom<Uuid> for CachedUuid<T> {
+    type Error = TypedUuidError;
+
+    fn try_from(uuid: Uuid) -> Result<Self, Self::Error> {
+        Self::from_uuid(uuid)
+    }
+}
+
+impl<T: UuidType> From<CachedUuid<T>> for TypedUuid<T> {
+    fn from(id: CachedUuid<T>) -> Self {
+        id.typed_uuid
+    }
+}
+
+impl<T: UuidType> From<CachedUuid<T>> for UserFriendlyUuid<T> {
+    fn from(id: CachedUuid<T>) -> Self {
+        id.typed_uuid.into()
+    }
+}
+
+impl<T: UuidType> From<CachedUuid<T>> for Uuid {
+    fn from(id: CachedUuid<T>) -> Self {
+        id.typed_uuid.into_uuid()
+    }
+}
+
+impl<T: UuidType> Serialize for CachedUuid<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.typed_uuid.serialize(serializer)
+    }
+}
+
+impl<'de, T: UuidType> Deserialize<'de> for CachedUuid<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        TypedUuid::deserialize(deserializer).map(Self::from_typed)
+    }
+}
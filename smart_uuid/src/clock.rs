@@ -0,0 +1,36 @@
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// A source of the current time for constructors that stamp IDs with it.
+///
+/// [`TypedUuid::new_ordered_with_clock`](crate::TypedUuid::new_ordered_with_clock),
+/// [`IdempotencyKey::new_with_clock`](crate::IdempotencyKey::new_with_clock) and
+/// [`SnowflakeGenerator::with_clock`](crate::SnowflakeGenerator::with_clock) take one;
+/// their clock-less counterparts use [`SystemClock`]. In tests, pass a `MockClock`
+/// (`test-util` feature) to control time without sleeping.
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> SystemTime;
+}
+
+/// The system's wall clock.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> SystemTime {
+        (**self).now()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> SystemTime {
+        (**self).now()
+    }
+}
@@ -0,0 +1,151 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+
+use crate::error::TypedUuidError;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::UserFriendlyUuid;
+
+/// A component of a [`CompositeId`].
+///
+/// Implemented for `TypedUuid<T>` (rendered in user-friendly `prefix_uuid` form)
+/// and for `u64` (rendered in decimal). Rendered parts must not contain `.`.
+pub trait CompositePart: Copy {
+    /// Writes the canonical string form of this part.
+    fn fmt_part(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// Parses a part from its canonical string form.
+    fn parse_part(s: &str) -> Result<Self, TypedUuidError>;
+
+    /// Orders two parts. Typed IDs order by their UUID bytes.
+    fn cmp_part(&self, other: &Self) -> Ordering;
+}
+
+impl<T: UuidType> CompositePart for TypedUuid<T> {
+    fn fmt_part(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}_{}", self.variant_type().prefix(), self.as_uuid())
+    }
+
+    fn parse_part(s: &str) -> Result<Self, TypedUuidError> {
+        UserFriendlyUuid::parse_str(s).map(UserFriendlyUuid::into_typed_uuid)
+    }
+
+    fn cmp_part(&self, other: &Self) -> Ordering {
+        self.as_uuid().cmp(other.as_uuid())
+    }
+}
+
+impl CompositePart for u64 {
+    fn fmt_part(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+
+    fn parse_part(s: &str) -> Result<Self, TypedUuidError> {
+        s.parse()
+            .map_err(|_| TypedUuidError::InvalidFormat(format!("expected an unsigned integer, found '{}'", s)))
+    }
+
+    fn cmp_part(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+}
+
+/// An identifier made of two parts, such as a join-table row or a graph edge.
+///
+/// Format: `{first}.{second}`, e.g. `order_550e8400-....item_6ba7b810-...` for two
+/// typed IDs, or `order_550e8400-....3` for a typed ID and a `u64` line number.
+///
+/// Orders by the first part, then the second. Serializes as the string form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CompositeId<A: CompositePart, B: CompositePart> {
+    first: A,
+    second: B,
+}
+
+impl<A: CompositePart, B: CompositePart> CompositeId<A, B> {
+    /// Creates a composite ID from its two parts.
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+
+    /// Returns the first part.
+    pub fn first(&self) -> A {
+        self.first
+    }
+
+    /// Returns the second part.
+    pub fn second(&self) -> B {
+        self.second
+    }
+
+    /// Consumes self and returns both parts.
+    pub fn into_parts(self) -> (A, B) {
+        (self.first, self.second)
+    }
+
+    /// Parses a composite string like `order_550e8400-....item_6ba7b810-...`.
+    pub fn parse_str(s: &str) -> Result<Self, TypedUuidError> {
+        // Neither prefixes, UUIDs nor integers contain '.', so the first one is the separator
+        let (first, second) = s.split_once('.').ok_or_else(|| {
+            TypedUuidError::InvalidFormat(
+                "expected format 'first.second', no '.' found".to_string(),
+            )
+        })?;
+
+        Ok(Self {
+            first: A::parse_part(first)?,
+            second: B::parse_part(second)?,
+        })
+    }
+}
+
+impl<A: CompositePart, B: CompositePart> fmt::Display for CompositeId<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.first.fmt_part(f)?;
+        f.write_str(".")?;
+        self.second.fmt_part(f)
+    }
+}
+
+impl<A: CompositePart, B: CompositePart> FromStr for CompositeId<A, B> {
+    type Err = TypedUuidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_str(s)
+    }
+}
+
+impl<A: CompositePart + Eq, B: CompositePart + Eq> PartialOrd for CompositeId<A, B> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<A: CompositePart + Eq, B: CompositePart + Eq> Ord for CompositeId<A, B> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.first
+            .cmp_part(&other.first)
+            .then_with(|| self.second.cmp_part(&other.second))
+    }
+}
+
+impl<A: CompositePart, B: CompositePart> Serialize for CompositeId<A, B> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, A: CompositePart, B: CompositePart> Deserialize<'de> for CompositeId<A, B> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Self::parse_str(&s).map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,37 @@
+//! Creation-time accessors returning the date-time types of `chrono`, `time` and `jiff`,
+//! each behind the feature of the same name.
+
+//!
+//! Timestamps hold at most 44 bits of milliseconds (until the year 2527), within the
+//! range of all three crates, so the conversions never fail for time-ordered IDs.
+
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+
+#[cfg(feature = "chrono")]
+impl<T: UuidType> TypedUuid<T> {
+    /// Returns the creation time as a `chrono` UTC date-time, or `None` if this ID is
+    /// not time-ordered.
+    pub fn timestamp_chrono(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::from_timestamp_millis(self.timestamp_millis()? as i64)
+    }
+}
+
+#[cfg(feature = "time")]
+impl<T: UuidType> TypedUuid<T> {
+    /// Returns the creation time as a `time` UTC date-time, or `None` if this ID is not
+    /// time-ordered.
+    pub fn timestamp_time(&self) -> Option<time::OffsetDateTime> {
+        let nanos = self.timestamp_millis()? as i128 * 1_000_000;
+        time::OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl<T: UuidType> TypedUuid<T> {
+    /// Returns the creation time as a `jiff` timestamp, or `None` if this ID is not
+    /// time-ordered.
+    pub fn timestamp_jiff(&self) -> Option<jiff::Timestamp> {
+        jiff::Timestamp::from_millisecond(self.timestamp_millis()? as i64).ok()
+    }
+}
@@ -0,0 +1,39 @@
+//! [Diesel](https://diesel.rs) support for typed IDs in Postgres `UUID` columns.
+//!
+//! `TypedUuid<T>` maps to `diesel::sql_types::Uuid`, so it can be used in `table!`
+//! structs, filters and inserts wherever a `Uuid` can:
+//!
+//! ```text
+//! #[derive(Queryable, Insertable)]
+//! #[diesel(table_name = users)]
+//! struct User {
+//!     id: TypedUuid<UserType>,
+//!     name: String,
+//! }
+//! ```
+//!
+//! The impls are for the `Pg` backend, not for a connection type, so they work
+//! unchanged with `diesel-async`'s `AsyncPgConnection`. Loading validates the
+//! discriminant, so a typed column never yields a mistyped ID.
+
+use ::diesel::deserialize::{self, FromSql};
+use ::diesel::pg::{Pg, PgValue};
+use ::diesel::serialize::{self, Output, ToSql};
+use ::diesel::sql_types::Uuid as SqlUuid;
+use uuid::Uuid;
+
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+
+impl<T: UuidType> ToSql<SqlUuid, Pg> for TypedUuid<T> {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <Uuid as ToSql<SqlUuid, Pg>>::to_sql(self.as_uuid(), out)
+    }
+}
+
+impl<T: UuidType> FromSql<SqlUuid, Pg> for TypedUuid<T> {
+    fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+        let uuid = <Uuid as FromSql<SqlUuid, Pg>>::from_sql(value)?;
+        Ok(TypedUuid::from_uuid(uuid)?)
+    }
+}
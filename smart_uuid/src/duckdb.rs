@@ -0,0 +1,78 @@
+//! [DuckDB](https://docs.rs/duckdb) support: typed IDs as `UUID` values, and SQL macros
+//! for checking UUID columns.
+//!
+//! `TypedUuid` implements `ToSql` and `FromSql`, so it binds as a parameter and
+//! appends with `Appender::append_row` like any other value:
+//!
+//! ```text
+//! conn.execute_batch("CREATE TABLE orders (id UUID, customer UUID)")?;
+//! let mut appender = conn.appender("orders")?;
+//! for order in &orders {
+//!     appender.append_row(duckdb::params![order.id, order.customer])?;
+//! }
+//! ```
+//!
+//! Values are passed as hyphenated strings, as `duckdb` does for `Uuid`, and DuckDB
+//! casts them to its native `UUID` type. Reading validates the discriminant, so a typed
+//! column never yields a mistyped ID.
+
+use ::duckdb::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, Value, ValueRef};
+use ::duckdb::{Connection, Result};
+use uuid::Uuid;
+
+use crate::traits::{variants, UuidType};
+use crate::typed_uuid::TypedUuid;
+
+impl<T: UuidType> ToSql for TypedUuid<T> {
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Owned(Value::Text(self.as_uuid().to_string())))
+    }
+}
+
+impl<T: UuidType> FromSql for TypedUuid<T> {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let uuid = Uuid::column_result(value)?;
+        TypedUuid::from_uuid(uuid).map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
+
+/// Creates the SQL macros `{name}_valid(id)` and `{name}_prefix(id)` on `conn`, for
+/// checking `UUID` columns inside DuckDB.
+///
+/// - `{name}_valid(id)` is true if the first byte of `id` is a discriminant of `T`.
+/// - `{name}_prefix(id)` returns that variant's prefix, or NULL.
+///
+/// Both return NULL for NULL. Only the discriminant is checked, not the schema version
+/// of a migrated ID. Macros are plain SQL, so they also work in table macros:
+///
+/// ```text
+/// smart_uuid::duckdb::register_macros::<UserType>(&conn, "user_id")?;
+/// conn.execute_batch(
+///     "CREATE MACRO bad_customers() AS TABLE
+///          SELECT * FROM orders WHERE NOT user_id_valid(customer)",
+/// )?;
+/// ```
+///
+/// # Panics
+/// Panics if `name` is not a plain SQL identifier (ASCII letters, digits and `_`,
+/// not starting with a digit).
+pub fn register_macros<T: UuidType>(conn: &Connection, name: &str) -> Result<()> {
+    let is_identifier = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_');
+    assert!(is_identifier, "macro name {:?} is not a plain SQL identifier", name);
+
+    // DuckDB renders UUIDs as lowercase hex, so the first two characters are byte 0
+    let first_byte = "left(CAST(id AS VARCHAR), 2)";
+    let discriminants = variants::<T>()
+        .map(|v| format!("'{:02x}'", v.discriminant()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let prefixes: String = variants::<T>()
+        .map(|v| format!(" WHEN '{:02x}' THEN '{}'", v.discriminant(), v.prefix().replace('\'', "''")))
+        .collect();
+
+    conn.execute_batch(&format!(
+        "CREATE OR REPLACE MACRO {name}_valid(id) AS {first_byte} IN ({discriminants});
+         CREATE OR REPLACE MACRO {name}_prefix(id) AS CASE {first_byte}{prefixes} END;"
+    ))
+}
@@ -0,0 +1,79 @@
+use uuid::Uuid;
+
+use crate::bloom::BloomFilter;
+use crate::error::SuspectedDuplicate;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+
+/// A fixed-size Bloom filter over 128-bit UUID values, for catching duplicate IDs as
+/// defense in depth.
+///
+/// Random IDs practically never collide; this guards against the failures that make
+/// them collide anyway, such as a cloned VM restarting with the same RNG state or a
+/// seeded RNG left in production. Pass it to [`TypedUuid::new_guarded`], or feed it IDs
+/// from elsewhere with [`check`](Self::check).
+///
+/// Memory is fixed at construction. A value reported as new is certainly new; a value
+/// reported as a duplicate was seen before or is a false positive, at about the rate
+/// chosen at construction while no more than `expected_items` values were inserted,
+/// and rising after that. Safe to share between threads; the filter is lock-free.
+#[derive(Debug)]
+pub struct DuplicateGuard {
+    filter: BloomFilter,
+}
+
+impl DuplicateGuard {
+    /// Creates a filter sized for `expected_items` values at the given false-positive rate.
+    ///
+    /// # Panics
+    /// Panics if `false_positive_rate` is not strictly between 0 and 1.
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        Self {
+            filter: BloomFilter::new(expected_items, false_positive_rate),
+        }
+    }
+
+    /// Records `uuid`, failing if it may have been recorded before.
+    pub fn check(&self, uuid: &Uuid) -> Result<(), SuspectedDuplicate> {
+        if self.insert(uuid) {
+            Ok(())
+        } else {
+            Err(SuspectedDuplicate { uuid: *uuid })
+        }
+    }
+
+    /// Records `uuid`, returning `true` if it was certainly not recorded before.
+    ///
+    /// Two threads inserting the same value at the same time may both get `true`.
+    pub fn insert(&self, uuid: &Uuid) -> bool {
+        self.filter.insert(uuid)
+    }
+
+    /// Returns `true` if `uuid` may have been recorded, without recording it.
+    pub fn contains(&self, uuid: &Uuid) -> bool {
+        self.filter.contains(uuid)
+    }
+
+    /// Returns the filter's size in bytes.
+    pub fn memory_bytes(&self) -> usize {
+        self.filter.memory_bytes()
+    }
+
+    /// Forgets every recorded value.
+    pub fn clear(&self) {
+        self.filter.clear();
+    }
+}
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Creates a new TypedUuid like [`new`](Self::new), failing if `guard` has
+    /// (probably) seen it before.
+    ///
+    /// A failure means the RNG may be broken, or is a false positive; either way the
+    /// ID is not returned, so callers decide whether to retry or alert.
+    pub fn new_guarded(variant: T, guard: &DuplicateGuard) -> Result<Self, SuspectedDuplicate> {
+        let id = Self::new(variant);
+        guard.check(id.as_uuid())?;
+        Ok(id)
+    }
+}
@@ -1,7 +1,15 @@
+use std::borrow::Cow;
+use std::fmt;
 use thiserror::Error;
 
+use crate::traits::{variants, UuidType};
+
 /// Errors that can occur when working with TypedUuid and UserFriendlyUuid.
+///
+/// New variants may be added in minor releases; match on [`kind`](Self::kind) or use
+/// the `is_*` helpers when only the category matters.
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
 pub enum TypedUuidError {
     /// The UUID does not contain a valid type discriminant.
     #[error("invalid discriminant {found} for type {type_name}")]
@@ -10,18 +18,398 @@ pub enum TypedUuidError {
         type_name: &'static str,
     },
 
-    /// Failed to parse a UUID string.
+    /// Failed to parse a UUID string. The original `uuid::Error` is kept as the source.
     #[error("failed to parse UUID: {0}")]
-    ParseError(String),
+    ParseError(#[from] uuid::Error),
 
     /// The prefix in a UserFriendlyUuid string is not recognized.
-    #[error("unknown prefix '{prefix}' for type {type_name}")]
+    ///
+    /// `expected` lists the valid prefixes, or is empty when they are not known statically.
+    /// `did_you_mean` is the valid prefix closest to the given one, if any is close enough
+    /// to be a likely typo.
+    #[error(
+        "unknown prefix '{prefix}' for type {type_name}{}{}",
+        DidYouMean(.did_you_mean),
+        ExpectedPrefixes(.expected)
+    )]
     UnknownPrefix {
         prefix: String,
         type_name: &'static str,
+        expected: Cow<'static, [&'static str]>,
+        did_you_mean: Option<&'static str>,
     },
 
     /// Invalid format for UserFriendlyUuid string.
     #[error("invalid format: {0}")]
     InvalidFormat(String),
+
+    /// A user-friendly `prefix_uuid` string was given where a bare UUID was expected.
+    #[error("found user-friendly UUID with prefix '{prefix}'; parse it with UserFriendlyUuid::parse_str or TypedUuid::parse_lenient")]
+    UnexpectedPrefix {
+        prefix: String,
+    },
+
+    /// The UUID was written with a newer schema version than this build understands.
+    #[error("schema version {found} is newer than the current version {current} for type {type_name}")]
+    UnsupportedSchemaVersion {
+        found: u8,
+        current: u8,
+        type_name: &'static str,
+    },
+
+    /// The ID is valid, but its variant is not among those accepted here.
+    ///
+    /// `found` and `allowed` are variant prefixes.
+    #[error("{found} IDs of type {type_name} are not allowed here{}", ExpectedPrefixes(.allowed))]
+    VariantNotAllowed {
+        found: &'static str,
+        type_name: &'static str,
+        allowed: Vec<&'static str>,
+    },
+}
+
+impl TypedUuidError {
+    /// An [`UnknownPrefix`](Self::UnknownPrefix) error for `T`, suggesting the closest of its prefixes.
+    pub(crate) fn unknown_prefix<T: UuidType>(prefix: &str) -> Self {
+        Self::UnknownPrefix {
+            prefix: prefix.to_string(),
+            type_name: std::any::type_name::<T>(),
+            // Collected from the variants, as `T::prefixes()` needs `T: 'static`
+            expected: Cow::Owned(variants::<T>().map(|v| v.prefix()).collect()),
+            did_you_mean: did_you_mean(prefix, variants::<T>().map(|v| v.prefix())),
+        }
+    }
+
+    /// Returns the category of this error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::InvalidDiscriminant { .. } => ErrorKind::InvalidDiscriminant,
+            Self::ParseError(_) => ErrorKind::Parse,
+            Self::UnknownPrefix { .. } => ErrorKind::UnknownPrefix,
+            Self::InvalidFormat(_) => ErrorKind::InvalidFormat,
+            Self::UnexpectedPrefix { .. } => ErrorKind::UnexpectedPrefix,
+            Self::UnsupportedSchemaVersion { .. } => ErrorKind::UnsupportedSchemaVersion,
+            Self::VariantNotAllowed { .. } => ErrorKind::VariantNotAllowed,
+        }
+    }
+
+    /// Returns the stable numeric code of this error's kind, for mapping to API errors.
+    pub fn code(&self) -> u16 {
+        self.kind().code()
+    }
+
+    /// Returns `true` if the UUID body could not be parsed.
+    pub fn is_parse(&self) -> bool {
+        self.kind() == ErrorKind::Parse
+    }
+
+    /// Returns `true` if the prefix is not valid for the type.
+    pub fn is_unknown_prefix(&self) -> bool {
+        self.kind() == ErrorKind::UnknownPrefix
+    }
+
+    /// Returns `true` if the UUID does not encode a known variant.
+    pub fn is_invalid_discriminant(&self) -> bool {
+        self.kind() == ErrorKind::InvalidDiscriminant
+    }
+
+    /// Returns `true` if the string does not have the expected overall shape.
+    pub fn is_invalid_format(&self) -> bool {
+        self.kind() == ErrorKind::InvalidFormat
+    }
+
+    /// Returns `true` if a user-friendly string was given where a bare UUID was expected.
+    pub fn is_unexpected_prefix(&self) -> bool {
+        self.kind() == ErrorKind::UnexpectedPrefix
+    }
+
+    /// Returns `true` if the ID is valid but of a variant not accepted here.
+    pub fn is_variant_not_allowed(&self) -> bool {
+        self.kind() == ErrorKind::VariantNotAllowed
+    }
+}
+
+/// The category of a [`TypedUuidError`].
+///
+/// Each kind has a stable [`code`](Self::code) and [`name`](Self::name) that will
+/// never be reassigned, so they are safe to expose in API error payloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// See [`TypedUuidError::InvalidDiscriminant`].
+    InvalidDiscriminant,
+    /// See [`TypedUuidError::ParseError`].
+    Parse,
+    /// See [`TypedUuidError::UnknownPrefix`].
+    UnknownPrefix,
+    /// See [`TypedUuidError::InvalidFormat`].
+    InvalidFormat,
+    /// See [`TypedUuidError::UnexpectedPrefix`].
+    UnexpectedPrefix,
+    /// See [`TypedUuidError::UnsupportedSchemaVersion`].
+    UnsupportedSchemaVersion,
+    /// See [`TypedUuidError::VariantNotAllowed`].
+    VariantNotAllowed,
+}
+
+impl ErrorKind {
+    /// Returns the stable numeric code for this kind.
+    pub fn code(&self) -> u16 {
+        match self {
+            Self::InvalidDiscriminant => 1,
+            Self::Parse => 2,
+            Self::UnknownPrefix => 3,
+            Self::InvalidFormat => 4,
+            Self::UnexpectedPrefix => 5,
+            Self::UnsupportedSchemaVersion => 6,
+            Self::VariantNotAllowed => 7,
+        }
+    }
+
+    /// Returns the stable snake_case name for this kind, e.g. `"unknown_prefix"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::InvalidDiscriminant => "invalid_discriminant",
+            Self::Parse => "parse",
+            Self::UnknownPrefix => "unknown_prefix",
+            Self::InvalidFormat => "invalid_format",
+            Self::UnexpectedPrefix => "unexpected_prefix",
+            Self::UnsupportedSchemaVersion => "unsupported_schema_version",
+            Self::VariantNotAllowed => "variant_not_allowed",
+        }
+    }
+}
+
+/// Every failure from a [`parse_all`](crate::parse_all) call.
+///
+/// Unlike a single [`TypedUuidError`], this reports all bad inputs at once, so a batch
+/// endpoint can return them in one response.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{} of {total} inputs failed to parse{}", .failures.len(), FirstFailure(.failures))]
+pub struct BulkParseError {
+    failures: Vec<ParseFailure>,
+    total: usize,
+}
+
+impl BulkParseError {
+    pub(crate) fn new(failures: Vec<ParseFailure>, total: usize) -> Self {
+        Self { failures, total }
+    }
+
+    /// Returns the failures, in input order.
+    pub fn failures(&self) -> &[ParseFailure] {
+        &self.failures
+    }
+
+    /// Consumes self and returns the failures, in input order.
+    pub fn into_failures(self) -> Vec<ParseFailure> {
+        self.failures
+    }
+
+    /// Returns how many inputs were attempted, including the ones that parsed.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+}
+
+/// One input rejected by [`parse_all`](crate::parse_all).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFailure {
+    /// Position of the input in the original iterator.
+    pub index: usize,
+    /// The rejected input.
+    pub input: String,
+    /// Why it was rejected.
+    pub error: TypedUuidError,
+}
+
+/// Renders `; first at index 3 ('input'): reason` (or nothing for no failures).
+struct FirstFailure<'a>(&'a [ParseFailure]);
+
+impl fmt::Display for FirstFailure<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.first() {
+            Some(first) => write!(f, "; first at index {} ('{}'): {}", first.index, first.input, first.error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Errors that can occur when registering types with a [`UuidRegistry`](crate::UuidRegistry).
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RegistryError {
+    /// Two registered enums use the same prefix, so parsing would be ambiguous.
+    #[error("prefix '{prefix}' is claimed by both {existing} and {conflicting}")]
+    DuplicatePrefix {
+        prefix: &'static str,
+        existing: &'static str,
+        conflicting: &'static str,
+    },
+
+    /// Two registered enums in the same ID space use the same discriminant, so raw UUIDs
+    /// cannot be attributed.
+    #[error("discriminant {discriminant} is claimed by both {existing} and {conflicting} in ID space '{id_space}'")]
+    DiscriminantOverlap {
+        id_space: &'static str,
+        discriminant: u8,
+        existing: &'static str,
+        conflicting: &'static str,
+    },
+}
+
+/// Errors from [`TypedUuid::new_vanity`](crate::TypedUuid::new_vanity).
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum VanityError {
+    /// The pattern contains something other than hex digits and hyphens, or is too long.
+    #[error("invalid vanity pattern '{0}': expected at most 36 hex digits and hyphens")]
+    InvalidPattern(String),
+
+    /// No ID of this variant can render with the pattern, e.g. because it contradicts
+    /// the discriminant or version digits.
+    #[error("no {type_name} ID can start with '{pattern}'")]
+    Impossible {
+        pattern: String,
+        type_name: &'static str,
+    },
+
+    /// No match was found within the attempt budget.
+    #[error("no ID starting with '{pattern}' found in {attempts} attempts")]
+    Exhausted {
+        pattern: String,
+        attempts: u32,
+    },
+}
+
+/// Errors from [`migrate::retag`](crate::migrate::retag). Nothing is returned on error,
+/// so a failed migration plan can simply be discarded.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RetagError {
+    /// The mapping returned `None` for a discriminant present in the input.
+    #[error("no new discriminant for {discriminant} (in {uuid})")]
+    Unmapped {
+        uuid: uuid::Uuid,
+        discriminant: u8,
+    },
+
+    /// Two input UUIDs would be re-tagged to the same new UUID.
+    #[error("{first} and {second} would both become {retagged}")]
+    Collision {
+        first: uuid::Uuid,
+        second: uuid::Uuid,
+        retagged: uuid::Uuid,
+    },
+}
+
+/// A [`DuplicateGuard`](crate::DuplicateGuard) has probably seen this UUID before.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("{uuid} may have been seen before (duplicate or Bloom filter false positive)")]
+pub struct SuspectedDuplicate {
+    pub uuid: uuid::Uuid,
+}
+
+/// Errors from restoring a [`GeneratorState`](crate::GeneratorState).
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum GeneratorStateError {
+    /// The state was saved by a generator with another epoch, so its timestamps mean
+    /// something else.
+    #[error("state has epoch {found}, but the generator's epoch is {expected}")]
+    EpochMismatch {
+        expected: u64,
+        found: u64,
+    },
+
+    /// The state was saved by another worker, so it says nothing about this one's sequence.
+    #[error("state is for worker {found}, but the generator is worker {expected}")]
+    WorkerMismatch {
+        expected: u16,
+        found: u16,
+    },
+
+    /// A stored state string could not be parsed.
+    #[error("invalid generator state '{0}': expected 'epoch:worker:timestamp:sequence'")]
+    InvalidFormat(String),
+}
+
+/// Errors from encoding or generating Snowflakes.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SnowflakeError {
+    /// The Snowflake has its sign bit set, so it is not a valid 63-bit Snowflake.
+    #[error("Snowflake {0} has its sign bit set")]
+    SignBitSet(u64),
+
+    /// The layout's epoch plus the Snowflake's timestamp is past the largest timestamp a
+    /// time-ordered ID can hold (2^44 - 1 milliseconds after the Unix epoch).
+    #[error("Snowflake timestamp {timestamp} plus epoch {epoch_millis} does not fit a time-ordered ID")]
+    TimestampOutOfRange {
+        epoch_millis: u64,
+        timestamp: u64,
+    },
+
+    /// The generator has issued every sequence of the last millisecond its 41-bit
+    /// timestamp can represent, so no later Snowflake exists.
+    #[error("Snowflake generator is exhausted: the timestamp and sequence are both at their maximum")]
+    Exhausted,
+}
+
+/// Returns the candidate closest to `prefix` by edit distance (counting a swap of two
+/// adjacent characters as one edit), if it is within one edit per three characters.
+/// Ties go to the earliest candidate.
+#[doc(hidden)]
+pub fn did_you_mean(prefix: &str, candidates: impl IntoIterator<Item = &'static str>) -> Option<&'static str> {
+    let prefix: Vec<char> = prefix.chars().collect();
+    candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let chars: Vec<char> = candidate.chars().collect();
+            let distance = edit_distance(&prefix, &chars);
+            let allowed = (prefix.len().max(chars.len()) / 3).max(1);
+            (distance > 0 && distance <= allowed).then_some((distance, candidate))
+        })
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Optimal string alignment distance: Levenshtein plus adjacent transpositions.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    // rows[i][j] is the distance between a[..i] and b[..j]; only three rows are live
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let substitution = previous[j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            current[j] = substitution.min(previous[j] + 1).min(current[j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Renders ` (did you mean 'a'?)` (or nothing).
+struct DidYouMean<'a>(&'a Option<&'static str>);
+
+impl fmt::Display for DidYouMean<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(suggestion) => write!(f, " (did you mean '{}'?)", suggestion),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Renders `, expected one of: a, b, c` (or nothing for an empty list).
+struct ExpectedPrefixes<'a>(&'a [&'static str]);
+
+impl fmt::Display for ExpectedPrefixes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        write!(f, ", expected one of: {}", self.0.join(", "))
+    }
 }
@@ -0,0 +1,129 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::TypedUuidError;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+
+/// The ID of the event that started a chain of events, carried by every event in it.
+///
+/// A root event is its own correlation; every event it causes, directly or not,
+/// inherits the same correlation ID. Serializes and displays like the inner ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent, bound = "")]
+pub struct CorrelationId<T: UuidType>(TypedUuid<T>);
+
+/// The ID of the event that directly caused this one.
+///
+/// A root event is its own cause. Serializes and displays like the inner ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent, bound = "")]
+pub struct CausationId<T: UuidType>(TypedUuid<T>);
+
+impl<T: UuidType> CorrelationId<T> {
+    /// Starts a new chain at the root event `event_id`.
+    pub fn new_root(event_id: TypedUuid<T>) -> Self {
+        Self(event_id)
+    }
+
+    /// Returns the correlation of an event caused by `parent`: the parent's own.
+    pub fn from_parent<P>(parent: &EventEnvelope<T, P>) -> Self {
+        parent.correlation_id
+    }
+
+    /// Returns the ID of the root event.
+    pub fn as_typed_uuid(&self) -> &TypedUuid<T> {
+        &self.0
+    }
+}
+
+impl<T: UuidType> CausationId<T> {
+    /// Returns the causation of an event caused by the event `parent_event_id`.
+    pub fn from_parent(parent_event_id: TypedUuid<T>) -> Self {
+        Self(parent_event_id)
+    }
+
+    /// Returns the ID of the causing event.
+    pub fn as_typed_uuid(&self) -> &TypedUuid<T> {
+        &self.0
+    }
+}
+
+/// An event with its ID and propagation metadata.
+///
+/// Construct root events with [`new_root`](Self::new_root) and follow-up events with
+/// [`caused_by`](Self::caused_by) so correlation and causation are always set the same
+/// way, whichever service emits the event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(serialize = "P: Serialize", deserialize = "P: Deserialize<'de>"))]
+pub struct EventEnvelope<T: UuidType, P> {
+    pub event_id: TypedUuid<T>,
+    pub correlation_id: CorrelationId<T>,
+    pub causation_id: CausationId<T>,
+    pub payload: P,
+}
+
+impl<T: UuidType, P> EventEnvelope<T, P> {
+    /// Creates an event that starts a new chain: it is its own correlation and cause.
+    pub fn new_root(variant: T, payload: P) -> Self {
+        let event_id = TypedUuid::new(variant);
+        Self {
+            event_id,
+            correlation_id: CorrelationId::new_root(event_id),
+            causation_id: CausationId::from_parent(event_id),
+            payload,
+        }
+    }
+
+    /// Creates an event caused by `parent`, inheriting its correlation.
+    pub fn caused_by<Q>(parent: &EventEnvelope<T, Q>, variant: T, payload: P) -> Self {
+        Self {
+            event_id: TypedUuid::new(variant),
+            correlation_id: CorrelationId::from_parent(parent),
+            causation_id: CausationId::from_parent(parent.event_id),
+            payload,
+        }
+    }
+
+    /// Returns `true` if this event started its chain.
+    pub fn is_root(&self) -> bool {
+        self.event_id == self.correlation_id.0
+    }
+}
+
+macro_rules! impl_id_wrapper {
+    ($name:ident) => {
+        // Manual impl: a derive would require `T: Hash`
+        impl<T: UuidType> Hash for $name<T> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.0.hash(state);
+            }
+        }
+
+        impl<T: UuidType> fmt::Display for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl<T: UuidType> FromStr for $name<T> {
+            type Err = TypedUuidError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.parse().map(Self)
+            }
+        }
+
+        impl<T: UuidType> From<$name<T>> for TypedUuid<T> {
+            fn from(id: $name<T>) -> Self {
+                id.0
+            }
+        }
+    };
+}
+
+impl_id_wrapper!(CorrelationId);
+impl_id_wrapper!(CausationId);
@@ -0,0 +1,36 @@
+//! [FlatBuffers](https://flatbuffers.dev) helpers.
+//!
+//! Store IDs as the 16-byte struct in [`SCHEMA`]: include it in your schema, write
+//! fields with `smart_uuid::Uuid::new(&id.to_wire_bytes())` (the flatc-generated
+//! constructor), and read them back with [`TypedUuid::from_flatbuffers`], which
+//! takes the generated `bytes()` accessor's result and validates the discriminant.
+//!
+//! Where a schema already uses `string` fields for IDs,
+//! [`create_flatbuffers_string`](TypedUuid::create_flatbuffers_string) writes the
+//! user-friendly form; read it with [`UserFriendlyUuid::parse_str`].
+
+use ::flatbuffers::{Allocator, Array, FlatBufferBuilder, WIPOffset};
+
+use crate::error::TypedUuidError;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::UserFriendlyUuid;
+
+/// Schema snippet defining `smart_uuid.Uuid`, a struct of 16 bytes in wire format v1.
+pub const SCHEMA: &str = include_str!("../schema/smart_uuid.fbs");
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Reads an ID from the `bytes` field of a `smart_uuid.Uuid` struct, validating the
+    /// discriminant.
+    pub fn from_flatbuffers(bytes: Array<'_, u8, 16>) -> Result<Self, TypedUuidError> {
+        Self::from_wire_bytes(bytes.into())
+    }
+
+    /// Writes this ID in user-friendly `prefix_uuid` form as a FlatBuffers string.
+    pub fn create_flatbuffers_string<'fbb, A: Allocator>(
+        &self,
+        builder: &mut FlatBufferBuilder<'fbb, A>,
+    ) -> WIPOffset<&'fbb str> {
+        builder.create_string(&UserFriendlyUuid::from(*self).hyphenated().to_string())
+    }
+}
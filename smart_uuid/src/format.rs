@@ -0,0 +1,196 @@
+use std::fmt::{self, Write};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use uuid::Uuid;
+
+use crate::traits::UuidType;
+
+/// The process-wide policy, packed by [`FormatConfig::pack`] so `Display` reads it
+/// with one atomic load.
+static GLOBAL: AtomicU32 = AtomicU32::new(FormatConfig::DEFAULT.pack());
+
+/// How [`UserFriendlyUuid`](crate::UserFriendlyUuid)'s `Display` renders IDs: the
+/// separator after the prefix, the case of the body, and its encoding.
+///
+/// Each `UuidType` may fix its own with [`UuidType::format_config`] (with the derive,
+/// `#[uuid_type(format = EXPR)]`); every other type follows the process-wide policy
+/// from [`set_global`](Self::set_global), so an operator can switch a whole fleet's
+/// log rendering from configuration:
+///
+/// ```
+/// use smart_uuid::{BodyFormat, FormatConfig};
+///
+/// let short_logs = std::env::var("ID_FORMAT").as_deref() == Ok("short");
+/// if short_logs {
+///     FormatConfig::new().with_body(BodyFormat::Base32).set_global();
+/// }
+/// ```
+///
+/// Only `Display` changes. Serialization, `String` conversions, storage and routing
+/// keys keep the canonical `{prefix}_{uuid}` form, and parsing accepts only the forms
+/// it always did, so a display-only format such as base32 or another separator does
+/// not round-trip through [`parse_str`](crate::UserFriendlyUuid::parse_str). Use
+/// [`UserFriendlyUuid::hyphenated`](crate::UserFriendlyUuid::hyphenated) wherever a
+/// string must be parsed back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormatConfig {
+    separator: char,
+    case: Case,
+    body: BodyFormat,
+}
+
+/// The case of the digits in a displayed UUID body.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Case {
+    /// `550e8400-e29b-...`, the default.
+    #[default]
+    Lower,
+    /// `550E8400-E29B-...`.
+    Upper,
+}
+
+/// The encoding of a displayed UUID body.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BodyFormat {
+    /// 36 hex digits and hyphens, the default.
+    #[default]
+    Hyphenated,
+    /// 32 hex digits without hyphens.
+    Simple,
+    /// 26 Crockford base32 digits, the same as the `serde_with` `Base32` adapter.
+    Base32,
+}
+
+impl FormatConfig {
+    /// The canonical form: `_`, lowercase, hyphenated.
+    pub const DEFAULT: Self = Self {
+        separator: '_',
+        case: Case::Lower,
+        body: BodyFormat::Hyphenated,
+    };
+
+    /// Returns the canonical form, to be adjusted with the `with_*` methods.
+    pub const fn new() -> Self {
+        Self::DEFAULT
+    }
+
+    /// Sets the character written between the prefix and the body.
+    pub const fn with_separator(mut self, separator: char) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Sets the case of the body's digits.
+    pub const fn with_case(mut self, case: Case) -> Self {
+        self.case = case;
+        self
+    }
+
+    /// Sets the encoding of the body.
+    pub const fn with_body(mut self, body: BodyFormat) -> Self {
+        self.body = body;
+        self
+    }
+
+    /// Returns the character written between the prefix and the body.
+    pub fn separator(&self) -> char {
+        self.separator
+    }
+
+    /// Returns the case of the body's digits.
+    pub fn case(&self) -> Case {
+        self.case
+    }
+
+    /// Returns the encoding of the body.
+    pub fn body(&self) -> BodyFormat {
+        self.body
+    }
+
+    /// Makes this the policy for every type without its own.
+    pub fn set_global(self) {
+        GLOBAL.store(self.pack(), Ordering::Relaxed);
+    }
+
+    /// Returns the process-wide policy, [`DEFAULT`](Self::DEFAULT) unless
+    /// [`set_global`](Self::set_global) was called.
+    pub fn global() -> Self {
+        Self::unpack(GLOBAL.load(Ordering::Relaxed))
+    }
+
+    /// Restores the process-wide policy to [`DEFAULT`](Self::DEFAULT).
+    pub fn reset_global() {
+        Self::DEFAULT.set_global();
+    }
+
+    /// Returns the policy `Display` uses for `T`: its own if it has one, otherwise
+    /// the process-wide one.
+    pub fn for_type<T: UuidType>() -> Self {
+        T::format_config().unwrap_or_else(Self::global)
+    }
+
+    /// Writes `{prefix}{separator}{body}` under this policy.
+    pub(crate) fn write(&self, f: &mut fmt::Formatter<'_>, prefix: &str, uuid: &Uuid) -> fmt::Result {
+        f.write_str(prefix)?;
+        f.write_char(self.separator)?;
+        match (self.body, self.case) {
+            (BodyFormat::Hyphenated, Case::Lower) => write!(f, "{:x}", uuid.hyphenated()),
+            (BodyFormat::Hyphenated, Case::Upper) => write!(f, "{:X}", uuid.hyphenated()),
+            (BodyFormat::Simple, Case::Lower) => write!(f, "{:x}", uuid.simple()),
+            (BodyFormat::Simple, Case::Upper) => write!(f, "{:X}", uuid.simple()),
+            (BodyFormat::Base32, case) => {
+                let mut encoded = encode_base32(uuid.as_u128());
+                if case == Case::Upper {
+                    encoded.make_ascii_uppercase();
+                }
+                f.write_str(std::str::from_utf8(&encoded).expect("base32 output is ASCII"))
+            }
+        }
+    }
+
+    /// Packs the policy into 32 bits: the separator in the low 21, then one bit of
+    /// case and two of body encoding.
+    const fn pack(self) -> u32 {
+        let case = match self.case {
+            Case::Lower => 0,
+            Case::Upper => 1,
+        };
+        let body = match self.body {
+            BodyFormat::Hyphenated => 0,
+            BodyFormat::Simple => 1,
+            BodyFormat::Base32 => 2,
+        };
+        self.separator as u32 | case << 21 | body << 22
+    }
+
+    fn unpack(bits: u32) -> Self {
+        Self {
+            separator: char::from_u32(bits & 0x1f_ffff).expect("packed separator is a char"),
+            case: if bits >> 21 & 1 == 0 { Case::Lower } else { Case::Upper },
+            body: match bits >> 22 & 0b11 {
+                0 => BodyFormat::Hyphenated,
+                1 => BodyFormat::Simple,
+                _ => BodyFormat::Base32,
+            },
+        }
+    }
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+pub(crate) const CROCKFORD: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+pub(crate) const BASE32_LEN: usize = 26;
+
+/// 128 bits in 26 five-bit digits; the first digit only carries the top 3 bits.
+pub(crate) fn encode_base32(mut value: u128) -> [u8; BASE32_LEN] {
+    let mut out = [0u8; BASE32_LEN];
+    for digit in out.iter_mut().rev() {
+        *digit = CROCKFORD[(value & 0x1f) as usize];
+        value >>= 5;
+    }
+    out
+}
@@ -0,0 +1,154 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::clock::{Clock, SystemClock};
+use crate::snowflake::SnowflakeGenerator;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::UserFriendlyUuid;
+
+/// Largest counter value that fits in the 48-bit node field of a sequential ID.
+const MAX_SEQUENCE: u64 = (1 << 48) - 1;
+
+/// A strategy for minting new IDs, for code that should not decide how IDs are made.
+///
+/// Take a generator where you would call [`TypedUuid::new`], and the caller picks the
+/// scheme: [`RandomGenerator`] in production, [`OrderedGenerator`] for index-friendly
+/// keys, [`SnowflakeGenerator`] for Snowflake IDs, or [`SequentialGenerator`] for
+/// predictable IDs in tests.
+///
+/// ```
+/// use smart_uuid::{SequentialGenerator, TypedUuid, UuidGenerator, UuidType};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+/// enum OrderType { Order }
+///
+/// struct OrderService<G: UuidGenerator> {
+///     ids: G,
+/// }
+///
+/// impl<G: UuidGenerator> OrderService<G> {
+///     fn place(&self) -> TypedUuid<OrderType> {
+///         TypedUuid::new_with(&self.ids, OrderType::Order)
+///     }
+/// }
+///
+/// let service = OrderService { ids: SequentialGenerator::new() };
+/// assert_eq!(service.place().to_string(), "00000000-0000-8000-8000-000000000001");
+/// ```
+///
+/// `generate` is generic over the ID type, so the trait is not dyn-compatible; share
+/// one generator through `&G` or `Arc<G>`, which implement it too.
+pub trait UuidGenerator {
+    /// Returns a new ID of `variant`.
+    fn generate<T: UuidType>(&self, variant: T) -> TypedUuid<T>;
+}
+
+impl<G: UuidGenerator + ?Sized> UuidGenerator for &G {
+    fn generate<T: UuidType>(&self, variant: T) -> TypedUuid<T> {
+        (**self).generate(variant)
+    }
+}
+
+impl<G: UuidGenerator + ?Sized> UuidGenerator for Arc<G> {
+    fn generate<T: UuidType>(&self, variant: T) -> TypedUuid<T> {
+        (**self).generate(variant)
+    }
+}
+
+/// Generates random v8 IDs, exactly like [`TypedUuid::new`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RandomGenerator;
+
+impl UuidGenerator for RandomGenerator {
+    fn generate<T: UuidType>(&self, variant: T) -> TypedUuid<T> {
+        TypedUuid::new(variant)
+    }
+}
+
+/// Generates time-ordered IDs stamped with `clock`'s time, like
+/// [`TypedUuid::new_ordered_with_clock`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OrderedGenerator<C: Clock = SystemClock> {
+    clock: C,
+}
+
+impl OrderedGenerator {
+    /// Creates a generator reading the system clock.
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+}
+
+impl<C: Clock> OrderedGenerator<C> {
+    /// Creates a generator reading the time from `clock`.
+    pub fn with_clock(clock: C) -> Self {
+        Self { clock }
+    }
+}
+
+impl<C: Clock> UuidGenerator for OrderedGenerator<C> {
+    fn generate<T: UuidType>(&self, variant: T) -> TypedUuid<T> {
+        TypedUuid::new_ordered_with_clock(variant, &self.clock)
+    }
+}
+
+/// Generates IDs from a counter, so every run mints the same IDs in the same order.
+///
+/// The counter is shared by all variants and written into the last group of the UUID,
+/// like `FixtureFactory`'s (`test-util` feature), which instead counts each
+/// variant separately. IDs are only unique within one generator: use it for tests and
+/// reproducible runs, never for IDs that leave them.
+#[derive(Debug, Default)]
+pub struct SequentialGenerator {
+    next: AtomicU64,
+}
+
+impl SequentialGenerator {
+    /// Creates a generator whose first ID has counter 1.
+    pub fn new() -> Self {
+        Self::starting_at(1)
+    }
+
+    /// Creates a generator whose first ID has counter `first`.
+    pub fn starting_at(first: u64) -> Self {
+        Self {
+            next: AtomicU64::new(first),
+        }
+    }
+}
+
+impl UuidGenerator for SequentialGenerator {
+    /// # Panics
+    /// Panics once the counter exceeds 2^48 - 1.
+    fn generate<T: UuidType>(&self, variant: T) -> TypedUuid<T> {
+        let counter = self.next.fetch_add(1, Ordering::Relaxed);
+        assert!(counter <= MAX_SEQUENCE, "SequentialGenerator counter overflow");
+
+        let mut bytes = [0u8; 16];
+        bytes[10..].copy_from_slice(&counter.to_be_bytes()[2..]);
+        TypedUuid::from_v8_bytes(variant, bytes)
+    }
+}
+
+impl<C: Clock> UuidGenerator for SnowflakeGenerator<C> {
+    /// # Panics
+    /// Panics where [`SnowflakeGenerator::generate`] would return an error.
+    fn generate<T: UuidType>(&self, variant: T) -> TypedUuid<T> {
+        SnowflakeGenerator::generate(self, variant).unwrap_or_else(|err| panic!("{}", err))
+    }
+}
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Creates a new TypedUuid with `generator`.
+    pub fn new_with(generator: &impl UuidGenerator, variant: T) -> Self {
+        generator.generate(variant)
+    }
+}
+
+impl<T: UuidType> UserFriendlyUuid<T> {
+    /// Creates a new UserFriendlyUuid with `generator`.
+    pub fn new_with(generator: &impl UuidGenerator, variant: T) -> Self {
+        generator.generate(variant).into()
+    }
+}
@@ -0,0 +1,162 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::GeneratorStateError;
+
+/// A snapshot of a [`SnowflakeGenerator`](crate::SnowflakeGenerator)'s last timestamp
+/// and sequence, to carry across restarts.
+///
+/// A restarted generator otherwise starts from the clock alone: if the clock reads
+/// earlier than before the restart (NTP step, VM migration), or the process restarts
+/// within the same millisecond, it can reissue Snowflakes. Restoring the state with
+/// [`SnowflakeGenerator::restore`](crate::SnowflakeGenerator::restore) makes it continue
+/// strictly after the last one.
+///
+/// Serializes with serde, and displays and parses as `epoch:worker:timestamp:sequence`
+/// for stores holding plain strings. See [`StateStore`] for saving it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GeneratorState {
+    epoch_millis: u64,
+    worker_id: u16,
+    timestamp: u64,
+    sequence: u16,
+}
+
+impl GeneratorState {
+    pub(crate) fn new(epoch_millis: u64, worker_id: u16, timestamp: u64, sequence: u16) -> Self {
+        Self {
+            epoch_millis,
+            worker_id,
+            timestamp,
+            sequence,
+        }
+    }
+
+    /// Returns the epoch of the generator that saved the state, in Unix milliseconds.
+    pub fn epoch_millis(&self) -> u64 {
+        self.epoch_millis
+    }
+
+    /// Returns the worker ID of the generator that saved the state.
+    pub fn worker_id(&self) -> u16 {
+        self.worker_id
+    }
+
+    /// Returns the timestamp of the last Snowflake, in milliseconds since the epoch.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Returns the sequence number of the last Snowflake.
+    pub fn sequence(&self) -> u16 {
+        self.sequence
+    }
+}
+
+impl fmt::Display for GeneratorState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}:{}", self.epoch_millis, self.worker_id, self.timestamp, self.sequence)
+    }
+}
+
+impl FromStr for GeneratorState {
+    type Err = GeneratorStateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GeneratorStateError::InvalidFormat(s.to_string());
+        let mut fields = s.trim().split(':');
+        let mut next = || fields.next().ok_or_else(invalid);
+
+        let state = Self {
+            epoch_millis: next()?.parse().map_err(|_| invalid())?,
+            worker_id: next()?.parse().map_err(|_| invalid())?,
+            timestamp: next()?.parse().map_err(|_| invalid())?,
+            sequence: next()?.parse().map_err(|_| invalid())?,
+        };
+        if fields.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(state)
+    }
+}
+
+/// Somewhere to keep a [`GeneratorState`] between runs.
+///
+/// Load and restore the state at startup, and save it at shutdown and periodically
+/// while running. [`FileStateStore`] covers a local file; a Redis store is a few lines:
+///
+/// ```text
+/// struct RedisStateStore { client: redis::Client, key: String }
+///
+/// impl StateStore for RedisStateStore {
+///     type Error = redis::RedisError;
+///
+///     fn load(&self) -> Result<Option<GeneratorState>, Self::Error> {
+///         let value: Option<String> = self.client.get_connection()?.get(&self.key)?;
+///         Ok(value.and_then(|s| s.parse().ok()))
+///     }
+///
+///     fn save(&self, state: &GeneratorState) -> Result<(), Self::Error> {
+///         self.client.get_connection()?.set(&self.key, state.to_string())
+///     }
+/// }
+/// ```
+///
+/// Key the state by worker ID when several workers share a store.
+pub trait StateStore {
+    /// The error returned when the store cannot be read or written.
+    type Error;
+
+    /// Returns the saved state, or `None` if nothing was saved yet.
+    fn load(&self) -> Result<Option<GeneratorState>, Self::Error>;
+
+    /// Saves `state`, replacing any earlier one.
+    fn save(&self, state: &GeneratorState) -> Result<(), Self::Error>;
+}
+
+/// A [`StateStore`] keeping the state as one line of text in a file.
+///
+/// Saves write a sibling `.tmp` file and rename it over the old one, so a crash
+/// mid-save leaves the previous state intact.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStateStore {
+    path: PathBuf,
+}
+
+impl FileStateStore {
+    /// Creates a store at `path`. Nothing is read or written until used.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Returns the path of the state file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl StateStore for FileStateStore {
+    type Error = io::Error;
+
+    fn load(&self) -> io::Result<Option<GeneratorState>> {
+        match fs::read_to_string(&self.path) {
+            Ok(contents) => contents
+                .parse()
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn save(&self, state: &GeneratorState) -> io::Result<()> {
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, format!("{}\n", state))?;
+        fs::rename(&tmp, &self.path)
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::{btree_map, BTreeMap};
+use std::marker::PhantomData;
+
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::UserFriendlyUuid;
+
+/// A typed ID whose variant can be read, so iterators of it can be grouped with
+/// [`TypedUuidIterExt`].
+///
+/// Implemented for [`TypedUuid`], [`UserFriendlyUuid`] and references to either.
+pub trait HasVariant<T: UuidType> {
+    /// Returns the enum variant encoded in the ID.
+    fn variant_type(&self) -> T;
+}
+
+impl<T: UuidType> HasVariant<T> for TypedUuid<T> {
+    fn variant_type(&self) -> T {
+        TypedUuid::variant_type(self)
+    }
+}
+
+impl<T: UuidType> HasVariant<T> for UserFriendlyUuid<T> {
+    fn variant_type(&self) -> T {
+        UserFriendlyUuid::variant_type(self)
+    }
+}
+
+impl<T: UuidType, I: HasVariant<T> + ?Sized> HasVariant<T> for &I {
+    fn variant_type(&self) -> T {
+        (**self).variant_type()
+    }
+}
+
+/// A map from variants to values, iterated in discriminant order.
+///
+/// Returned by [`TypedUuidIterExt::group_by_variant`] and
+/// [`count_by_variant`](TypedUuidIterExt::count_by_variant). Keyed by discriminant, so
+/// `T` needs neither `Hash` nor `Ord`. Variants with no IDs are absent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantMap<T: UuidType, V> {
+    entries: BTreeMap<u8, V>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: UuidType, V> VariantMap<T, V> {
+    /// Returns the value for `variant`, if any.
+    pub fn get(&self, variant: T) -> Option<&V> {
+        self.entries.get(&variant.discriminant())
+    }
+
+    /// Returns the variants present, in discriminant order.
+    pub fn variants(&self) -> impl Iterator<Item = T> + '_ {
+        self.entries.keys().map(|&d| variant_of(d))
+    }
+
+    /// Iterates over variants and their values, in discriminant order.
+    pub fn iter(&self) -> impl Iterator<Item = (T, &V)> {
+        self.entries.iter().map(|(&d, value)| (variant_of(d), value))
+    }
+
+    /// Returns the number of variants present.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no variant is present.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn entry_or_default(&mut self, variant: T) -> &mut V
+    where
+        V: Default,
+    {
+        self.entries.entry(variant.discriminant()).or_default()
+    }
+}
+
+impl<T: UuidType, V> Default for VariantMap<T, V> {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: UuidType, V> IntoIterator for VariantMap<T, V> {
+    type Item = (T, V);
+    type IntoIter = std::iter::Map<btree_map::IntoIter<u8, V>, fn((u8, V)) -> (T, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter().map(|(d, value)| (variant_of(d), value))
+    }
+}
+
+fn variant_of<T: UuidType>(discriminant: u8) -> T {
+    T::from_discriminant(discriminant).expect("VariantMap keys are valid discriminants")
+}
+
+/// Grouping and filtering for iterators of typed IDs.
+///
+/// ```
+/// use smart_uuid::{TypedUuid, TypedUuidIterExt, UuidType};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+/// enum UserType { Retail, Business }
+///
+/// let ids = vec![
+///     TypedUuid::new(UserType::Retail),
+///     TypedUuid::new(UserType::Business),
+///     TypedUuid::new(UserType::Retail),
+/// ];
+/// let counts = ids.iter().count_by_variant();
+/// assert_eq!(counts.get(UserType::Retail), Some(&2));
+///
+/// let by_variant = ids.iter().group_by_variant();
+/// assert_eq!(by_variant.get(UserType::Business).map(Vec::len), Some(1));
+/// ```
+pub trait TypedUuidIterExt<T: UuidType>: Iterator + Sized
+where
+    Self::Item: HasVariant<T>,
+{
+    /// Collects the IDs into one `Vec` per variant, keeping their order.
+    fn group_by_variant(self) -> VariantMap<T, Vec<Self::Item>> {
+        let mut groups: VariantMap<T, Vec<Self::Item>> = VariantMap::default();
+        for id in self {
+            groups.entry_or_default(id.variant_type()).push(id);
+        }
+        groups
+    }
+
+    /// Counts the IDs of each variant.
+    fn count_by_variant(self) -> VariantMap<T, usize> {
+        let mut counts = VariantMap::default();
+        for id in self {
+            *counts.entry_or_default(id.variant_type()) += 1;
+        }
+        counts
+    }
+
+    /// Keeps only the IDs of `variant`.
+    fn filter_variant(self, variant: T) -> FilterVariant<Self, T> {
+        FilterVariant { iter: self, variant }
+    }
+}
+
+impl<T: UuidType, I> TypedUuidIterExt<T> for I
+where
+    I: Iterator,
+    I::Item: HasVariant<T>,
+{
+}
+
+/// Iterator returned by [`TypedUuidIterExt::filter_variant`].
+#[derive(Debug, Clone)]
+pub struct FilterVariant<I, T> {
+    iter: I,
+    variant: T,
+}
+
+impl<T: UuidType, I> Iterator for FilterVariant<I, T>
+where
+    I: Iterator,
+    I::Item: HasVariant<T>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let variant = self.variant;
+        self.iter.find(|id| id.variant_type() == variant)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
@@ -0,0 +1,42 @@
+use uuid::Uuid;
+
+use crate::error::TypedUuidError;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+
+/// Microsoft GUID interop.
+///
+/// A GUID stores its first three fields little-endian, so the 16 bytes .NET's
+/// `Guid.ToByteArray()` returns, or MSSQL keeps in a `uniqueidentifier`, are this
+/// crate's bytes with groups 1 to 3 reversed. The discriminant then sits in byte 3
+/// rather than byte 0; these methods swap the groups back before validating it.
+impl<T: UuidType> TypedUuid<T> {
+    /// Returns the bytes in GUID order, for `new Guid(bytes)` in .NET or a
+    /// `uniqueidentifier` parameter sent as binary.
+    pub fn to_bytes_le(&self) -> [u8; 16] {
+        self.as_uuid().to_bytes_le()
+    }
+
+    /// Creates a TypedUuid from bytes in GUID order, as returned by
+    /// `Guid.ToByteArray()`, validating the discriminant.
+    pub fn from_bytes_le(bytes: [u8; 16]) -> Result<Self, TypedUuidError> {
+        Self::from_uuid(Uuid::from_bytes_le(bytes))
+    }
+
+    /// Parses a GUID string that .NET built from this crate's bytes read as a GUID,
+    /// e.g. `new Guid(idBytes).ToString()`, or an MSSQL `uniqueidentifier` filled
+    /// with those bytes, then cast to text.
+    ///
+    /// Such strings show the first three groups byte-reversed. Braces and case are
+    /// accepted as by [`Uuid::parse_str`]; strings .NET got from our own string form
+    /// are already correct and parse with [`FromStr`](std::str::FromStr).
+    pub fn parse_guid(s: &str) -> Result<Self, TypedUuidError> {
+        Self::from_bytes(Uuid::parse_str(s)?.to_bytes_le())
+    }
+
+    /// Returns the string .NET shows for this ID after reading its bytes as a GUID,
+    /// the inverse of [`parse_guid`](Self::parse_guid).
+    pub fn to_guid_string(&self) -> String {
+        Uuid::from_bytes_le(*self.as_bytes()).to_string()
+    }
+}
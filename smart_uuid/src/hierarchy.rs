@@ -0,0 +1,29 @@
+use uuid::Uuid;
+
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Derives a deterministic child ID from this (parent) ID.
+    ///
+    /// The child's random bits are a SHA-1 name-based hash (as in UUID v5) of the
+    /// parent UUID, the child's discriminant and `salt`; byte 0 then carries the child
+    /// discriminant as usual. The same parent, child variant and salt always produce
+    /// the same child, so child IDs can be reconstructed without a lookup table.
+    ///
+    /// Use distinct salts (e.g., an attachment index) to derive several children of
+    /// the same variant. The child may belong to a different enum than the parent.
+    pub fn derive_child<C: UuidType>(&self, child_variant: C, salt: &[u8]) -> TypedUuid<C> {
+        let mut name = Vec::with_capacity(1 + salt.len());
+        name.push(child_variant.discriminant());
+        name.extend_from_slice(salt);
+
+        let hashed = Uuid::new_v5(self.as_uuid(), &name);
+        TypedUuid::from_v8_bytes(child_variant, *hashed.as_bytes())
+    }
+
+    /// Returns `true` if this ID is the child of `parent` derived with `salt`.
+    pub fn is_child_of<P: UuidType>(&self, parent: &TypedUuid<P>, salt: &[u8]) -> bool {
+        parent.derive_child(self.variant_type(), salt) == *self
+    }
+}
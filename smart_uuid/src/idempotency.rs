@@ -0,0 +1,181 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::clock::{Clock, SystemClock};
+use crate::error::TypedUuidError;
+use crate::routing::fnv1a;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::UserFriendlyUuid;
+
+/// An idempotency key: a typed ID plus an expiry and, optionally, a hash of the
+/// request it was issued for.
+///
+/// Formats as `{prefix}_{uuid}.{expiry}` or `{prefix}_{uuid}.{expiry}.{hash}`, where
+/// `expiry` is Unix seconds in decimal and `hash` is 16 hex digits. A server can reject
+/// expired keys, keys of the wrong type and keys replayed with a different request body
+/// from the string alone, before touching storage.
+///
+/// The request hash is 64-bit FNV-1a: it catches accidental reuse of a key with another
+/// request, not a client deliberately forging a collision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdempotencyKey<T: UuidType> {
+    id: TypedUuid<T>,
+    expires_at: u64,
+    request_hash: Option<u64>,
+}
+
+impl<T: UuidType> IdempotencyKey<T> {
+    /// Creates a key valid for `ttl`, usable with any request.
+    pub fn new(variant: T, ttl: Duration) -> Self {
+        Self::new_with_clock(variant, ttl, &SystemClock, None)
+    }
+
+    /// Creates a key valid for `ttl` that only [matches](Self::matches_request) `request`.
+    pub fn bound_to(variant: T, ttl: Duration, request: &[u8]) -> Self {
+        Self::new_with_clock(variant, ttl, &SystemClock, Some(request))
+    }
+
+    /// Creates a key valid for `ttl` from `clock`'s current time, optionally bound to a request.
+    pub fn new_with_clock(variant: T, ttl: Duration, clock: &impl Clock, request: Option<&[u8]>) -> Self {
+        Self::new_at(variant, clock.now() + ttl, request)
+    }
+
+    /// Creates a key expiring at `expires_at` (truncated to the second), optionally bound
+    /// to a request.
+    pub fn new_at(variant: T, expires_at: SystemTime, request: Option<&[u8]>) -> Self {
+        Self {
+            id: TypedUuid::new(variant),
+            expires_at: expires_at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+            request_hash: request.map(fnv1a),
+        }
+    }
+
+    /// Returns the key's ID.
+    pub fn id(&self) -> &TypedUuid<T> {
+        &self.id
+    }
+
+    /// Returns when the key expires.
+    pub fn expires_at(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.expires_at)
+    }
+
+    /// Returns `true` if the key has expired at `now`.
+    pub fn is_expired_at(&self, now: SystemTime) -> bool {
+        now >= self.expires_at()
+    }
+
+    /// Returns `true` if the key has expired.
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_with_clock(&SystemClock)
+    }
+
+    /// Returns `true` if the key has expired at `clock`'s current time.
+    pub fn is_expired_with_clock(&self, clock: &impl Clock) -> bool {
+        self.is_expired_at(clock.now())
+    }
+
+    /// Returns `true` if the key is bound to a request.
+    pub fn is_bound(&self) -> bool {
+        self.request_hash.is_some()
+    }
+
+    /// Returns `true` if `request` may be served under this key: the key is unbound,
+    /// or was bound to a request with the same bytes.
+    pub fn matches_request(&self, request: &[u8]) -> bool {
+        self.request_hash.is_none_or(|hash| hash == fnv1a(request))
+    }
+}
+
+impl<T: UuidType> Hash for IdempotencyKey<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.expires_at.hash(state);
+        self.request_hash.hash(state);
+    }
+}
+
+impl<T: UuidType> fmt::Display for IdempotencyKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", UserFriendlyUuid::from(self.id).hyphenated(), self.expires_at)?;
+        if let Some(hash) = self.request_hash {
+            write!(f, ".{:016x}", hash)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: UuidType> FromStr for IdempotencyKey<T> {
+    type Err = TypedUuidError;
+
+    /// Parses the key's structure. Expiry is not checked; call
+    /// [`is_expired`](Self::is_expired) for that. An expiry too far in the future for
+    /// `SystemTime` to represent is rejected as malformed.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            TypedUuidError::InvalidFormat(
+                "expected idempotency key 'prefix_uuid.expiry' or 'prefix_uuid.expiry.hash'".to_string(),
+            )
+        };
+
+        // Everything after the last '_' is the UUID body and our fields; none contain '_'
+        let body_start = s.rfind('_').ok_or_else(invalid)?;
+        let mut fields = s[body_start..].split('.');
+        let id_len = body_start + fields.next().ok_or_else(invalid)?.len();
+
+        let expires_at = fields
+            .next()
+            .and_then(parse_decimal)
+            // `expires_at()` must not overflow on a key a client made up
+            .filter(|&secs| UNIX_EPOCH.checked_add(Duration::from_secs(secs)).is_some())
+            .ok_or_else(invalid)?;
+        let request_hash = match fields.next() {
+            None => None,
+            Some(hex) if hex.len() == 16 && hex.bytes().all(|b| b.is_ascii_hexdigit()) => {
+                Some(u64::from_str_radix(hex, 16).map_err(|_| invalid())?)
+            }
+            Some(_) => return Err(invalid()),
+        };
+        if fields.next().is_some() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            id: UserFriendlyUuid::parse_str(&s[..id_len])?.into_typed_uuid(),
+            expires_at,
+            request_hash,
+        })
+    }
+}
+
+/// Parses ASCII digits only, unlike `u64::from_str`, which also accepts a leading `+`.
+fn parse_decimal(s: &str) -> Option<u64> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+impl<T: UuidType> Serialize for IdempotencyKey<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, T: UuidType> Deserialize<'de> for IdempotencyKey<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,151 @@
+use uuid::{Uuid, Variant};
+
+use crate::error::TypedUuidError;
+use crate::migrate;
+use crate::ordered;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+
+/// How the bits of a typed UUID are laid out, as reported by [`TypedUuid::inspect`].
+///
+/// Every layout keeps the discriminant in byte 0, so all of them parse and round-trip
+/// the same way; the layout only affects the version and variant bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Layout {
+    /// The default: a random RFC 9562 version 8 (custom) UUID, as made by [`TypedUuid::new`].
+    ///
+    /// IDs of enums with a [`Migrator`](crate::Migrator) have the variant field `110`
+    /// instead of `10x`, marking byte 1 as their schema version; see [`crate::migrate`].
+    /// `110` is not RFC 9562's variant, so validators that check it reject those IDs.
+    V8,
+    /// An ID carrying its creation time, as made by [`TypedUuid::new_ordered`].
+    ///
+    /// Byte 0 holds the discriminant, byte 1 the schema version (or 0), and the next
+    /// 44 bits (skipping the version nibble) Unix milliseconds. The version nibble is 8,
+    /// but the variant field in byte 8 is `111`, RFC 9562's reserved variant, so these
+    /// are not valid version 8 UUIDs: validators that check the variant reject them.
+    /// No [`V8`](Self::V8) ID carries that variant, not even one created before this
+    /// layout existed; the rest is random.
+    TimeOrdered,
+    /// An RFC 9562 version 4 UUID, as made by [`TypedUuid::new_v4_compatible`], for
+    /// systems that reject any other version.
+    V4Compatible,
+    /// No RFC version or variant bits, as made by [`TypedUuid::new_opaque`]: all 120
+    /// bits after the discriminant are random.
+    ///
+    /// Detected by the variant bits not being RFC 9562's. About one opaque ID in four
+    /// has those bits set by chance, and a few more carry the time-ordered or versioned
+    /// marker; they are reported as another layout instead.
+    Opaque,
+    /// Any other RFC 9562 UUID with a valid discriminant, e.g. one adopted with `from_uuid`.
+    Other,
+}
+
+impl Layout {
+    /// Detects the layout from the version and variant bits.
+    pub fn of(uuid: &Uuid) -> Self {
+        if ordered::has_time_ordered_marker(uuid) {
+            return Self::TimeOrdered;
+        }
+        if uuid.get_version_num() == 8 && uuid.as_bytes()[8] & 0xE0 == migrate::VERSIONED_MARKER {
+            return Self::V8;
+        }
+        if uuid.get_variant() != Variant::RFC4122 {
+            return Self::Opaque;
+        }
+        match uuid.get_version_num() {
+            8 => Self::V8,
+            4 => Self::V4Compatible,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// A breakdown of a typed UUID, returned by [`TypedUuid::inspect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Inspection<T: UuidType> {
+    variant: T,
+    layout: Layout,
+}
+
+impl<T: UuidType> Inspection<T> {
+    /// Returns the variant encoded in byte 0.
+    pub fn variant_type(&self) -> T {
+        self.variant
+    }
+
+    /// Returns the discriminant byte.
+    pub fn discriminant(&self) -> u8 {
+        self.variant.discriminant()
+    }
+
+    /// Returns the detected layout.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+}
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Creates a new TypedUuid that is also a valid version 4 UUID.
+    ///
+    /// Version 4 treats byte 0 as random, so the discriminant still fits; only the
+    /// version nibble differs from [`new`](Self::new). Use this for downstream systems
+    /// that reject any UUID whose version isn't 4. Parsing is unchanged.
+    pub fn new_v4_compatible(variant: T) -> Self {
+        let mut bytes: [u8; 16] = rand::random();
+        migrate::stamp(variant, &mut bytes);
+
+        let uuid = uuid::Builder::from_random_bytes(bytes).into_uuid();
+
+        // SAFETY: byte 0 was just set to the variant's discriminant, byte 1 to the current schema
+        unsafe { Self::from_uuid_unchecked(uuid) }
+    }
+
+    /// Creates a new TypedUuid without RFC version or variant bits.
+    ///
+    /// Treats the value as an opaque 128-bit identifier: every bit but the
+    /// discriminant is random, recovering the 6 bits [`new`](Self::new) spends on
+    /// version and variant. Only use this for keys that never reach systems that
+    /// validate UUIDs. Parsing is unchanged.
+    ///
+    /// For an enum with a [`Migrator`](crate::Migrator), the one random pattern in 64
+    /// that looks like an unmarked v8 ID gets the versioned marker, so it is not read
+    /// back as written before the opt-in; see [`crate::migrate`].
+    pub fn new_opaque(variant: T) -> Self {
+        let mut bytes: [u8; 16] = rand::random();
+        migrate::stamp(variant, &mut bytes);
+        migrate::mark_versioned::<T>(&mut bytes);
+
+        // SAFETY: byte 0 was just set to the variant's discriminant, byte 1 to the current schema
+        unsafe { Self::from_uuid_unchecked(Uuid::from_bytes(bytes)) }
+    }
+
+    /// Like [`from_bytes`](Self::from_bytes), but also requires the version and
+    /// variant bits to match `layout`, for protocols that only ever carry one kind of ID.
+    ///
+    /// Fails with [`TypedUuidError::InvalidFormat`] if the bytes have another layout.
+    pub fn from_bytes_with_layout(bytes: [u8; 16], layout: Layout) -> Result<Self, TypedUuidError> {
+        let found = Layout::of(&Uuid::from_bytes(bytes));
+        if found != layout {
+            return Err(TypedUuidError::InvalidFormat(format!(
+                "expected layout {:?}, found {:?}",
+                layout, found
+            )));
+        }
+        Self::from_bytes(bytes)
+    }
+
+    /// Returns the layout this UUID was generated with.
+    pub fn layout(&self) -> Layout {
+        Layout::of(self.as_uuid())
+    }
+
+    /// Breaks this UUID down into its variant and layout, for debugging and tooling.
+    pub fn inspect(&self) -> Inspection<T> {
+        Inspection {
+            variant: self.variant_type(),
+            layout: self.layout(),
+        }
+    }
+}
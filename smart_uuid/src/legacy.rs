@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::TypedUuidError;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+
+/// The longest random part accepted in a legacy ID, in characters.
+const MAX_RANDOM_LEN: usize = 64;
+
+/// A Stripe-style `{prefix}_{base62}` ID with no UUID behind it, e.g. `cus_NffrFeUfNV2Hib`.
+///
+/// The prefix is validated against the same enum as [`UserFriendlyUuid`](crate::UserFriendlyUuid),
+/// so one `UuidType` describes both formats while IDs are migrated. The random part is
+/// kept verbatim; [`LegacyIdMap`] mints the `TypedUuid` that replaces it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegacyPrefixedId<T: UuidType> {
+    variant: T,
+    random: String,
+}
+
+impl<T: UuidType> LegacyPrefixedId<T> {
+    /// Parses a legacy ID, checking the prefix and that the rest is 1 to 64 base62 characters.
+    pub fn parse_str(s: &str) -> Result<Self, TypedUuidError> {
+        // Base62 has no '_', so the last one separates the prefix
+        let (prefix, random) = s.rsplit_once('_').ok_or_else(|| {
+            TypedUuidError::InvalidFormat("expected legacy ID 'prefix_random'".to_string())
+        })?;
+        let is_base62 = random.bytes().all(|b| b.is_ascii_alphanumeric());
+        if random.is_empty() || random.len() > MAX_RANDOM_LEN || !is_base62 {
+            return Err(TypedUuidError::InvalidFormat(format!(
+                "legacy ID random part must be 1 to {} base62 characters",
+                MAX_RANDOM_LEN
+            )));
+        }
+        let variant = T::from_prefix(prefix).ok_or_else(|| TypedUuidError::unknown_prefix::<T>(prefix))?;
+        Ok(Self {
+            variant,
+            random: random.to_string(),
+        })
+    }
+
+    /// Returns the variant named by the prefix.
+    pub fn variant_type(&self) -> T {
+        self.variant
+    }
+
+    /// Returns the prefix string for this ID's variant.
+    pub fn prefix(&self) -> &'static str {
+        self.variant.prefix()
+    }
+
+    /// Returns the base62 part after the prefix.
+    pub fn random(&self) -> &str {
+        &self.random
+    }
+}
+
+// Manual impl: a derive would require `T: Hash`
+impl<T: UuidType> Hash for LegacyPrefixedId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.variant.discriminant().hash(state);
+        self.random.hash(state);
+    }
+}
+
+impl<T: UuidType> fmt::Display for LegacyPrefixedId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}_{}", self.prefix(), self.random)
+    }
+}
+
+impl<T: UuidType> FromStr for LegacyPrefixedId<T> {
+    type Err = TypedUuidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_str(s)
+    }
+}
+
+impl<T: UuidType> Serialize for LegacyPrefixedId<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, T: UuidType> Deserialize<'de> for LegacyPrefixedId<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Self::parse_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Replacements minted for legacy IDs, in both directions.
+///
+/// [`replace`](Self::replace) is idempotent: a legacy ID seen again gets the same
+/// `TypedUuid`. Persist the pairs from [`iter`](Self::iter) and reload them with
+/// [`insert`](Self::insert) to keep the mapping stable across runs.
+#[derive(Debug, Clone)]
+pub struct LegacyIdMap<T: UuidType> {
+    forward: HashMap<LegacyPrefixedId<T>, TypedUuid<T>>,
+    backward: HashMap<TypedUuid<T>, LegacyPrefixedId<T>>,
+}
+
+impl<T: UuidType> LegacyIdMap<T> {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self {
+            forward: HashMap::new(),
+            backward: HashMap::new(),
+        }
+    }
+
+    /// Returns the replacement for `legacy`, minting and recording one of the same
+    /// variant if there is none yet.
+    pub fn replace(&mut self, legacy: &LegacyPrefixedId<T>) -> TypedUuid<T> {
+        if let Some(id) = self.forward.get(legacy) {
+            return *id;
+        }
+        let id = TypedUuid::new(legacy.variant);
+        self.forward.insert(legacy.clone(), id);
+        self.backward.insert(id, legacy.clone());
+        id
+    }
+
+    /// Records an existing pair, e.g. loaded from storage.
+    ///
+    /// Fails with [`TypedUuidError::InvalidFormat`] if the variants differ or either
+    /// side is already mapped to something else.
+    pub fn insert(&mut self, legacy: LegacyPrefixedId<T>, id: TypedUuid<T>) -> Result<(), TypedUuidError> {
+        if legacy.variant != id.variant_type() {
+            return Err(TypedUuidError::InvalidFormat(format!(
+                "legacy ID {} and replacement {} have different variants",
+                legacy, id
+            )));
+        }
+        let conflict = self.forward.get(&legacy).is_some_and(|existing| *existing != id)
+            || self.backward.get(&id).is_some_and(|existing| *existing != legacy);
+        if conflict {
+            return Err(TypedUuidError::InvalidFormat(format!(
+                "legacy ID {} or replacement {} is already mapped",
+                legacy, id
+            )));
+        }
+        self.backward.insert(id, legacy.clone());
+        self.forward.insert(legacy, id);
+        Ok(())
+    }
+
+    /// Returns the replacement recorded for `legacy`.
+    pub fn get(&self, legacy: &LegacyPrefixedId<T>) -> Option<TypedUuid<T>> {
+        self.forward.get(legacy).copied()
+    }
+
+    /// Returns the legacy ID that `id` replaced.
+    pub fn legacy_for(&self, id: &TypedUuid<T>) -> Option<&LegacyPrefixedId<T>> {
+        self.backward.get(id)
+    }
+
+    /// Iterates over every recorded pair, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&LegacyPrefixedId<T>, &TypedUuid<T>)> {
+        self.forward.iter()
+    }
+
+    /// Returns the number of recorded pairs.
+    pub fn len(&self) -> usize {
+        self.forward.len()
+    }
+
+    /// Returns `true` if nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.forward.is_empty()
+    }
+}
+
+impl<T: UuidType> Default for LegacyIdMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,42 @@
+//! [Leptos](https://leptos.dev) router support for typed IDs in route and query params.
+//!
+//! `TypedUuid<T>` and `UserFriendlyUuid<T>` implement `IntoParam`, so they can be
+//! fields of a `#[derive(Params)]` struct:
+//!
+//! ```text
+//! #[derive(Params, Clone, PartialEq)]
+//! struct UserParams {
+//!     id: UserFriendlyUuid<UserType>,
+//! }
+//!
+//! // <Route path=path!("/users/:id") view=UserPage/>
+//! let params = use_params::<UserParams>();
+//! ```
+//!
+//! Params parse with the same `FromStr` as the backend, so a URL carrying a wrong
+//! prefix or a mistyped ID fails the same way in the browser as on the server. A
+//! missing param is `ParamsError::MissingParam`; wrap the field in `Option` to allow it.
+//!
+//! Yew's `Routable` derive needs no glue: it parses route fields with `FromStr`.
+
+use std::sync::Arc;
+
+use ::leptos_router::params::{IntoParam, ParamsError};
+
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::UserFriendlyUuid;
+
+impl<T: UuidType> IntoParam for TypedUuid<T> {
+    fn into_param(value: Option<&str>, name: &str) -> Result<Self, ParamsError> {
+        let value = value.ok_or_else(|| ParamsError::MissingParam(name.to_string()))?;
+        value.parse().map_err(|e| ParamsError::Params(Arc::new(e)))
+    }
+}
+
+impl<T: UuidType> IntoParam for UserFriendlyUuid<T> {
+    fn into_param(value: Option<&str>, name: &str) -> Result<Self, ParamsError> {
+        let value = value.ok_or_else(|| ParamsError::MissingParam(name.to_string()))?;
+        value.parse().map_err(|e| ParamsError::Params(Arc::new(e)))
+    }
+}
@@ -28,18 +28,119 @@
 //! // friendly.to_string() -> "retail_550e8400-e29b-..."
 //! ```
 
+mod any_typed_uuid;
+mod audit;
+mod bloom;
+mod bulk;
+mod cached_uuid;
+mod clock;
+mod composite;
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+mod datetime;
+#[cfg(feature = "diesel")]
+mod diesel;
+#[cfg(feature = "duckdb")]
+pub mod duckdb;
+mod duplicate;
 mod error;
+mod event;
+#[cfg(feature = "flatbuffers")]
+pub mod flatbuffers;
+mod format;
+mod generator;
+mod generator_state;
+mod group;
+mod guid;
+mod hierarchy;
+mod idempotency;
+mod layout;
+mod legacy;
+#[cfg(feature = "leptos")]
+mod leptos;
+pub mod metrics;
+pub mod migrate;
+mod ordered;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+mod prefixed_uuid;
+#[cfg(feature = "pseudonymize")]
+mod pseudonym;
+mod registry;
+#[cfg(feature = "rusqlite")]
+pub mod rusqlite;
+mod routing;
+mod sampling;
+#[cfg(feature = "serde_with")]
+pub mod serde_with;
+mod snowflake;
+mod strum;
+mod tagged_uuid;
+#[cfg(feature = "tokio-postgres")]
+pub mod tokio_postgres;
 mod traits;
+mod type_info;
 mod typed_uuid;
+mod typed_uuid2;
+mod union;
 mod user_friendly_uuid;
+mod v7;
+mod variant_uuid;
+pub mod validate;
+mod vanity;
+mod wire;
 
-pub use error::TypedUuidError;
-pub use traits::UuidType;
+#[cfg(feature = "test-util")]
+pub mod test;
+
+pub use any_typed_uuid::AnyTypedUuid;
+pub use audit::{AuditReport, UniquenessAuditor};
+pub use bulk::parse_all;
+pub use cached_uuid::CachedUuid;
+pub use clock::{Clock, SystemClock};
+pub use composite::{CompositeId, CompositePart};
+pub use duplicate::DuplicateGuard;
+pub use error::{BulkParseError, ErrorKind, GeneratorStateError, ParseFailure, RegistryError, RetagError, SnowflakeError, SuspectedDuplicate, TypedUuidError, VanityError};
+pub use event::{CausationId, CorrelationId, EventEnvelope};
+pub use format::{BodyFormat, Case, FormatConfig};
+pub use generator::{OrderedGenerator, RandomGenerator, SequentialGenerator, UuidGenerator};
+pub use generator_state::{FileStateStore, GeneratorState, StateStore};
+pub use group::{FilterVariant, HasVariant, TypedUuidIterExt, VariantMap};
+pub use idempotency::IdempotencyKey;
+pub use layout::{Inspection, Layout};
+pub use legacy::{LegacyIdMap, LegacyPrefixedId};
+pub use migrate::Migrator;
+pub use prefixed_uuid::PrefixedUuid;
+pub use registry::UuidRegistry;
+pub use sampling::{sample_variant, VariantDistribution};
+pub use snowflake::{SnowflakeGenerator, SnowflakeLayout, WorkerIdProvider};
+pub use tagged_uuid::{PrefixTag, TaggedUuid};
+pub use traits::{UuidType, UuidTypeDyn};
+pub use type_info::TypeInfo;
 pub use typed_uuid::TypedUuid;
-pub use user_friendly_uuid::UserFriendlyUuid;
+pub use typed_uuid2::TypedUuid2;
+pub use user_friendly_uuid::{HyphenatedUserFriendlyUuid, SimpleUserFriendlyUuid, UserFriendlyUuid, UserFriendlyUuidRef, MAX_PREFIX_LEN};
+pub use v7::TaggedV7;
+pub use variant_uuid::VariantUuid;
+pub use wire::WIRE_FORMAT_VERSION;
 
-// Re-export the derive macro
-pub use smart_uuid_derive::UuidType;
+// Re-export the derive macro and its attribute form
+pub use smart_uuid_derive::{uuid_type, UuidType};
 
 // Re-export uuid::Uuid for convenience
 pub use uuid::Uuid;
+
+// Re-export the uuid crate, whose error type is the source of TypedUuidError::ParseError
+pub use uuid;
+
+// Items used by macro-generated code. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::error::did_you_mean;
+    pub use crate::union::has_prefix;
+    pub use crate::user_friendly_uuid::split_prefix;
+    pub use serde;
+    #[cfg(feature = "inventory")]
+    pub use crate::registry::AutoRegistration;
+    #[cfg(feature = "inventory")]
+    pub use inventory;
+}
@@ -0,0 +1,60 @@
+//! Metric label helpers that keep label cardinality low.
+//!
+//! Labeling a metric with a full ID creates one time series per ID and can take
+//! down a metrics backend. Label with [`TypedUuid::variant_label`] (or the helpers
+//! below) instead: there is one value per variant, never per ID.
+//!
+//! The `record_*` helpers need the `metrics` feature and report through whatever
+//! [`metrics`](https://docs.rs/metrics) recorder is installed.
+
+use crate::any_typed_uuid::AnyTypedUuid;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::UserFriendlyUuid;
+
+#[cfg(feature = "metrics")]
+use crate::error::TypedUuidError;
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Returns a low-cardinality metric label for this ID: its variant's prefix.
+    pub fn variant_label(&self) -> &'static str {
+        self.variant_type().prefix()
+    }
+}
+
+impl<T: UuidType> UserFriendlyUuid<T> {
+    /// Returns a low-cardinality metric label for this ID: its variant's prefix.
+    pub fn variant_label(&self) -> &'static str {
+        self.prefix()
+    }
+}
+
+impl AnyTypedUuid {
+    /// Returns a low-cardinality metric label for this ID: its variant's prefix.
+    pub fn variant_label(&self) -> &'static str {
+        self.prefix()
+    }
+}
+
+/// Increments `smart_uuid_generated_total`, labeled with the enum and variant.
+#[cfg(feature = "metrics")]
+pub fn record_generated<T: UuidType + 'static>(variant: T) {
+    let info = variant.type_info();
+    ::metrics::counter!(
+        "smart_uuid_generated_total",
+        "type" => info.enum_name(),
+        "variant" => info.prefix(),
+    )
+    .increment(1);
+}
+
+/// Increments `smart_uuid_parse_errors_total`, labeled with the error's
+/// [`ErrorKind`](crate::ErrorKind) name. The rejected input is never recorded.
+#[cfg(feature = "metrics")]
+pub fn record_parse_error(error: &TypedUuidError) {
+    ::metrics::counter!(
+        "smart_uuid_parse_errors_total",
+        "kind" => error.kind().name(),
+    )
+    .increment(1);
+}
@@ -0,0 +1,191 @@
+//! Schema versioning for IDs that outlive the enum layout they were written with.
+//!
+//! An enum opts in by returning a [`Migrator`] from [`UuidType::migrator`]. Byte 1 of
+//! its IDs then records the schema version, and IDs with an older version are passed
+//! through [`Migrator::migrate`] whenever they are read with
+//! [`TypedUuid::from_uuid`](crate::TypedUuid::from_uuid) (and so when parsed or
+//! deserialized).
+//!
+//! For one-shot migrations that rewrite stored IDs instead, see [`retag`].
+//!
+//! # IDs created before opting in
+//!
+//! Byte 1 of IDs written before the enum had a migrator holds something else, so
+//! versioned IDs are marked where the layout allows it, and unmarked ones are read as
+//! version 0 and passed to [`Migrator::migrate`] like any older version. Versions
+//! should therefore start at 1.
+//!
+//! An ID the migrator returns unchanged is kept byte-for-byte, without the current
+//! version or the marker, so it still equals its stored primary key and keeps its
+//! [`shard_for`](crate::TypedUuid::shard_for) shard and storage keys; it is simply passed
+//! through the migrator again on every read. An ID the migrator does change gets both,
+//! and routes like the new ID it now is.
+//!
+//! - [`Layout::V8`](crate::Layout::V8) (`TypedUuid::new` and most generators): versioned
+//!   IDs have the variant field `110` in byte 8, which IDs from before the opt-in
+//!   cannot carry: they always have RFC 9562's `10x`.
+//! - [`Layout::TimeOrdered`](crate::Layout::TimeOrdered): byte 1 is 0 in IDs of an
+//!   enum without a migrator, so 0 means version 0.
+//! - [`Layout::V4Compatible`](crate::Layout::V4Compatible) and
+//!   [`Layout::Opaque`](crate::Layout::Opaque) have no spare bits for a marker: byte 1
+//!   is read as the version. An opaque ID whose random bits happen to look like an
+//!   unmarked v8 ID (version 8, variant `10x`) gets the marker when generated, since it
+//!   would otherwise be read as version 0. Only opt in if no such IDs are stored yet:
+//!   the old ones carry neither a version nor, reliably, a marker.
+//! - [`TypedUuid2`](crate::TypedUuid2) keeps its entity in byte 1 and never applies
+//!   migrators.
+//!
+//! ```text
+//! struct UserTypeMigrator;
+//!
+//! impl Migrator<UserType> for UserTypeMigrator {
+//!     fn current_version(&self) -> u8 { 2 }
+//!
+//!     fn migrate(&self, from_version: u8, uuid: Uuid) -> Result<Uuid, TypedUuidError> {
+//!         // Version 1 stored Business as 0; version 2 moved it to 1
+//!         ...
+//!     }
+//! }
+//!
+//! #[derive(UuidType)]
+//! #[uuid_type(migrator = UserTypeMigrator)]
+//! enum UserType { ... }
+//! ```
+
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::error::{RetagError, TypedUuidError};
+use crate::traits::UuidType;
+
+/// The variant field (top three bits of byte 8) of versioned v8 IDs: `110`.
+pub(crate) const VERSIONED_MARKER: u8 = 0xC0;
+
+/// Upgrades IDs of `T` written with an older schema version.
+///
+/// Implementations are usually unit structs referenced from a `static` (or a unit
+/// struct path, which is promoted to one).
+pub trait Migrator<T: UuidType>: Sync {
+    /// The schema version stamped into byte 1 of new IDs.
+    ///
+    /// Should start at 1: version 0 stands for IDs written before the enum opted in.
+    fn current_version(&self) -> u8;
+
+    /// Rewrites a UUID written with `from_version` (always older than the current one),
+    /// where 0 means before the enum opted in; see the [module docs](self).
+    ///
+    /// Typically remaps the discriminant in byte 0. Byte 1 of a changed result is set to
+    /// the current version afterwards, so implementations need not touch it; an
+    /// unchanged one is returned as stored.
+    ///
+    /// Called again each time an unchanged ID is read, so it must be deterministic.
+    fn migrate(&self, from_version: u8, uuid: Uuid) -> Result<Uuid, TypedUuidError>;
+}
+
+/// Writes the header bytes of a new ID: the discriminant, and the schema version if `T` has one.
+pub(crate) fn stamp<T: UuidType>(variant: T, bytes: &mut [u8; 16]) {
+    bytes[0] = variant.discriminant();
+    if let Some(migrator) = T::migrator() {
+        bytes[1] = migrator.current_version();
+    }
+}
+
+/// Marks a finished ID of `T` as carrying a schema version, if `T` has one.
+///
+/// Only IDs that look like random-layout v8 IDs (version 8, RFC variant `10x`) are
+/// changed, since those are the ones [`stored_version`] reads as version 0.
+pub(crate) fn mark_versioned<T: UuidType>(bytes: &mut [u8; 16]) {
+    if T::migrator().is_some() && bytes[6] >> 4 == 8 && bytes[8] & 0xC0 == 0x80 {
+        bytes[8] = (bytes[8] & 0x1F) | VERSIONED_MARKER;
+    }
+}
+
+/// Returns the schema version in byte 1, or 0 for an ID written before `T` opted in.
+///
+/// Time-ordered IDs of unversioned enums already hold 0 there.
+fn stored_version(uuid: &Uuid) -> u8 {
+    let bytes = uuid.as_bytes();
+    // RFC variant `10x`: a random-layout v8 ID without the marker
+    if bytes[6] >> 4 == 8 && bytes[8] & 0xC0 == 0x80 {
+        return 0;
+    }
+    bytes[1]
+}
+
+/// Brings a stored UUID up to the current schema version of `T`, if `T` is versioned.
+pub(crate) fn upgrade<T: UuidType>(uuid: Uuid) -> Result<Uuid, TypedUuidError> {
+    let Some(migrator) = T::migrator() else {
+        return Ok(uuid);
+    };
+
+    let found = stored_version(&uuid);
+    let current = migrator.current_version();
+    if found == current {
+        return Ok(uuid);
+    }
+    if found > current {
+        return Err(TypedUuidError::UnsupportedSchemaVersion {
+            found,
+            current,
+            type_name: std::any::type_name::<T>(),
+        });
+    }
+
+    let migrated = migrator.migrate(found, uuid)?;
+    // Keep IDs the migration leaves alone exactly as stored, so lookups and routing agree
+    if migrated == uuid {
+        return Ok(uuid);
+    }
+    let mut bytes = *migrated.as_bytes();
+    bytes[1] = current;
+    mark_versioned::<T>(&mut bytes);
+    Ok(Uuid::from_bytes(bytes))
+}
+
+/// Plans a bulk re-tagging of stored UUIDs whose enum discriminants have changed.
+///
+/// Applies `map` to byte 0 of every UUID and returns `(old, new)` pairs in input
+/// order, including pairs where nothing changed, so the result is a complete
+/// translation table for an `UPDATE` script. Fails if `map` returns `None` for a
+/// discriminant in the input, or if two inputs would end up as the same UUID.
+///
+/// Only collisions within `iter` are detected: pass every ID of the column being
+/// migrated, not a batch of it.
+///
+/// # Example
+/// ```text
+/// // Retail (0) and Business (1) swapped places
+/// let pairs = migrate::retag(ids, |d| match d { 0 => Some(1), 1 => Some(0), d => Some(d) })?;
+/// for (old, new) in pairs.into_iter().filter(|(old, new)| old != new) { ... }
+/// ```
+pub fn retag<I, F>(iter: I, map: F) -> Result<Vec<(Uuid, Uuid)>, RetagError>
+where
+    I: IntoIterator<Item = Uuid>,
+    F: Fn(u8) -> Option<u8>,
+{
+    let mut pairs = Vec::new();
+    let mut seen: HashMap<Uuid, Uuid> = HashMap::new();
+
+    for old in iter {
+        let discriminant = old.as_bytes()[0];
+        let new_discriminant = map(discriminant).ok_or(RetagError::Unmapped {
+            uuid: old,
+            discriminant,
+        })?;
+
+        let mut bytes = *old.as_bytes();
+        bytes[0] = new_discriminant;
+        let new = Uuid::from_bytes(bytes);
+
+        if let Some(first) = seen.insert(new, old) {
+            return Err(RetagError::Collision {
+                first,
+                second: old,
+                retagged: new,
+            });
+        }
+        pairs.push((old, new));
+    }
+
+    Ok(pairs)
+}
@@ -0,0 +1,215 @@
+use std::ops::Range;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use uuid::{ClockSequence, Timestamp, Uuid};
+
+use crate::clock::{Clock, SystemClock};
+use crate::migrate;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::UserFriendlyUuid;
+
+/// Marks the time-ordered layout: the top three bits of byte 8, the variant field, all
+/// set (RFC 9562's reserved variant). Every other v8 ID, including ones created before
+/// this layout existed, has the RFC variant `10x` there, so none can be mistaken for it.
+pub(crate) const TIME_ORDERED_MARKER: u8 = 0xE0;
+
+/// Returns `true` if `uuid` has the time-ordered layout: version 8 and [`TIME_ORDERED_MARKER`].
+pub(crate) fn has_time_ordered_marker(uuid: &Uuid) -> bool {
+    uuid.get_version_num() == 8 && uuid.as_bytes()[8] & TIME_ORDERED_MARKER == TIME_ORDERED_MARKER
+}
+
+/// Timestamps are stored as 44 bits of Unix milliseconds, enough until the year 2527.
+pub(crate) const MAX_MILLIS: u64 = (1 << 44) - 1;
+
+fn to_millis(time: SystemTime) -> u64 {
+    let millis = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+    millis.min(MAX_MILLIS as u128) as u64
+}
+
+/// Writes the header and timestamp of a time-ordered ID into `bytes`.
+///
+/// Layout: byte 0 discriminant, byte 1 schema version (or 0), then the timestamp
+/// in bytes 2..=5, the low nibble of byte 6 and byte 7, around the version nibble.
+pub(crate) fn write_header<T: UuidType>(variant: T, millis: u64, bytes: &mut [u8; 16]) {
+    // Byte 1 must be constant for IDs to sort by time within a variant
+    bytes[1] = 0;
+    migrate::stamp(variant, bytes);
+
+    bytes[2..6].copy_from_slice(&((millis >> 12) as u32).to_be_bytes());
+    bytes[6] = 0x80 | ((millis >> 8) as u8 & 0x0F);
+    bytes[7] = millis as u8;
+}
+
+pub(crate) fn read_millis(uuid: &Uuid) -> u64 {
+    let bytes = uuid.as_bytes();
+    let high = u32::from_be_bytes([bytes[2], bytes[3], bytes[4], bytes[5]]) as u64;
+    (high << 12) | (((bytes[6] & 0x0F) as u64) << 8) | bytes[7] as u64
+}
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Creates a new time-ordered TypedUuid stamped with the current time.
+    ///
+    /// IDs of the same variant sort by creation time (to the millisecond) when
+    /// compared as UUIDs or bytes, which keeps B-tree indexes append-mostly. See
+    /// [`Layout::TimeOrdered`](crate::Layout::TimeOrdered) for the bit layout.
+    pub fn new_ordered(variant: T) -> Self {
+        Self::new_ordered_with_clock(variant, &SystemClock)
+    }
+
+    /// Creates a new time-ordered TypedUuid stamped with `clock`'s current time.
+    pub fn new_ordered_with_clock(variant: T, clock: &impl Clock) -> Self {
+        Self::new_ordered_at(variant, clock.now())
+    }
+
+    /// Creates a new time-ordered TypedUuid stamped with the given time.
+    ///
+    /// Times before the Unix epoch are stored as the epoch.
+    pub fn new_ordered_at(variant: T, time: SystemTime) -> Self {
+        let mut bytes: [u8; 16] = rand::random();
+        write_header(variant, to_millis(time), &mut bytes);
+        bytes[8] |= TIME_ORDERED_MARKER;
+
+        // SAFETY: byte 0 was just set to the variant's discriminant, byte 1 to the current schema
+        unsafe { Self::from_uuid_unchecked(Uuid::from_bytes(bytes)) }
+    }
+
+    /// Creates a new time-ordered TypedUuid from a [`uuid::Timestamp`], keeping its
+    /// clock sequence.
+    ///
+    /// The timestamp is truncated to milliseconds. The low 14 bits of its counter (the
+    /// clock sequence `Timestamp::to_gregorian` exposes) follow the layout flag, so IDs
+    /// stamped in the same millisecond sort by counter; the remaining bits are random.
+    pub fn new_ordered_from_timestamp(variant: T, timestamp: Timestamp) -> Self {
+        let (seconds, nanos) = timestamp.to_unix();
+        let millis = seconds.saturating_mul(1_000).saturating_add(nanos as u64 / 1_000_000);
+        let (_, counter) = timestamp.to_gregorian();
+
+        let mut bytes: [u8; 16] = rand::random();
+        write_header(variant, millis.min(MAX_MILLIS), &mut bytes);
+        bytes[8] = TIME_ORDERED_MARKER | (counter >> 9) as u8;
+        bytes[9] = (counter >> 1) as u8;
+        bytes[10] = (bytes[10] & 0x7F) | ((counter as u8 & 1) << 7);
+
+        // SAFETY: byte 0 was just set to the variant's discriminant, byte 1 to the current schema
+        unsafe { Self::from_uuid_unchecked(Uuid::from_bytes(bytes)) }
+    }
+
+    /// Creates a new time-ordered TypedUuid stamped with the current time and the next
+    /// value of `context`, the same clock sequence the `uuid` crate's v1, v6 and v7
+    /// constructors take.
+    pub fn new_ordered_with_context(variant: T, context: impl ClockSequence<Output = impl Into<u128>>) -> Self {
+        Self::new_ordered_from_timestamp(variant, Timestamp::now(context))
+    }
+
+    /// Returns the creation time as a [`uuid::Timestamp`], or `None` if this ID is not
+    /// time-ordered.
+    ///
+    /// The counter is the 14-bit clock sequence written by
+    /// [`new_ordered_from_timestamp`](Self::new_ordered_from_timestamp); for IDs made
+    /// otherwise it holds random bits.
+    pub fn uuid_timestamp(&self) -> Option<Timestamp> {
+        let millis = self.timestamp_millis()?;
+        let bytes = self.as_bytes();
+        let counter = ((bytes[8] & 0x1F) as u16) << 9 | (bytes[9] as u16) << 1 | (bytes[10] >> 7) as u16;
+        Some(Timestamp::from_unix_time(
+            millis / 1_000,
+            (millis % 1_000) as u32 * 1_000_000,
+            counter as u128,
+            14,
+        ))
+    }
+
+    /// Returns the creation time in Unix milliseconds, or `None` if this ID is not time-ordered.
+    pub fn timestamp_millis(&self) -> Option<u64> {
+        self.is_time_ordered().then(|| read_millis(self.as_uuid()))
+    }
+
+    /// Returns the creation time, or `None` if this ID is not time-ordered.
+    pub fn timestamp(&self) -> Option<SystemTime> {
+        self.timestamp_millis()
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis))
+    }
+
+    /// Returns how long before `now` this ID was created, or `None` if it is not
+    /// time-ordered. An ID stamped after `now` (clock skew) has an age of zero.
+    pub fn age(&self, now: SystemTime) -> Option<Duration> {
+        self.timestamp()
+            .map(|created| now.duration_since(created).unwrap_or_default())
+    }
+
+    /// Returns `true` if this ID is time-ordered and was created strictly after `time`.
+    ///
+    /// Compares at millisecond precision, the resolution of the stored timestamp.
+    pub fn created_after(&self, time: SystemTime) -> bool {
+        self.timestamp_millis()
+            .is_some_and(|millis| millis > to_millis(time))
+    }
+
+    /// Returns an inclusive UUID range covering every time-ordered ID of `variant`
+    /// created in `range`, for a single indexed `BETWEEN` query:
+    ///
+    /// ```text
+    /// let (low, high) = TypedUuid::bounds_for(OrderType::Order, tuesday..wednesday);
+    /// sqlx::query("SELECT * FROM orders WHERE id BETWEEN $1 AND $2").bind(low).bind(high)
+    /// ```
+    ///
+    /// Times are truncated to milliseconds and the end is exclusive. An empty range
+    /// yields `low > high`, which matches nothing.
+    ///
+    /// The range holds every time-ordered ID of `variant` from that window, but not only
+    /// those: an ID of another layout whose bytes 1..8 happen to fall inside it, such as
+    /// a random [`V8`](crate::Layout::V8) one, is returned too. If the column mixes
+    /// layouts, keep only the rows whose `layout()` is
+    /// [`Layout::TimeOrdered`](crate::Layout::TimeOrdered).
+    pub fn bounds_for(variant: T, range: Range<SystemTime>) -> (Uuid, Uuid) {
+        let start = to_millis(range.start);
+        let end = to_millis(range.end);
+
+        let mut low = [0u8; 16];
+        write_header(variant, start, &mut low);
+        low[8] = TIME_ORDERED_MARKER;
+
+        let mut high = [0xFFu8; 16];
+        match end.checked_sub(1) {
+            Some(last) => write_header(variant, last, &mut high),
+            None => {
+                // Nothing precedes the epoch: make the range empty
+                write_header(variant, 0, &mut high);
+                high[2..].fill(0);
+            }
+        }
+        high[8] = 0xFF;
+
+        (Uuid::from_bytes(low), Uuid::from_bytes(high))
+    }
+
+    fn is_time_ordered(&self) -> bool {
+        self.layout() == crate::Layout::TimeOrdered
+    }
+}
+
+impl<T: UuidType> UserFriendlyUuid<T> {
+    /// Creates a new time-ordered UserFriendlyUuid stamped with the current time; see
+    /// [`TypedUuid::new_ordered`].
+    ///
+    /// The prefix comes first, so the strings only sort by time within one variant.
+    pub fn new_ordered(variant: T) -> Self {
+        Self::from_typed_uuid(TypedUuid::new_ordered(variant))
+    }
+
+    /// Creates a new time-ordered UserFriendlyUuid stamped with the given time.
+    pub fn new_ordered_at(variant: T, time: SystemTime) -> Self {
+        Self::from_typed_uuid(TypedUuid::new_ordered_at(variant, time))
+    }
+
+    /// Returns the creation time in Unix milliseconds, or `None` if this ID is not
+    /// time-ordered; see [`TypedUuid::timestamp_millis`].
+    pub fn timestamp_millis(&self) -> Option<u64> {
+        self.as_typed_uuid().timestamp_millis()
+    }
+
+    /// Returns the creation time, or `None` if this ID is not time-ordered.
+    pub fn timestamp(&self) -> Option<SystemTime> {
+        self.as_typed_uuid().timestamp()
+    }
+}
@@ -0,0 +1,105 @@
+//! [Parquet](https://docs.rs/parquet) column helpers using the UUID logical type.
+//!
+//! Columns are `FIXED_LEN_BYTE_ARRAY(16)` annotated as `UUID`, so query engines see
+//! real UUIDs instead of strings. Values are the 16 bytes of wire format v1, and
+//! reading validates every discriminant, so a typed column never yields a mistyped ID.
+//!
+//! ```text
+//! let schema = Type::group_type_builder("orders")
+//!     .with_fields(vec![Arc::new(smart_uuid::parquet::uuid_column_type("customer"))])
+//!     .build()?;
+//! let mut writer = SerializedFileWriter::new(file, Arc::new(schema), props)?;
+//! let mut row_group = writer.next_row_group()?;
+//! smart_uuid::parquet::write_column(&mut row_group, &customers)?;
+//! row_group.close()?;
+//! ```
+//!
+//! Only required (non-null, non-repeated) columns are supported.
+
+use std::io::Write;
+
+use ::parquet::basic::{LogicalType, Repetition, Type as PhysicalType};
+use ::parquet::column::reader::ColumnReader;
+use ::parquet::column::writer::ColumnWriter;
+use ::parquet::data_type::FixedLenByteArray;
+use ::parquet::errors::{ParquetError, Result};
+use ::parquet::file::reader::RowGroupReader;
+use ::parquet::file::writer::SerializedRowGroupWriter;
+use ::parquet::schema::types::{ColumnDescriptor, Type};
+
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+
+/// Returns the schema of a required UUID column named `name`.
+pub fn uuid_column_type(name: &str) -> Type {
+    Type::primitive_type_builder(name, PhysicalType::FIXED_LEN_BYTE_ARRAY)
+        .with_repetition(Repetition::REQUIRED)
+        .with_length(16)
+        .with_logical_type(Some(LogicalType::Uuid))
+        .build()
+        .expect("a required FIXED_LEN_BYTE_ARRAY(16) is a valid UUID column")
+}
+
+/// Writes `ids` as the next column of `row_group`.
+///
+/// Fails without writing if that column is not a required UUID column.
+pub fn write_column<T: UuidType, W: Write + Send>(
+    row_group: &mut SerializedRowGroupWriter<'_, W>,
+    ids: &[TypedUuid<T>],
+) -> Result<()> {
+    let mut column = row_group
+        .next_column()?
+        .ok_or_else(|| ParquetError::General("no column left to write UUIDs to".to_string()))?;
+
+    match column.untyped() {
+        ColumnWriter::FixedLenByteArrayColumnWriter(writer) => {
+            check_uuid_column(writer.get_descriptor())?;
+            let values: Vec<FixedLenByteArray> = ids.iter().map(|id| id.as_bytes().to_vec().into()).collect();
+            writer.write_batch(&values, None, None)?;
+        }
+        _ => return Err(ParquetError::General(format!("next column {UUID_COLUMN_ERROR}"))),
+    }
+    column.close()
+}
+
+/// Reads column `i` of `row_group`, validating every ID.
+///
+/// An ID with a discriminant `T` does not know fails the whole read with a
+/// [`ParquetError::External`] wrapping the [`TypedUuidError`](crate::TypedUuidError).
+pub fn read_column<T: UuidType>(row_group: &dyn RowGroupReader, i: usize) -> Result<Vec<TypedUuid<T>>> {
+    check_uuid_column(row_group.metadata().column(i).column_descr())?;
+
+    let ColumnReader::FixedLenByteArrayColumnReader(mut reader) = row_group.get_column_reader(i)? else {
+        unreachable!("check_uuid_column verified the physical type");
+    };
+
+    let rows = usize::try_from(row_group.metadata().num_rows())?;
+    let mut values = Vec::with_capacity(rows);
+    reader.read_records(rows, None, None, &mut values)?;
+
+    values
+        .iter()
+        .map(|value| {
+            TypedUuid::try_from(value.data()).map_err(|e| ParquetError::External(Box::new(e)))
+        })
+        .collect()
+}
+
+fn check_uuid_column(descr: &ColumnDescriptor) -> Result<()> {
+    let is_uuid = descr.physical_type() == PhysicalType::FIXED_LEN_BYTE_ARRAY
+        && descr.type_length() == 16
+        && descr.logical_type() == Some(LogicalType::Uuid)
+        && descr.max_def_level() == 0
+        && descr.max_rep_level() == 0;
+    if is_uuid {
+        Ok(())
+    } else {
+        Err(not_a_uuid_column(descr.name()))
+    }
+}
+
+const UUID_COLUMN_ERROR: &str = "is not a required FIXED_LEN_BYTE_ARRAY(16) UUID column";
+
+fn not_a_uuid_column(name: &str) -> ParquetError {
+    ParquetError::General(format!("column '{name}' {UUID_COLUMN_ERROR}"))
+}
@@ -0,0 +1,57 @@
+use std::fmt;
+use uuid::Uuid;
+
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+
+/// A prefixed UUID whose variant was taken from the prefix alone.
+///
+/// Returned by [`UserFriendlyUuid::parse_prefix_authoritative`](crate::UserFriendlyUuid::parse_prefix_authoritative)
+/// for interop with systems that prefix plain UUIDs (e.g. v4) without the byte-0
+/// discriminant encoding. The body may be any UUID, so this is not a `TypedUuid`;
+/// [`to_typed_uuid`](Self::to_typed_uuid) converts it when the encoding does match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PrefixedUuid<T: UuidType> {
+    variant: T,
+    uuid: Uuid,
+}
+
+impl<T: UuidType> PrefixedUuid<T> {
+    pub(crate) fn new(variant: T, uuid: Uuid) -> Self {
+        Self { variant, uuid }
+    }
+
+    /// Returns the variant named by the prefix.
+    pub fn variant_type(&self) -> T {
+        self.variant
+    }
+
+    /// Returns a reference to the UUID body.
+    pub fn as_uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+
+    /// Consumes self and returns the UUID body.
+    pub fn into_uuid(self) -> Uuid {
+        self.uuid
+    }
+
+    /// Returns `true` if byte 0 of the body also encodes the prefix's variant.
+    pub fn discriminant_matched(&self) -> bool {
+        self.uuid.as_bytes()[0] == self.variant.discriminant()
+    }
+
+    /// Returns the `TypedUuid`, or `None` if the body does not encode the prefix's variant.
+    pub fn to_typed_uuid(&self) -> Option<TypedUuid<T>> {
+        if !self.discriminant_matched() {
+            return None;
+        }
+        TypedUuid::from_uuid(self.uuid).ok()
+    }
+}
+
+impl<T: UuidType> fmt::Display for PrefixedUuid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}_{}", self.variant.prefix(), self.uuid)
+    }
+}
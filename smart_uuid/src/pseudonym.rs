@@ -0,0 +1,55 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+
+/// Prepended to every HMAC input, so these pseudonyms never equal an HMAC the same
+/// key computes for another purpose.
+///
+/// Exported pseudonyms depend on this value; it must never change.
+const DOMAIN: &[u8] = b"smart_uuid/pseudonym/v1";
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Derives a pseudonymous ID of the same variant from this one, keyed by `key`.
+    ///
+    /// The body is the first 16 bytes of HMAC-SHA256 over the whole ID, laid out as a
+    /// v8 ID like [`new`](Self::new). The same ID and key always give the same
+    /// pseudonym, so exports keep joining and counting correctly, while without the key
+    /// a pseudonym cannot be traced back or recomputed from a known ID. Rotating the key
+    /// breaks the link to earlier exports.
+    ///
+    /// A pseudonym is itself a valid `TypedUuid<T>` and could collide with a real ID;
+    /// keep pseudonymized data apart from real IDs. Under GDPR, pseudonymized data is
+    /// still personal data for whoever holds the key.
+    pub fn pseudonymize(&self, key: &[u8]) -> Self {
+        pseudonymize_with(&keyed_mac(key), self)
+    }
+
+    /// Pseudonymizes every ID in `ids` with one key, like [`pseudonymize`](Self::pseudonymize).
+    ///
+    /// Sets up the key once rather than once per ID.
+    pub fn pseudonymize_all(ids: &[Self], key: &[u8]) -> Vec<Self> {
+        let mac = keyed_mac(key);
+        ids.iter().map(|id| pseudonymize_with(&mac, id)).collect()
+    }
+}
+
+fn keyed_mac(key: &[u8]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(DOMAIN);
+    mac
+}
+
+fn pseudonymize_with<T: UuidType>(mac: &Hmac<Sha256>, id: &TypedUuid<T>) -> TypedUuid<T> {
+    let mut mac = mac.clone();
+    mac.update(id.as_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    // The v1 derivation cleared what was then the time-ordered flag; keep doing so, or
+    // every pseudonym with that digest bit set would change
+    bytes[8] &= !0x20;
+    TypedUuid::from_v8_bytes(id.variant_type(), bytes)
+}
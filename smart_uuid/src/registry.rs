@@ -0,0 +1,214 @@
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::any_typed_uuid::AnyTypedUuid;
+use crate::error::{did_you_mean, RegistryError, TypedUuidError};
+use crate::traits::{variants, UuidType};
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::{split_prefix, UserFriendlyUuid};
+
+/// Resolves user-friendly UUID strings across several `UuidType` enums.
+///
+/// Each registered enum contributes its prefixes. [`parse`](Self::parse) looks up
+/// the prefix, parses the string with that enum's rules, and returns a type-erased
+/// [`AnyTypedUuid`] that can be downcast back to the concrete type.
+///
+/// # Example
+/// ```text
+/// let mut registry = UuidRegistry::new();
+/// registry.register::<UserType>()?.register::<DocumentType>()?;
+///
+/// let id = registry.parse("org_...")?;
+/// if let Some(user_id) = id.downcast::<UserType>() { ... }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct UuidRegistry {
+    entries: HashMap<&'static str, Registration>,
+    types: Vec<RegisteredType>,
+}
+
+#[derive(Debug, Clone)]
+struct RegisteredType {
+    type_id: TypeId,
+    type_name: &'static str,
+    id_space: Option<&'static str>,
+    discriminants: Vec<u8>,
+    prefixes: &'static [&'static str],
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Registration {
+    type_id: TypeId,
+    type_name: &'static str,
+    parse: fn(&str) -> Result<AnyTypedUuid, TypedUuidError>,
+    generate: fn(&str) -> Option<AnyTypedUuid>,
+}
+
+impl UuidRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers every prefix of the enum `T`.
+    ///
+    /// Registering the same type twice is a no-op. Fails without modifying the
+    /// registry if any prefix is already claimed by another type.
+    pub fn register<T: UuidType + 'static>(&mut self) -> Result<&mut Self, RegistryError> {
+        let registration = Registration {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            parse: |s| UserFriendlyUuid::<T>::parse_str(s).map(AnyTypedUuid::from),
+            generate: |prefix| T::from_prefix(prefix).map(|v| TypedUuid::new(v).into()),
+        };
+
+        // Check every prefix before inserting any, so a conflict leaves the registry untouched
+        for variant in variants::<T>() {
+            if let Some(existing) = self.entries.get(variant.prefix()) {
+                if existing.type_id != registration.type_id {
+                    return Err(RegistryError::DuplicatePrefix {
+                        prefix: variant.prefix(),
+                        existing: existing.type_name,
+                        conflicting: registration.type_name,
+                    });
+                }
+            }
+        }
+
+        if self.types.iter().any(|t| t.type_id == registration.type_id) {
+            return Ok(self);
+        }
+
+        for variant in variants::<T>() {
+            self.entries.insert(variant.prefix(), registration);
+        }
+        self.types.push(RegisteredType {
+            type_id: registration.type_id,
+            type_name: registration.type_name,
+            id_space: T::id_space(),
+            discriminants: variants::<T>().map(|v| v.discriminant()).collect(),
+            prefixes: T::prefixes(),
+        });
+        Ok(self)
+    }
+
+    /// Builds a registry from every enum marked `#[uuid_type(register)]` in the binary.
+    ///
+    /// Fails on the first prefix claimed by two different enums, wherever they are defined.
+    #[cfg(feature = "inventory")]
+    pub fn from_registered() -> Result<Self, RegistryError> {
+        let mut registry = Self::new();
+        for auto in inventory::iter::<AutoRegistration> {
+            (auto.register)(&mut registry)?;
+        }
+        Ok(registry)
+    }
+
+    /// Checks that no two registered enums in the same ID space share a discriminant.
+    ///
+    /// Prefix collisions are already rejected by [`register`](Self::register). Discriminant
+    /// overlap only matters when IDs of several enums end up in the same column and must be
+    /// attributed from the raw UUID alone. Such enums declare a shared
+    /// [`UuidType::id_space`]; enums without one are not checked, since the derive numbers
+    /// every enum from 0. Call this at startup to fail fast:
+    ///
+    /// ```text
+    /// UuidRegistry::from_registered()?.verify()?;
+    /// ```
+    pub fn verify(&self) -> Result<(), RegistryError> {
+        let mut owners: HashMap<(&'static str, u8), &'static str> = HashMap::new();
+        for registered in &self.types {
+            let Some(id_space) = registered.id_space else {
+                continue;
+            };
+            for &discriminant in &registered.discriminants {
+                if let Some(existing) = owners.insert((id_space, discriminant), registered.type_name) {
+                    return Err(RegistryError::DiscriminantOverlap {
+                        id_space,
+                        discriminant,
+                        existing,
+                        conflicting: registered.type_name,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses a user-friendly string using whichever registered enum owns its prefix.
+    pub fn parse(&self, s: &str) -> Result<AnyTypedUuid, TypedUuidError> {
+        let (prefix, _) = split_prefix(s)?;
+
+        let registration = self.entries.get(prefix).ok_or_else(|| TypedUuidError::UnknownPrefix {
+            prefix: prefix.to_string(),
+            type_name: "UuidRegistry",
+            expected: Cow::Owned(self.types.iter().flat_map(|t| t.prefixes.iter().copied()).collect()),
+            did_you_mean: did_you_mean(prefix, self.prefixes()),
+        })?;
+
+        (registration.parse)(s)
+    }
+
+    /// Creates a random ID for the variant using this prefix, or returns `None` if no
+    /// registered enum uses it.
+    ///
+    /// Lets callers that only know prefixes at runtime (e.g. other languages calling in
+    /// through FFI) mint IDs with the owning enum's rules.
+    pub fn generate(&self, prefix: &str) -> Option<AnyTypedUuid> {
+        self.entries.get(prefix).and_then(|registration| (registration.generate)(prefix))
+    }
+
+    /// Returns `true` if some registered enum uses this prefix.
+    pub fn contains_prefix(&self, prefix: &str) -> bool {
+        self.entries.contains_key(prefix)
+    }
+
+    /// Iterates over all registered prefixes, in no particular order.
+    pub fn prefixes(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries.keys().copied()
+    }
+}
+
+/// A registration submitted by `#[derive(UuidType)]` with `#[uuid_type(register)]`.
+///
+/// Collected at link time and consumed by [`UuidRegistry::from_registered`].
+#[cfg(feature = "inventory")]
+#[doc(hidden)]
+pub struct AutoRegistration {
+    register: fn(&mut UuidRegistry) -> Result<(), RegistryError>,
+}
+
+#[cfg(feature = "inventory")]
+impl AutoRegistration {
+    pub const fn new<T: UuidType + 'static>() -> Self {
+        Self {
+            register: |registry| registry.register::<T>().map(|_| ()),
+        }
+    }
+}
+
+#[cfg(feature = "inventory")]
+inventory::collect!(AutoRegistration);
+
+/// Submits an enum for automatic registration. Emitted by the derive macro.
+#[cfg(feature = "inventory")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_uuid_type {
+    ($ty:ty) => {
+        $crate::__private::inventory::submit! {
+            $crate::__private::AutoRegistration::new::<$ty>()
+        }
+    };
+}
+
+/// Submits an enum for automatic registration. Emitted by the derive macro.
+#[cfg(not(feature = "inventory"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_uuid_type {
+    ($ty:ty) => {
+        ::core::compile_error!("`#[uuid_type(register)]` requires the `inventory` feature of smart_uuid");
+    };
+}
@@ -0,0 +1,277 @@
+use crate::error::TypedUuidError;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::UserFriendlyUuid;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Returns the shard (in `0..n_shards`) this ID routes to.
+    ///
+    /// # Stability
+    /// The result depends only on bytes 1..16 of the UUID (never the discriminant,
+    /// so an ID keeps its shard if its variant is remapped) and on `n_shards`.
+    /// The algorithm is fixed: a 64-bit FNV-1a hash of those bytes fed into Jump
+    /// Consistent Hash (Lamping & Veach, 2014). It will not change outside a major
+    /// version, so every service and crate version routes an ID to the same shard.
+    /// The one exception is an ID a [`Migrator`](crate::Migrator) rewrites when it is
+    /// read, which routes like the rewritten ID; see [`crate::migrate`].
+    ///
+    /// Growing from `n` to `n + 1` shards moves only about `1 / (n + 1)` of the IDs.
+    ///
+    /// # Panics
+    /// Panics if `n_shards` is zero.
+    pub fn shard_for(&self, n_shards: u32) -> u32 {
+        assert!(n_shards > 0, "shard_for requires at least one shard");
+        jump_consistent_hash(self.routing_hash(), n_shards)
+    }
+
+    /// 64-bit FNV-1a hash of the non-discriminant bytes.
+    pub(crate) fn routing_hash(&self) -> u64 {
+        fnv1a(&self.as_bytes()[1..])
+    }
+}
+
+/// 64-bit FNV-1a. Fixed forever: its output is persisted in shard assignments and keys.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(FNV_OFFSET_BASIS, |hash, &byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME))
+}
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Returns an object-storage key that spreads IDs over two levels of directories,
+    /// e.g. `retail/3f/a2/0000e29b-...`.
+    ///
+    /// Shorthand for [`storage_key_with_depth(2)`](Self::storage_key_with_depth).
+    pub fn storage_key(&self) -> String {
+        self.storage_key_with_depth(2)
+    }
+
+    /// Returns an object-storage key with `depth` levels of two-hex-digit directories
+    /// between the prefix and the UUID.
+    ///
+    /// The directories come from the same hash as [`shard_for`](Self::shard_for), not
+    /// from the leading UUID digits, which hold the discriminant (and, for time-ordered
+    /// IDs, the timestamp) and would concentrate writes on a few hot prefixes in
+    /// S3/GCS. The layout is covered by the same stability guarantee.
+    ///
+    /// # Panics
+    /// Panics if `depth` exceeds 8.
+    pub fn storage_key_with_depth(&self, depth: usize) -> String {
+        assert!(depth <= 8, "storage keys support at most 8 directory levels");
+
+        let hash = self.routing_hash().to_be_bytes();
+        let mut key = String::with_capacity(self.variant_type().prefix().len() + depth * 3 + 38);
+        key.push_str(self.variant_type().prefix());
+        for byte in &hash[..depth] {
+            key.push_str(&format!("/{:02x}", byte));
+        }
+        key.push_str(&format!("/{}", self.as_uuid()));
+        key
+    }
+
+    /// Parses a key produced by [`storage_key_with_depth`](Self::storage_key_with_depth)
+    /// at any depth, checking that the prefix and directories match the UUID.
+    pub fn parse_storage_key(key: &str) -> Result<Self, TypedUuidError> {
+        let (prefix, rest) = key.split_once('/').ok_or_else(|| {
+            TypedUuidError::InvalidFormat("expected format 'prefix/../uuid', no '/' found".to_string())
+        })?;
+        let uuid_str = rest.rsplit('/').next().unwrap_or(rest);
+        let id: Self = uuid_str.parse()?;
+
+        if prefix != id.variant_type().prefix() {
+            return Err(TypedUuidError::unknown_prefix::<T>(prefix));
+        }
+
+        let depth = rest.matches('/').count();
+        if depth > 8 || id.storage_key_with_depth(depth) != key {
+            return Err(TypedUuidError::InvalidFormat(format!(
+                "storage key directories do not match UUID {}",
+                id.as_uuid()
+            )));
+        }
+
+        Ok(id)
+    }
+}
+
+/// Number of hash slots in a Redis Cluster.
+const REDIS_CLUSTER_SLOTS: u16 = 16384;
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Returns the ID as a Redis Cluster hash tag: `{retail_550e8400-...}`.
+    pub fn hash_tag(&self) -> String {
+        format!("{{{}}}", UserFriendlyUuid::from(*self).hyphenated())
+    }
+
+    /// Renders a Redis key from `pattern`, replacing each `{}` with the ID's
+    /// [hash tag](Self::hash_tag): `"orders:{}:items"` becomes
+    /// `orders:{retail_550e8400-...}:items`.
+    ///
+    /// Redis Cluster hashes only the tag, so every key rendered for one ID lands in
+    /// the same slot and multi-key commands and transactions over them work.
+    ///
+    /// # Panics
+    /// Panics if `pattern` has no `{}`, or has a `{` before its first `{}` (Redis
+    /// would hash that brace instead).
+    pub fn cluster_key(&self, pattern: &str) -> String {
+        let placeholder = pattern.find("{}");
+        assert!(
+            placeholder.is_some() && placeholder == pattern.find('{'),
+            "cluster key pattern {:?} must contain {{}} before any other '{{'",
+            pattern
+        );
+        pattern.replace("{}", &self.hash_tag())
+    }
+
+    /// Renders several [`cluster_key`](Self::cluster_key)s at once, all in one slot.
+    ///
+    /// # Panics
+    /// Panics if any pattern would panic in `cluster_key`.
+    pub fn cluster_keys(&self, patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(|pattern| self.cluster_key(pattern)).collect()
+    }
+
+    /// Returns the Redis Cluster slot (in `0..16384`) that this ID's keys hash to.
+    pub fn cluster_slot(&self) -> u16 {
+        crc16_xmodem(UserFriendlyUuid::from(*self).hyphenated().to_string().as_bytes()) % REDIS_CLUSTER_SLOTS
+    }
+}
+
+impl<T: UuidType> UserFriendlyUuid<T> {
+    /// Same as [`TypedUuid::hash_tag`].
+    pub fn hash_tag(&self) -> String {
+        self.as_typed_uuid().hash_tag()
+    }
+
+    /// Same as [`TypedUuid::cluster_key`].
+    pub fn cluster_key(&self, pattern: &str) -> String {
+        self.as_typed_uuid().cluster_key(pattern)
+    }
+
+    /// Same as [`TypedUuid::cluster_keys`].
+    pub fn cluster_keys(&self, patterns: &[&str]) -> Vec<String> {
+        self.as_typed_uuid().cluster_keys(patterns)
+    }
+
+    /// Same as [`TypedUuid::cluster_slot`].
+    pub fn cluster_slot(&self) -> u16 {
+        self.as_typed_uuid().cluster_slot()
+    }
+}
+
+/// CRC-16/XMODEM, the checksum Redis Cluster maps keys to slots with.
+fn crc16_xmodem(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0u16, |mut crc, &byte| {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+        }
+        crc
+    })
+}
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Returns the ID as a single NATS subject token:
+    /// `retail_550e8400e29b41d4a716446655440000`.
+    ///
+    /// The body is written without hyphens, and any prefix byte other than ASCII
+    /// letters, digits, `_` and `-` is escaped as `~` and two hex digits, so the token
+    /// never contains `.`, `*`, `>` or whitespace and can sit between dots in a subject
+    /// such as `orders.{token}.created`. Parse it back with
+    /// [`parse_subject_token`](Self::parse_subject_token).
+    pub fn to_subject_token(&self) -> String {
+        let prefix = self.variant_type().prefix();
+        let mut token = String::with_capacity(prefix.len() + 33);
+        for byte in prefix.bytes() {
+            if byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-' {
+                token.push(byte as char);
+            } else {
+                token.push_str(&format!("~{:02x}", byte));
+            }
+        }
+        token.push('_');
+        token.push_str(&self.simple().to_string());
+        token
+    }
+
+    /// Parses a token produced by [`to_subject_token`](Self::to_subject_token).
+    pub fn parse_subject_token(token: &str) -> Result<Self, TypedUuidError> {
+        let invalid = || TypedUuidError::InvalidFormat(format!("invalid subject token escape in '{}'", token));
+        let mut bytes = Vec::with_capacity(token.len());
+        let mut rest = token.as_bytes();
+        while let Some((&byte, tail)) = rest.split_first() {
+            if byte == b'~' {
+                let hex = tail.get(..2).filter(|hex| hex.iter().all(u8::is_ascii_hexdigit)).ok_or_else(invalid)?;
+                // Two hex digits always fit in a byte
+                bytes.push(u8::from_str_radix(std::str::from_utf8(hex).unwrap(), 16).unwrap());
+                rest = &tail[2..];
+            } else {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+        UserFriendlyUuid::try_parse_ascii(&bytes).map(UserFriendlyUuid::into_typed_uuid)
+    }
+}
+
+impl<T: UuidType> UserFriendlyUuid<T> {
+    /// Same as [`TypedUuid::to_subject_token`].
+    pub fn to_subject_token(&self) -> String {
+        self.as_typed_uuid().to_subject_token()
+    }
+
+    /// Same as [`TypedUuid::parse_subject_token`].
+    pub fn parse_subject_token(token: &str) -> Result<Self, TypedUuidError> {
+        TypedUuid::parse_subject_token(token).map(Self::from)
+    }
+}
+
+/// Jump Consistent Hash: maps a key to a bucket in `0..buckets`.
+fn jump_consistent_hash(mut key: u64, buckets: u32) -> u32 {
+    let mut b: i64 = -1;
+    let mut j: i64 = 0;
+    while j < buckets as i64 {
+        b = j;
+        key = key.wrapping_mul(2_862_933_555_777_941_757).wrapping_add(1);
+        j = ((b + 1) as f64 * ((1u64 << 31) as f64 / ((key >> 33) + 1) as f64)) as i64;
+    }
+    b as u32
+}
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Returns the bytes to use as a Kafka (or other partitioned log) message key.
+    ///
+    /// Always the 16 raw UUID bytes, never a string rendering, so producers keyed by
+    /// `TypedUuid` and by `UserFriendlyUuid` send the same ID to the same partition.
+    pub fn partition_key_bytes(&self) -> &[u8; 16] {
+        self.as_bytes()
+    }
+}
+
+impl<T: UuidType> UserFriendlyUuid<T> {
+    /// Returns the bytes to use as a Kafka message key.
+    ///
+    /// Identical to [`TypedUuid::partition_key_bytes`]: the prefix is not part of the key.
+    pub fn partition_key_bytes(&self) -> &[u8; 16] {
+        self.as_typed_uuid().as_bytes()
+    }
+}
+
+/// Typed IDs can be passed directly as `rdkafka` record keys.
+#[cfg(feature = "rdkafka")]
+impl<T: UuidType> rdkafka::message::ToBytes for TypedUuid<T> {
+    fn to_bytes(&self) -> &[u8] {
+        self.partition_key_bytes()
+    }
+}
+
+/// Keys with the raw UUID bytes, exactly like `TypedUuid`.
+#[cfg(feature = "rdkafka")]
+impl<T: UuidType> rdkafka::message::ToBytes for UserFriendlyUuid<T> {
+    fn to_bytes(&self) -> &[u8] {
+        self.partition_key_bytes()
+    }
+}
@@ -0,0 +1,50 @@
+//! [rusqlite](https://docs.rs/rusqlite) SQL functions for validating typed IDs inside SQLite.
+//!
+//! ```text
+//! smart_uuid::rusqlite::register_functions::<UserType>(&conn)?;
+//! conn.execute_batch(
+//!     "CREATE TABLE users (
+//!          id TEXT PRIMARY KEY CHECK (smart_uuid_valid(id)),
+//!          kind TEXT GENERATED ALWAYS AS (smart_uuid_prefix(id))
+//!      )",
+//! )?;
+//! ```
+//!
+//! Functions are per connection, so register them on every connection that touches
+//! a table whose constraints use them. Registering for another `UuidType` on the same
+//! connection replaces the earlier functions.
+
+use ::rusqlite::functions::{Context, FunctionFlags};
+use ::rusqlite::types::ValueRef;
+use ::rusqlite::{Connection, Result};
+
+use crate::traits::UuidType;
+use crate::user_friendly_uuid::UserFriendlyUuid;
+
+/// Installs `smart_uuid_valid(x)` and `smart_uuid_prefix(x)` on `conn`, both parsing
+/// `x` as a `UserFriendlyUuid<T>` string.
+///
+/// - `smart_uuid_valid(x)` returns 1 if `x` is a valid ID and 0 otherwise.
+/// - `smart_uuid_prefix(x)` returns the ID's prefix, or NULL if it is not valid.
+///
+/// Both return NULL for a NULL argument, like SQLite's own functions, so a CHECK on a
+/// nullable column still admits NULL. They are deterministic and can be used in
+/// indexes and generated columns.
+pub fn register_functions<T: UuidType>(conn: &Connection) -> Result<()> {
+    let flags = FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC;
+    conn.create_scalar_function("smart_uuid_valid", 1, flags, |ctx| {
+        Ok(parse_arg::<T>(ctx).map(|id| id.is_some()))
+    })?;
+    conn.create_scalar_function("smart_uuid_prefix", 1, flags, |ctx| {
+        Ok(parse_arg::<T>(ctx).flatten().map(|id| id.prefix()))
+    })
+}
+
+/// Parses the first argument: `None` for NULL, `Some(None)` for anything invalid.
+fn parse_arg<T: UuidType>(ctx: &Context<'_>) -> Option<Option<UserFriendlyUuid<T>>> {
+    match ctx.get_raw(0) {
+        ValueRef::Null => None,
+        ValueRef::Text(text) => Some(UserFriendlyUuid::try_parse_ascii(text).ok()),
+        _ => Some(None),
+    }
+}
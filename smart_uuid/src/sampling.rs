@@ -0,0 +1,71 @@
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::{Rng, RngCore};
+
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+
+/// A weighted distribution over variants, for load tests and seed data that need a
+/// realistic mix of entity types rather than a uniform one.
+///
+/// Implements [`rand::distributions::Distribution`], so it works with any `Rng`.
+///
+/// # Example
+/// ```text
+/// let mix = VariantDistribution::new(&[
+///     (UserType::Retail, 90.0),
+///     (UserType::Business, 9.0),
+///     (UserType::Organization, 1.0),
+/// ]).unwrap();
+/// let id = TypedUuid::new_sampled(&mut rng, &mix);
+/// ```
+#[derive(Debug, Clone)]
+pub struct VariantDistribution<T: UuidType> {
+    variants: Vec<T>,
+    index: WeightedIndex<f64>,
+}
+
+impl<T: UuidType> VariantDistribution<T> {
+    /// Creates a distribution picking each variant with probability proportional to its weight.
+    ///
+    /// Returns `None` if `weights` is empty, any weight is negative or not finite, or
+    /// all weights are zero.
+    pub fn new(weights: &[(T, f64)]) -> Option<Self> {
+        if weights.iter().any(|(_, w)| !w.is_finite()) {
+            return None;
+        }
+        let index = WeightedIndex::new(weights.iter().map(|(_, w)| *w)).ok()?;
+        Some(Self {
+            variants: weights.iter().map(|(v, _)| *v).collect(),
+            index,
+        })
+    }
+}
+
+impl<T: UuidType> Distribution<T> for VariantDistribution<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        self.variants[self.index.sample(rng)]
+    }
+}
+
+/// Picks one variant with probability proportional to its weight, using the thread RNG.
+///
+/// Builds a [`VariantDistribution`] on every call; build one yourself when sampling in a loop.
+///
+/// # Panics
+/// Panics if the weights are invalid (see [`VariantDistribution::new`]).
+pub fn sample_variant<T: UuidType>(weights: &[(T, f64)]) -> T {
+    VariantDistribution::new(weights)
+        .expect("sample_variant requires non-empty, finite, non-negative weights that are not all zero")
+        .sample(&mut rand::thread_rng())
+}
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Creates a new TypedUuid with a variant drawn from `distribution`.
+    ///
+    /// Both the variant and the random bits come from `rng`, so a seeded RNG yields a
+    /// reproducible data set.
+    pub fn new_sampled<R: RngCore + ?Sized>(rng: &mut R, distribution: &VariantDistribution<T>) -> Self {
+        let variant = distribution.sample(rng);
+        Self::new_with_rng(variant, rng)
+    }
+}
@@ -0,0 +1,139 @@
+//! [`serde_with`](https://docs.rs/serde_with) adapters for choosing the wire format of
+//! a [`TypedUuid`] per field.
+//!
+//! ```text
+//! #[serde_as]
+//! #[derive(Serialize, Deserialize)]
+//! struct Order {
+//!     #[serde_as(as = "FriendlyString")]
+//!     customer: TypedUuid<UserType>,
+//!     #[serde_as(as = "Option<RawBytes>")]
+//!     referrer: Option<TypedUuid<UserType>>,
+//!     #[serde_as(as = "Vec<Base32>")]
+//!     related: Vec<TypedUuid<UserType>>,
+//! }
+//! ```
+//!
+//! Every adapter validates the discriminant on the way in, exactly like the
+//! default `Deserialize` impl.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use ::serde_with::{DeserializeAs, SerializeAs};
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serializer};
+
+use crate::error::TypedUuidError;
+use crate::format::{encode_base32, BASE32_LEN, CROCKFORD};
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::UserFriendlyUuid;
+use uuid::Uuid;
+
+/// Encodes a [`TypedUuid`] as its user-friendly `prefix_uuid` string.
+pub struct FriendlyString;
+
+/// Encodes a [`TypedUuid`] as its 16 raw bytes.
+///
+/// Formats without a bytes type (such as JSON) write an array of numbers.
+pub struct RawBytes;
+
+/// Encodes a [`TypedUuid`] as 26 lowercase Crockford base32 characters, e.g. for
+/// compact, case-insensitive IDs in URLs.
+///
+/// Decoding ignores case and accepts `o`/`i`/`l` for `0`/`1`/`1`, as Crockford allows.
+pub struct Base32;
+
+impl<T: UuidType> SerializeAs<TypedUuid<T>> for FriendlyString {
+    fn serialize_as<S: Serializer>(source: &TypedUuid<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&UserFriendlyUuid::from(*source).hyphenated())
+    }
+}
+
+impl<'de, T: UuidType> DeserializeAs<'de, TypedUuid<T>> for FriendlyString {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<TypedUuid<T>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        UserFriendlyUuid::parse_str(&s)
+            .map(UserFriendlyUuid::into_typed_uuid)
+            .map_err(de::Error::custom)
+    }
+}
+
+impl<T: UuidType> SerializeAs<TypedUuid<T>> for RawBytes {
+    fn serialize_as<S: Serializer>(source: &TypedUuid<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(source.as_bytes())
+    }
+}
+
+impl<'de, T: UuidType> DeserializeAs<'de, TypedUuid<T>> for RawBytes {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<TypedUuid<T>, D::Error> {
+        deserializer.deserialize_bytes(RawBytesVisitor(PhantomData))
+    }
+}
+
+struct RawBytesVisitor<T>(PhantomData<T>);
+
+impl<'de, T: UuidType> Visitor<'de> for RawBytesVisitor<T> {
+    type Value = TypedUuid<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("16 bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        TypedUuid::try_from(v).map_err(E::custom)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = [0u8; 16];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(de::Error::invalid_length(17, &self));
+        }
+        TypedUuid::try_from(bytes).map_err(de::Error::custom)
+    }
+}
+
+impl<T: UuidType> SerializeAs<TypedUuid<T>> for Base32 {
+    fn serialize_as<S: Serializer>(source: &TypedUuid<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        let encoded = encode_base32(source.as_uuid().as_u128());
+        // The alphabet is ASCII
+        serializer.serialize_str(std::str::from_utf8(&encoded).expect("base32 output is ASCII"))
+    }
+}
+
+impl<'de, T: UuidType> DeserializeAs<'de, TypedUuid<T>> for Base32 {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<TypedUuid<T>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        decode_base32(&s)
+            .and_then(|value| TypedUuid::from_uuid(Uuid::from_u128(value)))
+            .map_err(de::Error::custom)
+    }
+}
+
+fn decode_base32(s: &str) -> Result<u128, TypedUuidError> {
+    if s.len() != BASE32_LEN {
+        return Err(TypedUuidError::InvalidFormat(format!(
+            "expected {BASE32_LEN} base32 characters, found {}",
+            s.len()
+        )));
+    }
+    let mut value: u128 = 0;
+    for (i, c) in s.bytes().enumerate() {
+        let digit = match c.to_ascii_lowercase() {
+            b'o' => 0,
+            b'i' | b'l' => 1,
+            c => CROCKFORD.iter().position(|&d| d == c).ok_or_else(|| {
+                TypedUuidError::InvalidFormat(format!("invalid base32 character '{}'", c as char))
+            })? as u128,
+        };
+        if i == 0 && digit > 0b111 {
+            return Err(TypedUuidError::InvalidFormat("base32 value overflows 128 bits".to_string()));
+        }
+        value = (value << 5) | digit;
+    }
+    Ok(value)
+}
@@ -0,0 +1,262 @@
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+use uuid::Uuid;
+
+use crate::clock::{Clock, SystemClock};
+use crate::error::{GeneratorStateError, SnowflakeError};
+use crate::generator_state::GeneratorState;
+use crate::layout::Layout;
+use crate::ordered::{self, TIME_ORDERED_MARKER};
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+
+const TIMESTAMP_BITS: u32 = 41;
+const WORKER_BITS: u32 = 10;
+const SEQUENCE_BITS: u32 = 12;
+
+const MAX_SEQUENCE: u16 = (1 << SEQUENCE_BITS) - 1;
+const MAX_TIMESTAMP: u64 = (1 << TIMESTAMP_BITS) - 1;
+
+/// Snowflake IDs (41-bit timestamp, 10-bit worker ID, 12-bit sequence) in the typed
+/// UUID container.
+///
+/// A Snowflake maps onto the [`TimeOrdered`](Layout::TimeOrdered) layout: its
+/// timestamp, shifted to the Unix epoch, fills the millisecond field, and the worker ID
+/// and sequence take the 22 bits after the layout flag. The IDs therefore keep the
+/// discriminant and prefix format, sort by time, then worker, then sequence, and
+/// report their creation time through [`TypedUuid::timestamp`]. Any existing Snowflake
+/// can be [encoded](Self::encode) and later [decoded](Self::decode) losslessly.
+///
+/// Nothing marks an ID as a Snowflake rather than a plain time-ordered ID; decoding a
+/// plain one returns its random bits as worker and sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnowflakeLayout {
+    epoch_millis: u64,
+}
+
+impl SnowflakeLayout {
+    /// Largest worker ID a Snowflake can hold.
+    pub const MAX_WORKER_ID: u16 = (1 << WORKER_BITS) - 1;
+
+    /// The layout of Twitter's original Snowflakes, with their 2010-11-04 epoch.
+    pub const TWITTER: Self = Self::with_epoch_millis(1_288_834_974_657);
+
+    /// A layout whose timestamps count milliseconds from `epoch_millis` after the Unix epoch.
+    pub const fn with_epoch_millis(epoch_millis: u64) -> Self {
+        Self { epoch_millis }
+    }
+
+    /// Returns the layout's epoch in Unix milliseconds.
+    pub fn epoch_millis(&self) -> u64 {
+        self.epoch_millis
+    }
+
+    /// Wraps an existing Snowflake in a typed ID.
+    ///
+    /// Fails if the Snowflake's sign bit is set, or if its time is past the 44-bit
+    /// millisecond field of a time-ordered ID, which only a layout with an epoch after
+    /// the year 2450 can reach.
+    pub fn encode<T: UuidType>(&self, variant: T, snowflake: u64) -> Result<TypedUuid<T>, SnowflakeError> {
+        if snowflake >> (TIMESTAMP_BITS + WORKER_BITS + SEQUENCE_BITS) != 0 {
+            return Err(SnowflakeError::SignBitSet(snowflake));
+        }
+        let timestamp = snowflake >> (WORKER_BITS + SEQUENCE_BITS);
+        let millis = self
+            .epoch_millis
+            .checked_add(timestamp)
+            .filter(|&millis| millis <= ordered::MAX_MILLIS)
+            .ok_or(SnowflakeError::TimestampOutOfRange {
+                epoch_millis: self.epoch_millis,
+                timestamp,
+            })?;
+        let low = (snowflake & ((1 << (WORKER_BITS + SEQUENCE_BITS)) - 1)) as u32;
+
+        let mut bytes: [u8; 16] = rand::random();
+        ordered::write_header(variant, millis, &mut bytes);
+        bytes[8] = TIME_ORDERED_MARKER | (low >> 17) as u8;
+        bytes[9] = (low >> 9) as u8;
+        bytes[10] = (low >> 1) as u8;
+        bytes[11] = (bytes[11] & 0x7F) | ((low as u8 & 1) << 7);
+
+        // SAFETY: byte 0 was just set to the variant's discriminant, byte 1 to the current schema
+        Ok(unsafe { TypedUuid::from_uuid_unchecked(Uuid::from_bytes(bytes)) })
+    }
+
+    /// Recovers the Snowflake from a typed ID, or returns `None` if the ID is not
+    /// time-ordered or its timestamp does not fit this layout's epoch.
+    pub fn decode<T: UuidType>(&self, id: &TypedUuid<T>) -> Option<u64> {
+        if id.layout() != Layout::TimeOrdered {
+            return None;
+        }
+        let timestamp = ordered::read_millis(id.as_uuid()).checked_sub(self.epoch_millis)?;
+        if timestamp >> TIMESTAMP_BITS != 0 {
+            return None;
+        }
+
+        let bytes = id.as_bytes();
+        let low = ((bytes[8] as u64 & 0x1F) << 17)
+            | ((bytes[9] as u64) << 9)
+            | ((bytes[10] as u64) << 1)
+            | (bytes[11] as u64 >> 7);
+        Some((timestamp << (WORKER_BITS + SEQUENCE_BITS)) | low)
+    }
+}
+
+/// Supplies the worker ID of a [`SnowflakeGenerator`].
+///
+/// Worker IDs must be unique among processes generating IDs at the same time; typical
+/// sources are a StatefulSet ordinal, a lease in a coordination service or a config value.
+pub trait WorkerIdProvider {
+    /// Returns this process's worker ID, at most [`SnowflakeLayout::MAX_WORKER_ID`].
+    fn worker_id(&self) -> u16;
+}
+
+/// A fixed worker ID.
+impl WorkerIdProvider for u16 {
+    fn worker_id(&self) -> u16 {
+        *self
+    }
+}
+
+impl<F: Fn() -> u16> WorkerIdProvider for F {
+    fn worker_id(&self) -> u16 {
+        self()
+    }
+}
+
+/// Generates Snowflake-layout typed IDs for one worker.
+///
+/// Up to 4096 IDs per millisecond; past that, IDs are stamped with the next
+/// millisecond, running slightly ahead of the clock until it catches up. If the clock
+/// goes backwards, keeps counting from the last timestamp, so IDs never repeat or go
+/// out of order.
+///
+/// Once the clock passes the last millisecond the 41-bit timestamp can hold, IDs stay
+/// stamped with it; after its 4096 sequences, generation fails with
+/// [`SnowflakeError::Exhausted`].
+#[derive(Debug)]
+pub struct SnowflakeGenerator<C: Clock = SystemClock> {
+    layout: SnowflakeLayout,
+    worker_id: u16,
+    clock: C,
+    last: Mutex<(u64, u16)>,
+}
+
+impl SnowflakeGenerator {
+    /// Creates a generator, reading the worker ID from `provider` once.
+    ///
+    /// # Panics
+    /// Panics if the worker ID is larger than [`SnowflakeLayout::MAX_WORKER_ID`].
+    pub fn new(layout: SnowflakeLayout, provider: impl WorkerIdProvider) -> Self {
+        Self::with_clock(layout, provider, SystemClock)
+    }
+}
+
+impl<C: Clock> SnowflakeGenerator<C> {
+    /// Like [`new`](SnowflakeGenerator::new), but reads the time from `clock`.
+    ///
+    /// # Panics
+    /// Panics if the worker ID is larger than [`SnowflakeLayout::MAX_WORKER_ID`].
+    pub fn with_clock(layout: SnowflakeLayout, provider: impl WorkerIdProvider, clock: C) -> Self {
+        let worker_id = provider.worker_id();
+        assert!(
+            worker_id <= SnowflakeLayout::MAX_WORKER_ID,
+            "Snowflake worker ID {} is larger than {}",
+            worker_id,
+            SnowflakeLayout::MAX_WORKER_ID
+        );
+        Self {
+            layout,
+            worker_id,
+            clock,
+            last: Mutex::new((0, 0)),
+        }
+    }
+
+    /// Returns the layout IDs are generated with.
+    pub fn layout(&self) -> SnowflakeLayout {
+        self.layout
+    }
+
+    /// Returns the worker ID embedded in every generated ID.
+    pub fn worker_id(&self) -> u16 {
+        self.worker_id
+    }
+
+    /// Returns a snapshot of the last Snowflake's timestamp and sequence, to save
+    /// for [`restore`](Self::restore) after a restart.
+    pub fn state(&self) -> GeneratorState {
+        let last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        GeneratorState::new(self.layout.epoch_millis, self.worker_id, last.0, last.1)
+    }
+
+    /// Makes the generator continue strictly after the Snowflake recorded in `state`,
+    /// whatever the clock says. A state older than what the generator has already
+    /// issued changes nothing.
+    ///
+    /// Fails if the state was saved by a generator with another epoch or worker ID.
+    pub fn restore(&self, state: &GeneratorState) -> Result<(), GeneratorStateError> {
+        if state.epoch_millis() != self.layout.epoch_millis {
+            return Err(GeneratorStateError::EpochMismatch {
+                expected: self.layout.epoch_millis,
+                found: state.epoch_millis(),
+            });
+        }
+        if state.worker_id() != self.worker_id {
+            return Err(GeneratorStateError::WorkerMismatch {
+                expected: self.worker_id,
+                found: state.worker_id(),
+            });
+        }
+
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        *last = (*last).max((state.timestamp(), state.sequence()));
+        Ok(())
+    }
+
+    /// Generates the next Snowflake.
+    ///
+    /// Fails with [`SnowflakeError::Exhausted`] once every Snowflake of the last
+    /// representable millisecond has been issued.
+    pub fn next_snowflake(&self) -> Result<u64, SnowflakeError> {
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        let now = self.now();
+        let (timestamp, sequence) = if now > last.0 {
+            (now, 0)
+        } else if last.1 < MAX_SEQUENCE {
+            (last.0, last.1 + 1)
+        } else if last.0 < MAX_TIMESTAMP {
+            // Sequence exhausted: borrow the next millisecond rather than wait for it
+            (last.0 + 1, 0)
+        } else {
+            return Err(SnowflakeError::Exhausted);
+        };
+        *last = (timestamp, sequence);
+
+        Ok((timestamp << (WORKER_BITS + SEQUENCE_BITS))
+            | ((self.worker_id as u64) << SEQUENCE_BITS)
+            | sequence as u64)
+    }
+
+    /// Generates the next ID of `variant`.
+    ///
+    /// Fails like [`next_snowflake`](Self::next_snowflake), or like
+    /// [`SnowflakeLayout::encode`] if the layout's epoch is too late for the timestamp.
+    pub fn generate<T: UuidType>(&self, variant: T) -> Result<TypedUuid<T>, SnowflakeError> {
+        self.layout.encode(variant, self.next_snowflake()?)
+    }
+
+    /// Milliseconds since the layout's epoch, clamped to the 41-bit range.
+    fn now(&self) -> u64 {
+        let millis = self
+            .clock
+            .now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        millis
+            .saturating_sub(self.layout.epoch_millis)
+            .min(MAX_TIMESTAMP)
+    }
+}
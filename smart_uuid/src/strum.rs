@@ -0,0 +1,85 @@
+/// Implements [`UuidType`](crate::UuidType) for an enum that already derives
+/// `strum::{EnumIter, IntoStaticStr, FromRepr}`, reusing its discriminants and names.
+///
+/// - the discriminant is the enum's own discriminant, decoded with `from_repr`
+/// - the prefix is the `&'static str` from `IntoStaticStr`, so `#[strum(serialize = ...)]`
+///   and `#[strum(serialize_all = ...)]` carry over
+/// - `from_prefix` scans `EnumIter`
+/// - [`DISCRIMINANTS`](crate::UuidType::DISCRIMINANTS) is collected at compile time by
+///   trying every `u8` with `from_repr`, so `const` checks such as
+///   [`TypedUuid::from_bytes_const`](crate::TypedUuid::from_bytes_const) work
+///
+/// Every discriminant must fit in a `u8`; give the enum `#[repr(u8)]` so the compiler
+/// checks it. Expands to paths under `::strum`, so the calling crate must depend on
+/// `strum` directly.
+///
+/// # Example
+/// ```
+/// use smart_uuid::{impl_uuid_type_from_strum, TypedUuid, UserFriendlyUuid};
+/// use strum::{EnumIter, FromRepr, IntoStaticStr};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, FromRepr, IntoStaticStr)]
+/// #[strum(serialize_all = "snake_case")]
+/// #[repr(u8)]
+/// enum LegacyKind {
+///     Customer = 3,
+///     #[strum(serialize = "org")]
+///     Organization = 7,
+/// }
+///
+/// impl_uuid_type_from_strum!(LegacyKind);
+///
+/// let id = UserFriendlyUuid::from(TypedUuid::new(LegacyKind::Organization));
+/// assert!(id.to_string().starts_with("org_07"));
+/// ```
+#[macro_export]
+macro_rules! impl_uuid_type_from_strum {
+    ($ty:ty) => {
+        impl $crate::UuidType for $ty {
+            const DISCRIMINANTS: &'static [u8] = {
+                // strum's `from_repr` is a `const fn` for fieldless enums
+                const fn is_discriminant(value: u8) -> bool {
+                    <$ty>::from_repr(value as _).is_some()
+                }
+                const COUNT: usize = {
+                    let (mut count, mut value) = (0, 0u16);
+                    while value <= u8::MAX as u16 {
+                        if is_discriminant(value as u8) {
+                            count += 1;
+                        }
+                        value += 1;
+                    }
+                    count
+                };
+                const LIST: [u8; COUNT] = {
+                    let (mut list, mut count, mut value) = ([0u8; COUNT], 0, 0u16);
+                    while value <= u8::MAX as u16 {
+                        if is_discriminant(value as u8) {
+                            list[count] = value as u8;
+                            count += 1;
+                        }
+                        value += 1;
+                    }
+                    list
+                };
+                &LIST
+            };
+
+            fn discriminant(&self) -> u8 {
+                *self as u8
+            }
+
+            fn from_discriminant(value: u8) -> ::core::option::Option<Self> {
+                <$ty>::from_repr(::core::convert::From::from(value))
+            }
+
+            fn prefix(&self) -> &'static str {
+                <&'static str as ::core::convert::From<$ty>>::from(*self)
+            }
+
+            fn from_prefix(prefix: &str) -> ::core::option::Option<Self> {
+                <$ty as ::strum::IntoEnumIterator>::iter().find(|v| $crate::UuidType::prefix(v) == prefix)
+            }
+        }
+    };
+}
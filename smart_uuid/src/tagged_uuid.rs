@@ -0,0 +1,303 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use uuid::Uuid;
+
+use crate::error::TypedUuidError;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::split_prefix;
+
+/// A marker type naming the prefix of a [`TaggedUuid`].
+///
+/// ```
+/// use smart_uuid::PrefixTag;
+///
+/// enum Org {}
+///
+/// impl PrefixTag for Org {
+///     const PREFIX: &'static str = "org";
+/// }
+/// ```
+pub trait PrefixTag: 'static {
+    /// The prefix written before the UUID, e.g. `"org"`.
+    const PREFIX: &'static str;
+}
+
+/// **Experimental.** A typed ID whose discriminant is part of its type.
+///
+/// Where `TypedUuid<UserType>` holds any variant of the enum, `TaggedUuid<2, Org>`
+/// holds only IDs with discriminant 2 and prefix `org`, so passing a retail ID where an
+/// organization ID is expected fails to compile rather than at runtime:
+///
+/// ```compile_fail
+/// use smart_uuid::{PrefixTag, TaggedUuid};
+///
+/// enum Retail {}
+/// impl PrefixTag for Retail {
+///     const PREFIX: &'static str = "retail";
+/// }
+/// enum Org {}
+/// impl PrefixTag for Org {
+///     const PREFIX: &'static str = "org";
+/// }
+///
+/// fn close_org(id: TaggedUuid<2, Org>) {}
+/// close_org(TaggedUuid::<0, Retail>::new());
+/// ```
+///
+/// The bytes are laid out exactly like `TypedUuid`'s, and [`to_typed`](Self::to_typed)
+/// and [`TypedUuid::to_tagged`] convert between the two, so code can adopt it one
+/// function at a time.
+///
+/// IDs can be built in `const` context with [`from_u128`](Self::from_u128), e.g. for
+/// well-known IDs:
+///
+/// ```
+/// use smart_uuid::{PrefixTag, TaggedUuid};
+///
+/// enum Org {}
+/// impl PrefixTag for Org {
+///     const PREFIX: &'static str = "org";
+/// }
+///
+/// const ROOT_ORG: TaggedUuid<2, Org> = TaggedUuid::from_u128(0x0200_0000_0000_8000_8000_0000_0000_0001);
+/// assert_eq!(ROOT_ORG.to_string(), "org_02000000-0000-8000-8000-000000000001");
+/// ```
+///
+/// Displays, parses and serializes as `{prefix}_{uuid}`; hashes and orders like the
+/// underlying [`Uuid`]. A `TaggedUuid` knows nothing of schema versions: byte 1 is kept
+/// as is, and [`to_typed`](Self::to_typed) reads it like any stored UUID.
+pub struct TaggedUuid<const DISC: u8, P: PrefixTag> {
+    uuid: Uuid,
+    _marker: PhantomData<P>,
+}
+
+impl<const DISC: u8, P: PrefixTag> TaggedUuid<DISC, P> {
+    /// The discriminant in byte 0 of every ID of this type.
+    pub const DISCRIMINANT: u8 = DISC;
+
+    /// Creates a new random v8 ID, like [`TypedUuid::new`].
+    pub fn new() -> Self {
+        let mut bytes: [u8; 16] = rand::random();
+        bytes[0] = DISC;
+        Self {
+            uuid: Uuid::new_v8(bytes),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Wraps a UUID given as a `u128`, in `const` context.
+    ///
+    /// # Panics
+    /// Panics (at compile time, in a `const`) if the top byte is not `DISC`.
+    pub const fn from_u128(value: u128) -> Self {
+        assert!((value >> 120) as u8 == DISC, "TaggedUuid::from_u128: byte 0 is not the discriminant");
+        Self {
+            uuid: Uuid::from_u128(value),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Wraps `uuid`, failing with [`TypedUuidError::InvalidDiscriminant`] if byte 0 is
+    /// not `DISC`.
+    pub fn from_uuid(uuid: Uuid) -> Result<Self, TypedUuidError> {
+        let found = uuid.as_bytes()[0];
+        if found != DISC {
+            return Err(TypedUuidError::InvalidDiscriminant {
+                found,
+                type_name: std::any::type_name::<Self>(),
+            });
+        }
+        Ok(Self {
+            uuid,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns the prefix, `P::PREFIX`.
+    pub fn prefix(&self) -> &'static str {
+        P::PREFIX
+    }
+
+    /// Returns a reference to the underlying UUID.
+    pub const fn as_uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+
+    /// Consumes self and returns the underlying UUID.
+    pub const fn into_uuid(self) -> Uuid {
+        self.uuid
+    }
+
+    /// Converts to the `TypedUuid` of an enum, checking that `T` has a variant with
+    /// this discriminant and prefix.
+    ///
+    /// The UUID goes through [`TypedUuid::from_uuid`], so if `T` has a
+    /// [`Migrator`](crate::Migrator) the result is the one parsing the ID would give.
+    /// IDs made by [`new`](Self::new) or [`from_u128`](Self::from_u128) carry no schema
+    /// version and are read as written before `T` opted in; for a versioned enum, make
+    /// IDs with [`TypedUuid::new`] and [`TypedUuid::to_tagged`] instead.
+    ///
+    /// Fails with [`TypedUuidError::InvalidDiscriminant`] if `T` has no such
+    /// discriminant, or [`TypedUuidError::UnknownPrefix`] if its variant uses another prefix.
+    pub fn to_typed<T: UuidType>(self) -> Result<TypedUuid<T>, TypedUuidError> {
+        let typed = TypedUuid::<T>::from_uuid(self.uuid)?;
+        // Checked after any migration, which may have moved the ID to another variant
+        if typed.variant_type().prefix() != P::PREFIX {
+            return Err(TypedUuidError::unknown_prefix::<T>(P::PREFIX));
+        }
+        Ok(typed)
+    }
+}
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Converts to a [`TaggedUuid`], checking the discriminant and that the variant's
+    /// prefix is `P::PREFIX`.
+    ///
+    /// Fails with [`TypedUuidError::InvalidDiscriminant`] if this ID is another
+    /// variant, or [`TypedUuidError::UnknownPrefix`] if the prefixes differ.
+    pub fn to_tagged<const DISC: u8, P: PrefixTag>(&self) -> Result<TaggedUuid<DISC, P>, TypedUuidError> {
+        let tagged = TaggedUuid::from_uuid(*self.as_uuid())?;
+        check_variant::<T, P>(DISC)?;
+        Ok(tagged)
+    }
+}
+
+/// Checks that `T`'s variant with `discriminant` exists and uses `P`'s prefix.
+fn check_variant<T: UuidType, P: PrefixTag>(discriminant: u8) -> Result<(), TypedUuidError> {
+    let variant = T::from_discriminant(discriminant).ok_or(TypedUuidError::InvalidDiscriminant {
+        found: discriminant,
+        type_name: std::any::type_name::<T>(),
+    })?;
+    if variant.prefix() != P::PREFIX {
+        return Err(TypedUuidError::unknown_prefix::<T>(P::PREFIX));
+    }
+    Ok(())
+}
+
+impl<const DISC: u8, P: PrefixTag> Default for TaggedUuid<DISC, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Manual impls: derives would require `P` to implement each trait
+impl<const DISC: u8, P: PrefixTag> Clone for TaggedUuid<DISC, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const DISC: u8, P: PrefixTag> Copy for TaggedUuid<DISC, P> {}
+
+impl<const DISC: u8, P: PrefixTag> PartialEq for TaggedUuid<DISC, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.uuid == other.uuid
+    }
+}
+
+impl<const DISC: u8, P: PrefixTag> Eq for TaggedUuid<DISC, P> {}
+
+impl<const DISC: u8, P: PrefixTag> PartialOrd for TaggedUuid<DISC, P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const DISC: u8, P: PrefixTag> Ord for TaggedUuid<DISC, P> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.uuid.cmp(&other.uuid)
+    }
+}
+
+impl<const DISC: u8, P: PrefixTag> Hash for TaggedUuid<DISC, P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.uuid.hash(state);
+    }
+}
+
+impl<const DISC: u8, P: PrefixTag> fmt::Debug for TaggedUuid<DISC, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaggedUuid")
+            .field("uuid", &self.uuid)
+            .field("prefix", &P::PREFIX)
+            .finish()
+    }
+}
+
+impl<const DISC: u8, P: PrefixTag> fmt::Display for TaggedUuid<DISC, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}_{}", P::PREFIX, self.uuid)
+    }
+}
+
+impl<const DISC: u8, P: PrefixTag> FromStr for TaggedUuid<DISC, P> {
+    type Err = TypedUuidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (prefix, body) = split_prefix(s)?;
+        if prefix != P::PREFIX {
+            return Err(TypedUuidError::UnknownPrefix {
+                prefix: prefix.to_string(),
+                type_name: std::any::type_name::<Self>(),
+                expected: std::borrow::Cow::Borrowed(const { &[P::PREFIX] }),
+                did_you_mean: crate::error::did_you_mean(prefix, [P::PREFIX]),
+            });
+        }
+        Self::from_uuid(Uuid::parse_str(body)?)
+    }
+}
+
+impl<const DISC: u8, P: PrefixTag> TryFrom<Uuid> for TaggedUuid<DISC, P> {
+    type Error = TypedUuidError;
+
+    fn try_from(uuid: Uuid) -> Result<Self, Self::Error> {
+        Self::from_uuid(uuid)
+    }
+}
+
+impl<const DISC: u8, P: PrefixTag, T: UuidType> TryFrom<TypedUuid<T>> for TaggedUuid<DISC, P> {
+    type Error = TypedUuidError;
+
+    fn try_from(id: TypedUuid<T>) -> Result<Self, Self::Error> {
+        id.to_tagged()
+    }
+}
+
+impl<const DISC: u8, P: PrefixTag, T: UuidType> TryFrom<TaggedUuid<DISC, P>> for TypedUuid<T> {
+    type Error = TypedUuidError;
+
+    fn try_from(id: TaggedUuid<DISC, P>) -> Result<Self, Self::Error> {
+        id.to_typed()
+    }
+}
+
+impl<const DISC: u8, P: PrefixTag> From<TaggedUuid<DISC, P>> for Uuid {
+    fn from(id: TaggedUuid<DISC, P>) -> Self {
+        id.uuid
+    }
+}
+
+impl<const DISC: u8, P: PrefixTag> Serialize for TaggedUuid<DISC, P> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, const DISC: u8, P: PrefixTag> Deserialize<'de> for TaggedUuid<DISC, P> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,358 @@
+//! Helpers for writing tests against code that uses typed UUIDs.
+//!
+//! Only built with the `test-util` feature; enable it from `[dev-dependencies]`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use crate::clock::Clock;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::UserFriendlyUuid;
+
+/// Largest counter value that fits in the 48-bit node field of a fixture ID.
+const MAX_COUNTER: u64 = (1 << 48) - 1;
+
+/// Mints stable, human-recognizable IDs for tests.
+///
+/// Each variant has its own counter starting at 1, and the counter is written
+/// into the last group of the UUID. Everything else stays zero apart from the
+/// discriminant and the v8 version/variant bits, so output reads like:
+///
+/// ```text
+/// retail_00000000-0000-8000-8000-000000000001
+/// retail_00000000-0000-8000-8000-000000000002
+/// business_01000000-0000-8000-8000-000000000001
+/// ```
+///
+/// Fixture IDs are valid `TypedUuid`s and round-trip through parsing and serde
+/// like any other ID. They are deterministic, so never use them outside tests.
+#[derive(Debug, Clone)]
+pub struct FixtureFactory<T: UuidType> {
+    counters: HashMap<u8, u64>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: UuidType> FixtureFactory<T> {
+    /// Creates a factory with every variant's counter at zero.
+    pub fn new() -> Self {
+        Self {
+            counters: HashMap::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the next fixture ID for the given variant.
+    ///
+    /// # Panics
+    /// Panics if the variant's counter exceeds 2^48 - 1.
+    pub fn next(&mut self, variant: T) -> TypedUuid<T> {
+        let counter = self.counters.entry(variant.discriminant()).or_insert(0);
+        *counter += 1;
+        assert!(*counter <= MAX_COUNTER, "FixtureFactory counter overflow for {:?}", variant);
+        Self::fixture(variant, *counter)
+    }
+
+    /// Returns the next fixture ID for the given variant in user-friendly form.
+    pub fn next_friendly(&mut self, variant: T) -> UserFriendlyUuid<T> {
+        self.next(variant).into()
+    }
+
+    /// Returns the fixture ID with the given counter value, without advancing any counter.
+    ///
+    /// Useful for writing the expected value in an assertion:
+    /// `FixtureFactory::fixture(UserType::Retail, 1)`.
+    ///
+    /// # Panics
+    /// Panics if `counter` exceeds 2^48 - 1.
+    pub fn fixture(variant: T, counter: u64) -> TypedUuid<T> {
+        assert!(counter <= MAX_COUNTER, "FixtureFactory counter overflow for {:?}", variant);
+
+        // The counter lives in bytes 10..16 (the node field), clear of the
+        // version and variant bits that `new_v8` sets in bytes 6 and 8.
+        let mut bytes = [0u8; 16];
+        bytes[10..].copy_from_slice(&counter.to_be_bytes()[2..]);
+
+        TypedUuid::from_v8_bytes(variant, bytes)
+    }
+
+    /// Returns how many IDs have been minted for the given variant.
+    pub fn count(&self, variant: T) -> u64 {
+        self.counters
+            .get(&variant.discriminant())
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Resets every variant's counter to zero.
+    pub fn reset(&mut self) {
+        self.counters.clear();
+    }
+}
+
+impl<T: UuidType> Default for FixtureFactory<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A [`Clock`] that only moves when told to, for testing time-ordered IDs and expiry
+/// without sleeping.
+///
+/// Pass it by reference (or in an `Arc`) so the test keeps a handle to advance it:
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use smart_uuid::test::MockClock;
+/// use smart_uuid::{TypedUuid, UuidType};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+/// enum OrderType { Order }
+///
+/// let clock = MockClock::new(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+/// let first = TypedUuid::new_ordered_with_clock(OrderType::Order, &clock);
+/// clock.advance(Duration::from_millis(1));
+/// let second = TypedUuid::new_ordered_with_clock(OrderType::Order, &clock);
+/// assert!(first.as_uuid() < second.as_uuid());
+/// ```
+#[derive(Debug)]
+pub struct MockClock {
+    now: Mutex<SystemTime>,
+}
+
+impl MockClock {
+    /// Creates a clock stopped at `now`.
+    pub fn new(now: SystemTime) -> Self {
+        Self { now: Mutex::new(now) }
+    }
+
+    /// Moves the clock to `now`, which may be in the past.
+    pub fn set(&self, now: SystemTime) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = now;
+    }
+
+    /// Moves the clock forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) += by;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Placeholder that [`redactions`] and [`filters`] write over generated IDs.
+#[cfg(feature = "insta")]
+pub const TYPED_UUID_PLACEHOLDER: &str = "[typed_uuid]";
+
+/// Returns [insta](https://docs.rs/insta) redactions replacing every ID in a
+/// serialized snapshot with [`TYPED_UUID_PLACEHOLDER`].
+///
+/// Matches any string value, at any depth, holding a bare UUID or a `prefix_uuid`.
+/// Friendly IDs keep their prefix, so the snapshot still shows which type of ID
+/// was there: `"retail_[typed_uuid]"`. Other values are left alone.
+///
+/// ```text
+/// let mut settings = insta::Settings::clone_current();
+/// for (selector, redaction) in smart_uuid::test::redactions() {
+///     settings.add_redaction(selector, redaction);
+/// }
+/// settings.bind(|| insta::assert_json_snapshot!(response));
+/// ```
+#[cfg(feature = "insta")]
+pub fn redactions() -> Vec<(&'static str, insta::internals::Redaction)> {
+    use insta::internals::Content;
+
+    let redaction = insta::dynamic_redaction(|value: Content, _path| {
+        match value.as_str().and_then(redact_id) {
+            Some(redacted) => Content::from(redacted),
+            None => value,
+        }
+    });
+    vec![(".**", redaction)]
+}
+
+/// Returns insta filters (regex and replacement) doing the same as [`redactions`]
+/// on snapshot text, for `assert_snapshot!` and `assert_debug_snapshot!` where
+/// there is no serialized structure to select from.
+///
+/// Friendly IDs are replaced first so their prefix survives.
+#[cfg(feature = "insta")]
+pub fn filters() -> Vec<(&'static str, &'static str)> {
+    vec![
+        (
+            r"\b([a-z][a-z0-9_]*)_[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}\b",
+            "${1}_[typed_uuid]",
+        ),
+        (
+            r"\b[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}\b",
+            TYPED_UUID_PLACEHOLDER,
+        ),
+    ]
+}
+
+/// Returns the current insta settings with [`redactions`] and [`filters`] added.
+///
+/// ```text
+/// smart_uuid::test::insta_settings().bind(|| insta::assert_json_snapshot!(response));
+/// ```
+#[cfg(feature = "insta")]
+pub fn insta_settings() -> insta::Settings {
+    let mut settings = insta::Settings::clone_current();
+    for (selector, redaction) in redactions() {
+        settings.add_redaction(selector, redaction);
+    }
+    for (regex, replacement) in filters() {
+        settings.add_filter(regex, replacement);
+    }
+    settings
+}
+
+/// Redacts `value` if it is a bare or `prefix_uuid` ID.
+#[cfg(feature = "insta")]
+fn redact_id(value: &str) -> Option<String> {
+    if uuid::Uuid::try_parse(value).is_ok() {
+        return Some(TYPED_UUID_PLACEHOLDER.to_string());
+    }
+    let (prefix, body) = crate::user_friendly_uuid::split_prefix(value).ok()?;
+    let is_prefix = prefix.starts_with(|c: char| c.is_ascii_lowercase())
+        && prefix.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_');
+    (is_prefix && uuid::Uuid::try_parse(body).is_ok()).then(|| format!("{}_{}", prefix, TYPED_UUID_PLACEHOLDER))
+}
+
+/// Asserts that a typed ID carries the expected variant.
+///
+/// Works with both [`TypedUuid`] and [`UserFriendlyUuid`]. On failure the panic
+/// message shows the expected and actual variants, their prefixes and the full ID.
+///
+/// ```
+/// use smart_uuid::{assert_variant, TypedUuid, UuidType};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+/// enum UserType { Retail, Business }
+///
+/// let id = TypedUuid::new(UserType::Retail);
+/// assert_variant!(id, UserType::Retail);
+/// ```
+#[macro_export]
+macro_rules! assert_variant {
+    ($id:expr, $variant:expr $(,)?) => {{
+        let id = &$id;
+        let expected = $variant;
+        if id.variant_type() != expected {
+            $crate::test::variant_mismatch(id, id.variant_type(), expected, ::core::option::Option::None);
+        }
+    }};
+    ($id:expr, $variant:expr, $($arg:tt)+) => {{
+        let id = &$id;
+        let expected = $variant;
+        if id.variant_type() != expected {
+            $crate::test::variant_mismatch(
+                id,
+                id.variant_type(),
+                expected,
+                ::core::option::Option::Some(::core::format_args!($($arg)+)),
+            );
+        }
+    }};
+}
+
+/// Asserts that two typed IDs are equal.
+///
+/// Like `assert_eq!`, but the panic message shows each side's variant, prefix
+/// and full value instead of the raw `Debug` output.
+///
+/// ```
+/// use smart_uuid::{assert_same_id, TypedUuid, UuidType};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+/// enum UserType { Retail, Business }
+///
+/// let id = TypedUuid::new(UserType::Retail);
+/// let copy = id;
+/// assert_same_id!(id, copy);
+/// ```
+#[macro_export]
+macro_rules! assert_same_id {
+    ($left:expr, $right:expr $(,)?) => {{
+        let (left, right) = (&$left, &$right);
+        if left != right {
+            $crate::test::id_mismatch(
+                left,
+                left.variant_type(),
+                right,
+                right.variant_type(),
+                ::core::option::Option::None,
+            );
+        }
+    }};
+    ($left:expr, $right:expr, $($arg:tt)+) => {{
+        let (left, right) = (&$left, &$right);
+        if left != right {
+            $crate::test::id_mismatch(
+                left,
+                left.variant_type(),
+                right,
+                right.variant_type(),
+                ::core::option::Option::Some(::core::format_args!($($arg)+)),
+            );
+        }
+    }};
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn variant_mismatch<T: UuidType>(
+    id: &dyn fmt::Display,
+    actual: T,
+    expected: T,
+    message: Option<fmt::Arguments<'_>>,
+) -> ! {
+    panic!(
+        "assertion `id has variant` failed{}\n  expected variant: {:?} (prefix `{}`)\n    actual variant: {:?} (prefix `{}`)\n                id: {}",
+        MessageSuffix(message),
+        expected,
+        expected.prefix(),
+        actual,
+        actual.prefix(),
+        id,
+    )
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn id_mismatch<A: UuidType, B: UuidType>(
+    left: &dyn fmt::Display,
+    left_variant: A,
+    right: &dyn fmt::Display,
+    right_variant: B,
+    message: Option<fmt::Arguments<'_>>,
+) -> ! {
+    panic!(
+        "assertion `left == right` failed{}\n   left: {} ({:?}, prefix `{}`)\n  right: {} ({:?}, prefix `{}`)",
+        MessageSuffix(message),
+        left,
+        left_variant,
+        left_variant.prefix(),
+        right,
+        right_variant,
+        right_variant.prefix(),
+    )
+}
+
+/// Renders the optional custom assertion message as `: message`.
+struct MessageSuffix<'a>(Option<fmt::Arguments<'a>>);
+
+impl fmt::Display for MessageSuffix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(message) => write!(f, ": {}", message),
+            None => Ok(()),
+        }
+    }
+}
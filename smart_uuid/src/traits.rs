@@ -1,5 +1,12 @@
 use std::fmt::Debug;
 
+use crate::any_typed_uuid::AnyTypedUuid;
+use crate::error::TypedUuidError;
+use crate::format::FormatConfig;
+use crate::migrate::Migrator;
+use crate::type_info::{self, TypeInfo};
+use crate::user_friendly_uuid::UserFriendlyUuid;
+
 /// Trait that must be implemented by enum types used with TypedUuid and UserFriendlyUuid.
 ///
 /// This trait is typically derived using `#[derive(UuidType)]` rather than implemented manually.
@@ -8,6 +15,15 @@ use std::fmt::Debug;
 /// - Byte discriminant encoding for UUID storage
 /// - String prefix for human-readable formatting
 pub trait UuidType: Copy + Clone + Eq + PartialEq + Debug + Sized {
+    /// Every discriminant in use, for checks in `const` context such as
+    /// [`TypedUuid::from_bytes_const`](crate::TypedUuid::from_bytes_const).
+    ///
+    /// The derive macro and [`impl_uuid_type_from_strum!`](crate::impl_uuid_type_from_strum)
+    /// emit the list. Hand-written implementations must set it to use those checks: the
+    /// default is empty, and with it every `const` check fails with a message saying the
+    /// list is missing.
+    const DISCRIMINANTS: &'static [u8] = &[];
+
     /// Returns the byte discriminant for this variant.
     /// Used internally to encode the type in UUID byte 0.
     fn discriminant(&self) -> u8;
@@ -18,4 +34,125 @@ pub trait UuidType: Copy + Clone + Eq + PartialEq + Debug + Sized {
 
     /// Returns the prefix string used in UserFriendlyUuid formatting.
     fn prefix(&self) -> &'static str;
+
+    /// Returns static metadata (enum name, variant name, prefix) for this variant.
+    ///
+    /// The derive macro emits these as statics. The default implementation builds
+    /// and caches them on first use, naming the variant after its `Debug` output.
+    fn type_info(&self) -> &'static TypeInfo
+    where
+        Self: 'static,
+    {
+        type_info::cached(*self)
+    }
+
+    /// Returns the variant using this prefix, or `None` if no variant does.
+    ///
+    /// The derive macro emits a `match`. The default implementation scans every variant.
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        variants::<Self>().find(|v| v.prefix() == prefix)
+    }
+
+    /// Returns the migrator for this enum's schema versions, if it opts into versioning.
+    ///
+    /// When `Some`, byte 1 of every ID holds the schema version: new IDs get the
+    /// migrator's current version, and [`TypedUuid::from_uuid`](crate::TypedUuid::from_uuid)
+    /// upgrades IDs written with an older one. Defaults to `None`; with the derive, set
+    /// it with `#[uuid_type(migrator = PATH)]` on the enum.
+    ///
+    /// The lifetime spares `UuidType` a `'static` bound; implementations return a static.
+    fn migrator<'a>() -> Option<&'a dyn Migrator<Self>>
+    where
+        Self: 'a,
+    {
+        None
+    }
+
+    /// Returns the ID space this enum shares with others, if any.
+    ///
+    /// Enums in one space store IDs side by side, e.g. in a single column, and must
+    /// not share discriminants, which [`UuidRegistry::verify`](crate::UuidRegistry::verify)
+    /// checks. The derive numbers every enum's variants from 0, so such enums need
+    /// `#[uuid_type(discriminant = "hash")]` or a manual implementation. Defaults to
+    /// `None`; with the derive, set it with `#[uuid_type(id_space = "...")]` on the enum.
+    fn id_space() -> Option<&'static str> {
+        None
+    }
+
+    /// Returns how `UserFriendlyUuid`'s `Display` renders this enum's IDs.
+    ///
+    /// Defaults to `None`, following the process-wide [`FormatConfig::global`]; with
+    /// the derive, set it with `#[uuid_type(format = EXPR)]` on the enum.
+    fn format_config() -> Option<FormatConfig> {
+        None
+    }
+
+    /// Returns the prefixes of all variants, in discriminant order.
+    ///
+    /// The derive macro emits a static list. The default implementation builds
+    /// and caches it on first use.
+    fn prefixes() -> &'static [&'static str]
+    where
+        Self: 'static,
+    {
+        type_info::cached_prefixes::<Self>()
+    }
+}
+
+/// Object-safe companion to [`UuidType`], describing an ID kind at runtime.
+///
+/// Implemented for every `UuidType` through a blanket impl, so any variant can be
+/// boxed as `Box<dyn UuidTypeDyn>`. Apart from [`variant_info`](Self::variant_info),
+/// the methods describe the whole enum rather than the boxed variant.
+///
+/// Method names differ from `UuidType`'s so both traits can be in scope at once.
+pub trait UuidTypeDyn {
+    /// Returns the name of the enum, e.g. `"UserType"`.
+    fn enum_name(&self) -> &'static str;
+
+    /// Returns the metadata of this particular variant.
+    fn variant_info(&self) -> &'static TypeInfo;
+
+    /// Returns the metadata of every variant, in discriminant order.
+    fn variant_infos(&self) -> Vec<&'static TypeInfo>;
+
+    /// Returns the prefix for a discriminant, or `None` if the enum has no such variant.
+    fn prefix_for(&self, discriminant: u8) -> Option<&'static str>;
+
+    /// Returns the discriminant for a prefix, or `None` if the enum has no such variant.
+    fn discriminant_for(&self, prefix: &str) -> Option<u8>;
+
+    /// Parses a user-friendly string with this enum's rules.
+    fn parse_any(&self, s: &str) -> Result<AnyTypedUuid, TypedUuidError>;
+}
+
+impl<T: UuidType + 'static> UuidTypeDyn for T {
+    fn enum_name(&self) -> &'static str {
+        self.type_info().enum_name()
+    }
+
+    fn variant_info(&self) -> &'static TypeInfo {
+        self.type_info()
+    }
+
+    fn variant_infos(&self) -> Vec<&'static TypeInfo> {
+        variants::<T>().map(|v| v.type_info()).collect()
+    }
+
+    fn prefix_for(&self, discriminant: u8) -> Option<&'static str> {
+        T::from_discriminant(discriminant).map(|v| v.prefix())
+    }
+
+    fn discriminant_for(&self, prefix: &str) -> Option<u8> {
+        T::from_prefix(prefix).map(|v| v.discriminant())
+    }
+
+    fn parse_any(&self, s: &str) -> Result<AnyTypedUuid, TypedUuidError> {
+        UserFriendlyUuid::<T>::parse_str(s).map(AnyTypedUuid::from)
+    }
+}
+
+/// Iterates over every variant of `T` in discriminant order.
+pub(crate) fn variants<T: UuidType>() -> impl Iterator<Item = T> {
+    (0..=u8::MAX).filter_map(T::from_discriminant)
 }
@@ -0,0 +1,115 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+
+use crate::traits::{variants, UuidType};
+
+/// Static description of one variant of a `UuidType` enum.
+///
+/// Obtained from [`UuidType::type_info`]. The derive macro emits these as statics;
+/// manual implementations get a lazily built, cached instance.
+pub struct TypeInfo {
+    enum_name: &'static str,
+    variant_name: &'static str,
+    prefix: &'static str,
+    discriminant: u8,
+    type_id: fn() -> TypeId,
+}
+
+impl TypeInfo {
+    /// Creates a TypeInfo for a variant of `T`. Used by macro-generated code.
+    #[doc(hidden)]
+    pub const fn new<T: 'static>(
+        enum_name: &'static str,
+        variant_name: &'static str,
+        prefix: &'static str,
+        discriminant: u8,
+    ) -> Self {
+        Self {
+            enum_name,
+            variant_name,
+            prefix,
+            discriminant,
+            type_id: TypeId::of::<T>,
+        }
+    }
+
+    /// Returns the name of the enum, e.g. `"UserType"`.
+    pub fn enum_name(&self) -> &'static str {
+        self.enum_name
+    }
+
+    /// Returns the name of the variant, e.g. `"Retail"`.
+    pub fn variant_name(&self) -> &'static str {
+        self.variant_name
+    }
+
+    /// Returns the variant's prefix, e.g. `"retail"`.
+    pub fn prefix(&self) -> &'static str {
+        self.prefix
+    }
+
+    /// Returns the variant's discriminant byte.
+    pub fn discriminant(&self) -> u8 {
+        self.discriminant
+    }
+
+    /// Returns the `TypeId` of the enum.
+    pub fn type_id(&self) -> TypeId {
+        (self.type_id)()
+    }
+
+    /// Returns `true` if this describes a variant of `T`.
+    pub fn is<T: 'static>(&self) -> bool {
+        self.type_id() == TypeId::of::<T>()
+    }
+}
+
+impl fmt::Debug for TypeInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypeInfo")
+            .field("enum_name", &self.enum_name)
+            .field("variant_name", &self.variant_name)
+            .field("prefix", &self.prefix)
+            .field("discriminant", &self.discriminant)
+            .finish()
+    }
+}
+
+/// Builds (once) and returns the TypeInfo for a manually implemented `UuidType`.
+pub(crate) fn cached<T: UuidType + 'static>(variant: T) -> &'static TypeInfo {
+    static CACHE: OnceLock<Mutex<HashMap<(TypeId, u8), &'static TypeInfo>>> = OnceLock::new();
+
+    let key = (TypeId::of::<T>(), variant.discriminant());
+    let mut cache = CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+
+    // At most 256 entries per type are ever leaked
+    cache.entry(key).or_insert_with(|| {
+        let type_name = std::any::type_name::<T>();
+        Box::leak(Box::new(TypeInfo::new::<T>(
+            type_name.rsplit("::").next().unwrap_or(type_name),
+            Box::leak(format!("{:?}", variant).into_boxed_str()),
+            variant.prefix(),
+            variant.discriminant(),
+        )))
+    })
+}
+
+/// Builds (once) and returns the prefix list for a manually implemented `UuidType`.
+pub(crate) fn cached_prefixes<T: UuidType + 'static>() -> &'static [&'static str] {
+    static CACHE: OnceLock<Mutex<HashMap<TypeId, &'static [&'static str]>>> = OnceLock::new();
+
+    let mut cache = CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+
+    // One list is leaked per type
+    cache
+        .entry(TypeId::of::<T>())
+        .or_insert_with(|| Box::leak(variants::<T>().map(|v| v.prefix()).collect()))
+}
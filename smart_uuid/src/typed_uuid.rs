@@ -1,16 +1,39 @@
+use std::borrow::{Borrow, Cow};
+use std::cmp::Ordering;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
+use std::ops::Deref;
 use std::str::FromStr;
+use rand::{CryptoRng, RngCore};
 use uuid::Uuid;
 use serde::{Serialize, Deserialize, Serializer, Deserializer};
 
 use crate::error::TypedUuidError;
+use crate::migrate;
 use crate::traits::UuidType;
+use crate::user_friendly_uuid::{split_prefix, UserFriendlyUuid};
 
 /// A strongly-typed UUID that encodes an enum variant in its bytes.
 ///
 /// Uses UUID v8 (custom) format, storing the type discriminant in byte 0.
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+///
+/// Hashes and compares exactly like the underlying [`Uuid`], so a
+/// `HashMap<TypedUuid<T>, V>` can be queried with a plain `&Uuid`.
+///
+/// The struct is `#[repr(transparent)]` over [`Uuid`], and this is guaranteed: a
+/// `TypedUuid<T>` has exactly the layout of its 16 bytes, so `&[TypedUuid<T>]` can be
+/// viewed as `&[Uuid]` or `&[[u8; 16]]` without copying: see
+/// [`as_uuid_slice`](Self::as_uuid_slice) and [`as_byte_slice`](Self::as_byte_slice), or
+/// the `bytemuck` feature.
+///
+/// The `zerocopy` feature derives `IntoBytes`, `Immutable` and `KnownLayout`. Like the
+/// `uuid` crate's own zerocopy support, it also needs `RUSTFLAGS="--cfg uuid_unstable"`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(all(feature = "zerocopy", uuid_unstable), derive(zerocopy::IntoBytes, zerocopy::Immutable, zerocopy::KnownLayout))]
+#[cfg_attr(feature = "diesel", derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Uuid))]
+#[repr(transparent)]
 pub struct TypedUuid<T: UuidType> {
     inner: Uuid,
     _marker: PhantomData<T>,
@@ -19,19 +42,110 @@ pub struct TypedUuid<T: UuidType> {
 impl<T: UuidType> TypedUuid<T> {
     /// Creates a new TypedUuid with a random UUID and the given type variant.
     pub fn new(variant: T) -> Self {
-        use rand::Rng;
+        Self::new_with_rng(variant, &mut rand::thread_rng())
+    }
 
-        let mut rng = rand::thread_rng();
+    /// Creates a new TypedUuid with random bits drawn from `rng`.
+    ///
+    /// Accepts any [`RngCore`], so a seeded RNG or a hardware TRNG driver implementing
+    /// `rand_core` plugs in directly.
+    pub fn new_with_rng<R: RngCore + ?Sized>(variant: T, rng: &mut R) -> Self {
         let mut bytes = [0u8; 16];
 
         // Fill with random bytes
-        rng.fill(&mut bytes);
+        rng.fill_bytes(&mut bytes);
+
+        Self::from_v8_bytes(variant, bytes)
+    }
+
+    /// Like [`new_with_rng`](Self::new_with_rng), but only accepts RNGs marked as
+    /// cryptographically secure, for IDs that must be unguessable (e.g. in URLs that
+    /// grant access).
+    pub fn new_secure<R: RngCore + CryptoRng + ?Sized>(variant: T, rng: &mut R) -> Self {
+        Self::new_with_rng(variant, rng)
+    }
+
+    /// Returns the smallest v8 ID of `variant`: every payload bit zero.
+    ///
+    /// ```text
+    /// retail: 00000000-0000-8000-8000-000000000000
+    /// org:    01000000-0000-8000-8000-000000000000
+    /// ```
+    ///
+    /// Random and time-ordered IDs of the variant all sort between `nil` and
+    /// [`max`](Self::max), so the pair works as sentinels and as exclusive bounds,
+    /// e.g. `WHERE id > $1 AND id < $2` to scan one variant. Byte 1 holds the schema
+    /// version, if `T` has one.
+    pub fn nil(variant: T) -> Self {
+        Self::from_v8_bytes(variant, [0u8; 16])
+    }
+
+    /// Returns the largest v8 ID of `variant`: every payload bit one.
+    ///
+    /// ```text
+    /// retail: 00ffffff-ffff-8fff-ffff-ffffffffffff
+    /// ```
+    ///
+    /// The variant field is all ones too, the time-ordered marker, so the result reports
+    /// [`Layout::TimeOrdered`](crate::Layout::TimeOrdered) with the latest
+    /// representable timestamp. See [`nil`](Self::nil).
+    pub fn max(variant: T) -> Self {
+        let mut bytes = [0xFFu8; 16];
+        migrate::stamp(variant, &mut bytes);
+        // Version 8; `new_v8` would also reset the variant field to RFC 9562's
+        bytes[6] = 0x8F;
 
-        // Set the discriminant in byte 0
-        bytes[0] = variant.discriminant();
+        // SAFETY: byte 0 was just set to the variant's discriminant, byte 1 to the current schema
+        unsafe { Self::from_uuid_unchecked(Uuid::from_bytes(bytes)) }
+    }
+
+    /// Returns the inclusive UUID range holding every ID of `variant`, whatever its
+    /// layout, for a single indexed `BETWEEN` query:
+    ///
+    /// ```text
+    /// let (low, high) = TypedUuid::range_for(UserType::Business);
+    /// sqlx::query("SELECT * FROM users WHERE id BETWEEN $1 AND $2").bind(low).bind(high)
+    /// ```
+    ///
+    /// The bounds are byte 0 followed by all zero or all one bits. Unlike
+    /// [`nil`](Self::nil) and [`max`](Self::max) they also cover v4-compatible and
+    /// opaque IDs, and every schema version in byte 1. They are plain [`Uuid`]s,
+    /// since neither is a valid v8 ID.
+    pub fn range_for(variant: T) -> (Uuid, Uuid) {
+        let discriminant = variant.discriminant();
+        let mut low = [0u8; 16];
+        let mut high = [0xFFu8; 16];
+        low[0] = discriminant;
+        high[0] = discriminant;
+        (Uuid::from_bytes(low), Uuid::from_bytes(high))
+    }
+
+    /// Views a slice of typed IDs as plain UUIDs, without copying.
+    ///
+    /// For binding millions of IDs as one array parameter, e.g. to `UNNEST($1::uuid[])`.
+    pub fn as_uuid_slice(ids: &[Self]) -> &[Uuid] {
+        // SAFETY: `TypedUuid<T>` is `repr(transparent)` over `Uuid` (the `PhantomData`
+        // is zero-sized), so both slices have the same layout
+        unsafe { std::slice::from_raw_parts(ids.as_ptr().cast::<Uuid>(), ids.len()) }
+    }
+
+    /// Views a slice of typed IDs as their raw bytes, without copying.
+    pub fn as_byte_slice(ids: &[Self]) -> &[[u8; 16]] {
+        // SAFETY: as in `as_uuid_slice`; `Uuid` is itself guaranteed to have the
+        // layout of `[u8; 16]`
+        unsafe { std::slice::from_raw_parts(ids.as_ptr().cast::<[u8; 16]>(), ids.len()) }
+    }
+
+    /// Builds a v8 TypedUuid from a 16-byte payload, overwriting byte 0 with
+    /// the variant's discriminant.
+    pub(crate) fn from_v8_bytes(variant: T, mut bytes: [u8; 16]) -> Self {
+        // Set the discriminant in byte 0 (and the schema version, if any)
+        migrate::stamp(variant, &mut bytes);
 
         // Create a v8 UUID (this will set version and variant bits)
-        let uuid = Uuid::new_v8(bytes);
+        let mut bytes = Uuid::new_v8(bytes).into_bytes();
+        migrate::mark_versioned::<T>(&mut bytes);
+        let uuid = Uuid::from_bytes(bytes);
 
         Self {
             inner: uuid,
@@ -40,7 +154,11 @@ impl<T: UuidType> TypedUuid<T> {
     }
 
     /// Creates a TypedUuid from an existing UUID, validating the discriminant.
+    ///
+    /// If `T` has a [`Migrator`](crate::Migrator) and the UUID was written with an older
+    /// schema version, the returned ID holds the migrated UUID.
     pub fn from_uuid(uuid: Uuid) -> Result<Self, TypedUuidError> {
+        let uuid = migrate::upgrade::<T>(uuid)?;
         let bytes = uuid.as_bytes();
         let discriminant = bytes[0];
 
@@ -56,7 +174,152 @@ impl<T: UuidType> TypedUuid<T> {
         })
     }
 
+    /// Creates a TypedUuid from its 16 raw bytes, validating the discriminant like
+    /// [`from_uuid`](Self::from_uuid).
+    ///
+    /// Any layout is accepted; to also check the version bits, use
+    /// [`from_bytes_with_layout`](Self::from_bytes_with_layout).
+    pub fn from_bytes(bytes: [u8; 16]) -> Result<Self, TypedUuidError> {
+        Self::from_uuid(Uuid::from_bytes(bytes))
+    }
+
+    /// Creates a TypedUuid from a byte slice, e.g. a field read off a binary protocol.
+    ///
+    /// Fails with [`TypedUuidError::ParseError`] unless the slice is exactly 16 bytes,
+    /// then validates like [`from_bytes`](Self::from_bytes).
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, TypedUuidError> {
+        Self::from_uuid(Uuid::from_slice(bytes)?)
+    }
+
+    /// Creates a TypedUuid from its raw bytes in `const` context, for well-known IDs
+    /// and fixtures declared as `static`s:
+    ///
+    /// ```
+    /// use smart_uuid::{uuid, TypedUuid, UuidType};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+    /// enum UserType {
+    ///     Retail,
+    ///     #[uuid_type(prefix = "org")]
+    ///     Organization,
+    /// }
+    ///
+    /// static ROOT_ORG: TypedUuid<UserType> =
+    ///     TypedUuid::from_bytes_const(uuid::uuid!("01000000-0000-8000-8000-000000000001").into_bytes());
+    /// assert_eq!(ROOT_ORG.variant_type(), UserType::Organization);
+    /// ```
+    ///
+    /// Byte 0 is checked against [`UuidType::DISCRIMINANTS`], so in a `const` or
+    /// `static` a wrong discriminant fails to compile:
+    ///
+    /// ```compile_fail
+    /// # use smart_uuid::{uuid, TypedUuid, UuidType};
+    /// # #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+    /// # enum UserType { Retail }
+    /// static BROKEN: TypedUuid<UserType> =
+    ///     TypedUuid::from_bytes_const(uuid::uuid!("07000000-0000-8000-8000-000000000001").into_bytes());
+    /// ```
+    ///
+    /// No schema migration is applied.
+    ///
+    /// # Panics
+    /// Panics if byte 0 is not one of `T::DISCRIMINANTS`, or if that list is empty.
+    pub const fn from_bytes_const(bytes: [u8; 16]) -> Self {
+        assert!(
+            !T::DISCRIMINANTS.is_empty(),
+            "TypedUuid::from_bytes_const: the UuidType impl does not list its DISCRIMINANTS"
+        );
+        let mut i = 0;
+        while i < T::DISCRIMINANTS.len() && T::DISCRIMINANTS[i] != bytes[0] {
+            i += 1;
+        }
+        assert!(
+            i < T::DISCRIMINANTS.len(),
+            "TypedUuid::from_bytes_const: byte 0 is not a discriminant of the type"
+        );
+
+        Self {
+            inner: Uuid::from_bytes(bytes),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a TypedUuid from an existing UUID without validating the discriminant.
+    ///
+    /// Intended for hot paths reading UUIDs that were already validated when written,
+    /// e.g. scanning millions of rows from a column only ever filled with `TypedUuid<T>`.
+    /// In debug builds the discriminant is still checked. No schema migration is applied.
+    ///
+    /// # Safety
+    ///
+    /// Byte 0 of `uuid` must be a discriminant for which `T::from_discriminant`
+    /// returns `Some`. Every other method assumes this invariant; violating it makes
+    /// [`variant_type`](Self::variant_type) panic and lets a mistyped ID flow through
+    /// code that trusts the type parameter.
+    pub unsafe fn from_uuid_unchecked(uuid: Uuid) -> Self {
+        debug_assert!(
+            T::from_discriminant(uuid.as_bytes()[0]).is_some(),
+            "from_uuid_unchecked called with invalid discriminant {} for {}",
+            uuid.as_bytes()[0],
+            std::any::type_name::<T>(),
+        );
+
+        Self {
+            inner: uuid,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Parses either a bare UUID or a user-friendly `prefix_uuid` string.
+    ///
+    /// A prefix, when present, must match the encoded variant. Use this at boundaries
+    /// that accept both forms; [`FromStr`] rejects prefixed input with
+    /// [`TypedUuidError::UnexpectedPrefix`].
+    pub fn parse_lenient(s: &str) -> Result<Self, TypedUuidError> {
+        match s.parse() {
+            Err(TypedUuidError::UnexpectedPrefix { .. }) => {
+                UserFriendlyUuid::parse_str(s).map(UserFriendlyUuid::into_typed_uuid)
+            }
+            result => result,
+        }
+    }
+
+    /// Parses a bare UUID like [`FromStr`], then accepts it only if its variant is
+    /// one of `allowed`, for endpoints that take some kinds of ID but not others.
+    ///
+    /// Fails with [`TypedUuidError::VariantNotAllowed`] for a valid ID of another variant.
+    pub fn parse_allowed(s: &str, allowed: &[T]) -> Result<Self, TypedUuidError> {
+        s.parse::<Self>()?.check_allowed(allowed)
+    }
+
+    /// Returns self if its variant is one of `allowed`.
+    pub(crate) fn check_allowed(self, allowed: &[T]) -> Result<Self, TypedUuidError> {
+        let variant = self.variant_type();
+        if allowed.contains(&variant) {
+            return Ok(self);
+        }
+        Err(TypedUuidError::VariantNotAllowed {
+            found: variant.prefix(),
+            type_name: std::any::type_name::<T>(),
+            allowed: allowed.iter().map(|v| v.prefix()).collect(),
+        })
+    }
+
+    /// Parses a UUID string given as ASCII bytes, validating the discriminant.
+    ///
+    /// Accepts the same formats as [`FromStr`] without first converting to `&str`,
+    /// like [`Uuid::try_parse_ascii`].
+    pub fn try_parse_ascii(input: &[u8]) -> Result<Self, TypedUuidError> {
+        let uuid = Uuid::try_parse_ascii(input)?;
+        Self::from_uuid(uuid)
+    }
+
     /// Returns the enum variant encoded in this UUID.
+    ///
+    /// Decodes byte 0 on each call, a single `match` costing under a nanosecond
+    /// (`cargo bench -p smart_uuid --bench variant`). The variant is deliberately not
+    /// cached in the struct: `TypedUuid` is guaranteed to be exactly its 16 bytes.
+    /// Hot loops that branch on it can wrap IDs in [`CachedUuid`](crate::CachedUuid).
     pub fn variant_type(&self) -> T {
         let bytes = self.inner.as_bytes();
         let discriminant = bytes[0];
@@ -66,6 +329,59 @@ impl<T: UuidType> TypedUuid<T> {
             .expect("TypedUuid contains invalid discriminant - this is a bug")
     }
 
+    /// Returns `true` if this UUID encodes `variant`.
+    ///
+    /// To test against several variants at once, use [`variant_matches!`](crate::variant_matches).
+    pub fn is_variant(&self, variant: T) -> bool {
+        self.variant_type() == variant
+    }
+
+    /// Shorthand for [`is_variant`](Self::is_variant), for guards that read as prose:
+    /// `ensure!(id.is(ApiResource::User))`.
+    pub fn is(&self, variant: T) -> bool {
+        self.is_variant(variant)
+    }
+
+    /// Returns the same ID re-tagged as `new_variant`: only byte 0 changes.
+    ///
+    /// For state-machine promotions such as `draft` to `published`, where the entity
+    /// should stay recognizable: every other byte, including any timestamp and schema
+    /// version, is kept, so the two IDs can be correlated by their last 15 bytes.
+    ///
+    /// The result is a valid ID but not a fresh one. It collides with another ID only
+    /// if that ID had the same 15 bytes, which for random IDs happens only when the same
+    /// ID is re-tagged twice, e.g. a retried promotion; make promotions idempotent rather
+    /// than relying on uniqueness. Anyone holding the old ID can derive the new one, so
+    /// don't use this where a variant change must not be linkable.
+    pub fn replace_variant(self, new_variant: T) -> Self {
+        let mut bytes = self.inner.into_bytes();
+        bytes[0] = new_variant.discriminant();
+        Self {
+            inner: Uuid::from_bytes(bytes),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Recasts this ID as an ID of another enum, mapping its variant with `f`.
+    ///
+    /// For refactors that split or merge enums: the discriminant is rewritten to the
+    /// new variant's and every other byte is kept, so stored IDs stay linkable to the
+    /// recast ones without being regenerated. If `U` has a schema version, byte 1 is
+    /// stamped with it and the ID marked as versioned (see [`migrate`]); otherwise
+    /// byte 1 is kept too.
+    ///
+    /// Like [`replace_variant`](Self::replace_variant), the result is not a fresh ID: two
+    /// IDs mapped to the same variant collide only if they differed in byte 0 alone. To
+    /// rewrite a whole column and check for that, see [`migrate::retag`].
+    pub fn map_type<U: UuidType>(self, f: impl FnOnce(T) -> U) -> TypedUuid<U> {
+        let mut bytes = self.inner.into_bytes();
+        migrate::stamp(f(self.variant_type()), &mut bytes);
+        migrate::mark_versioned::<U>(&mut bytes);
+
+        // SAFETY: byte 0 was just set to a discriminant of `U`, byte 1 to its current schema
+        unsafe { TypedUuid::from_uuid_unchecked(Uuid::from_bytes(bytes)) }
+    }
+
     /// Returns a reference to the underlying UUID.
     pub fn as_uuid(&self) -> &Uuid {
         &self.inner
@@ -80,8 +396,130 @@ impl<T: UuidType> TypedUuid<T> {
     pub fn as_bytes(&self) -> &[u8; 16] {
         self.inner.as_bytes()
     }
+
+    /// Returns the UUID as a big-endian `u128`, like [`Uuid::as_u128`]: the
+    /// discriminant is the most significant byte.
+    pub fn as_u128(&self) -> u128 {
+        self.inner.as_u128()
+    }
+
+    /// Returns the UUID as a little-endian `u128`, like [`Uuid::to_u128_le`]: the
+    /// discriminant is the least significant byte.
+    pub fn to_u128_le(&self) -> u128 {
+        self.inner.to_u128_le()
+    }
+
+    /// Creates a TypedUuid from a big-endian `u128`, as returned by
+    /// [`as_u128`](Self::as_u128), validating the discriminant like
+    /// [`from_uuid`](Self::from_uuid).
+    pub fn try_from_u128(value: u128) -> Result<Self, TypedUuidError> {
+        Self::from_uuid(Uuid::from_u128(value))
+    }
+
+    /// Creates a TypedUuid from a little-endian `u128`, as returned by
+    /// [`to_u128_le`](Self::to_u128_le), validating the discriminant.
+    pub fn try_from_u128_le(value: u128) -> Result<Self, TypedUuidError> {
+        Self::from_uuid(Uuid::from_u128_le(value))
+    }
+
+    /// Returns the hyphenated UUID string as an owned `Cow`, for APIs taking
+    /// `impl Into<Cow<'static, str>>`.
+    pub fn to_cow(&self) -> Cow<'static, str> {
+        Cow::Owned(self.to_string())
+    }
+
+    /// Returns an adapter that displays the UUID as 32 hex digits without hyphens.
+    pub fn simple(&self) -> uuid::fmt::Simple {
+        self.inner.simple()
+    }
+
+    /// Returns an adapter that displays the UUID in the standard hyphenated form.
+    pub fn hyphenated(&self) -> uuid::fmt::Hyphenated {
+        self.inner.hyphenated()
+    }
+
+    /// Returns an adapter that displays the UUID hyphenated inside braces: `{...}`.
+    pub fn braced(&self) -> uuid::fmt::Braced {
+        self.inner.braced()
+    }
+
+    /// Returns an adapter that displays the UUID as a URN: `urn:uuid:...`.
+    pub fn urn(&self) -> uuid::fmt::Urn {
+        self.inner.urn()
+    }
+}
+
+// Manual impl: hashing must match `Uuid` for the `Borrow<Uuid>` impl, and this
+// avoids the `T: Hash` bound a derive would add.
+impl<T: UuidType> Hash for TypedUuid<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+    }
+}
+
+impl<T: UuidType> PartialOrd for TypedUuid<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders like the underlying [`Uuid`]: by discriminant first, since it is byte 0,
+/// then by the remaining bytes. IDs therefore group by variant in a `BTreeMap`, and
+/// time-ordered IDs of one variant sort by creation time.
+impl<T: UuidType> Ord for TypedUuid<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.inner.cmp(&other.inner)
+    }
+}
+
+impl<T: UuidType> PartialEq<Uuid> for TypedUuid<T> {
+    fn eq(&self, other: &Uuid) -> bool {
+        self.inner == *other
+    }
+}
+
+impl<T: UuidType> PartialEq<TypedUuid<T>> for Uuid {
+    fn eq(&self, other: &TypedUuid<T>) -> bool {
+        *self == other.inner
+    }
+}
+
+impl<T: UuidType> AsRef<Uuid> for TypedUuid<T> {
+    fn as_ref(&self) -> &Uuid {
+        &self.inner
+    }
+}
+
+impl<T: UuidType> Borrow<Uuid> for TypedUuid<T> {
+    fn borrow(&self) -> &Uuid {
+        &self.inner
+    }
+}
+
+/// Exposes the read-only [`Uuid`] API (`as_bytes`, `get_version`, ...) directly.
+/// Inherent methods such as [`variant_type`](TypedUuid::variant_type) take precedence.
+impl<T: UuidType> Deref for TypedUuid<T> {
+    type Target = Uuid;
+
+    fn deref(&self) -> &Uuid {
+        &self.inner
+    }
 }
 
+// SAFETY: `TypedUuid<T>` is `repr(transparent)` over `Uuid`; the only other field is
+// a zero-sized `PhantomData`.
+//
+// `wrap` and friends skip discriminant validation, just like `from_uuid_unchecked`.
+// Nothing becomes memory-unsafe, but `variant_type` panics on an invalid discriminant.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: UuidType> bytemuck::TransparentWrapper<Uuid> for TypedUuid<T> {}
+
+// SAFETY: `Uuid` is 16 bytes with no padding, and `repr(transparent)` adds none.
+// There is deliberately no `AnyBitPattern`/`Pod` impl, since not every 16 bytes hold a
+// valid discriminant.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: UuidType + 'static> bytemuck::NoUninit for TypedUuid<T> {}
+
 impl<T: UuidType> fmt::Debug for TypedUuid<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("TypedUuid")
@@ -97,22 +535,82 @@ impl<T: UuidType> fmt::Display for TypedUuid<T> {
     }
 }
 
+/// Compact form for embedded logging: the discriminant and the last 8 hex digits,
+/// e.g. `2_9abcdef0`. Needs no `core::fmt` or allocation on the target.
+#[cfg(feature = "defmt")]
+impl<T: UuidType> defmt::Format for TypedUuid<T> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        let bytes = self.as_bytes();
+        let tail = u32::from_be_bytes([bytes[12], bytes[13], bytes[14], bytes[15]]);
+        defmt::write!(f, "{=u8}_{=u32:08x}", bytes[0], tail)
+    }
+}
+
+/// Parses any form the `uuid` crate does: simple, hyphenated, braced (`{...}`) or URN
+/// (`urn:uuid:...`), in either case. Display with the matching adapter, e.g.
+/// [`braced`](TypedUuid::braced), to write a form legacy systems expect.
 impl<T: UuidType> FromStr for TypedUuid<T> {
     type Err = TypedUuidError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let uuid = Uuid::parse_str(s)
-            .map_err(|e| TypedUuidError::ParseError(e.to_string()))?;
+        let uuid = Uuid::parse_str(s).map_err(|e| {
+            // A common mistake is passing the user-friendly form; say so instead of
+            // reporting an invalid character
+            match split_prefix(s) {
+                Ok((prefix, body)) if Uuid::parse_str(body).is_ok() => TypedUuidError::UnexpectedPrefix {
+                    prefix: prefix.to_string(),
+                },
+                _ => TypedUuidError::ParseError(e),
+            }
+        })?;
         Self::from_uuid(uuid)
     }
 }
 
+impl<T: UuidType> TryFrom<&str> for TypedUuid<T> {
+    type Error = TypedUuidError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl<T: UuidType> TryFrom<String> for TypedUuid<T> {
+    type Error = TypedUuidError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl<T: UuidType> TryFrom<[u8; 16]> for TypedUuid<T> {
+    type Error = TypedUuidError;
+
+    fn try_from(bytes: [u8; 16]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl<T: UuidType> TryFrom<&[u8]> for TypedUuid<T> {
+    type Error = TypedUuidError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_slice(bytes)
+    }
+}
+
 impl<T: UuidType> From<TypedUuid<T>> for Uuid {
     fn from(typed: TypedUuid<T>) -> Self {
         typed.inner
     }
 }
 
+impl<T: UuidType> From<TypedUuid<T>> for String {
+    fn from(typed: TypedUuid<T>) -> Self {
+        typed.to_string()
+    }
+}
+
 impl<T: UuidType> Serialize for TypedUuid<T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -131,3 +629,25 @@ impl<'de, T: UuidType> Deserialize<'de> for TypedUuid<T> {
         Self::from_uuid(uuid).map_err(serde::de::Error::custom)
     }
 }
+
+/// Returns `true` if the variant of a typed ID matches a pattern, like `matches!`.
+///
+/// Works with anything that has a `variant_type()` method, such as [`TypedUuid`] and
+/// [`UserFriendlyUuid`].
+///
+/// ```
+/// use smart_uuid::{variant_matches, TypedUuid, UuidType};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+/// enum UserType { Retail, Business, Admin }
+///
+/// let id = TypedUuid::new(UserType::Business);
+/// assert!(variant_matches!(id, UserType::Retail | UserType::Business));
+/// assert!(!variant_matches!(id, UserType::Admin));
+/// ```
+#[macro_export]
+macro_rules! variant_matches {
+    ($id:expr, $pattern:pat $(if $guard:expr)? $(,)?) => {
+        ::core::matches!($id.variant_type(), $pattern $(if $guard)?)
+    };
+}
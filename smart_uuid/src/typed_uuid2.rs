@@ -0,0 +1,170 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::str::FromStr;
+use uuid::Uuid;
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+
+use crate::error::TypedUuidError;
+use crate::traits::UuidType;
+use crate::user_friendly_uuid::split_prefix;
+
+/// A UUID that encodes two enum variants: a domain in byte 0 and an entity in byte 1.
+///
+/// Lets platform-wide IDs express `billing` + `invoice` vs `billing` + `payment`
+/// without one flat enum listing every entity of every domain. Otherwise behaves
+/// like [`TypedUuid`](crate::TypedUuid): v8 format, displays and serializes as the
+/// plain UUID, hashes like the underlying [`Uuid`].
+///
+/// The user-friendly form joins both prefixes: `billing_invoice_550e8400-...`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct TypedUuid2<D: UuidType, E: UuidType> {
+    inner: Uuid,
+    _marker: PhantomData<(D, E)>,
+}
+
+impl<D: UuidType, E: UuidType> TypedUuid2<D, E> {
+    /// Creates a new TypedUuid2 with a random UUID and the given domain and entity.
+    pub fn new(domain: D, entity: E) -> Self {
+        let mut bytes: [u8; 16] = rand::random();
+        bytes[0] = domain.discriminant();
+        bytes[1] = entity.discriminant();
+
+        Self {
+            inner: Uuid::new_v8(bytes),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a TypedUuid2 from an existing UUID, validating both discriminants.
+    pub fn from_uuid(uuid: Uuid) -> Result<Self, TypedUuidError> {
+        let bytes = uuid.as_bytes();
+
+        D::from_discriminant(bytes[0]).ok_or(TypedUuidError::InvalidDiscriminant {
+            found: bytes[0],
+            type_name: std::any::type_name::<D>(),
+        })?;
+        E::from_discriminant(bytes[1]).ok_or(TypedUuidError::InvalidDiscriminant {
+            found: bytes[1],
+            type_name: std::any::type_name::<E>(),
+        })?;
+
+        Ok(Self {
+            inner: uuid,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Parses a user-friendly string like `billing_invoice_550e8400-...`.
+    pub fn parse_friendly(s: &str) -> Result<Self, TypedUuidError> {
+        let (prefix, uuid_str) = split_prefix(s)?;
+        let id = Self::from_uuid(Uuid::parse_str(uuid_str)?)?;
+
+        // Compare the whole prefix, so either part may itself contain underscores
+        let domain = id.domain().prefix();
+        let entity = id.entity().prefix();
+        let matches = prefix.len() == domain.len() + 1 + entity.len()
+            && prefix.starts_with(domain)
+            && prefix[domain.len()..].starts_with('_')
+            && prefix.ends_with(entity);
+        if !matches {
+            return Err(TypedUuidError::UnknownPrefix {
+                prefix: prefix.to_string(),
+                type_name: std::any::type_name::<Self>(),
+                expected: std::borrow::Cow::Borrowed(&[]),
+                did_you_mean: None,
+            });
+        }
+
+        Ok(id)
+    }
+
+    /// Returns the domain variant encoded in byte 0.
+    pub fn domain(&self) -> D {
+        D::from_discriminant(self.inner.as_bytes()[0])
+            .expect("TypedUuid2 contains invalid domain discriminant - this is a bug")
+    }
+
+    /// Returns the entity variant encoded in byte 1.
+    pub fn entity(&self) -> E {
+        E::from_discriminant(self.inner.as_bytes()[1])
+            .expect("TypedUuid2 contains invalid entity discriminant - this is a bug")
+    }
+
+    /// Returns a reference to the underlying UUID.
+    pub fn as_uuid(&self) -> &Uuid {
+        &self.inner
+    }
+
+    /// Consumes self and returns the underlying UUID.
+    pub fn into_uuid(self) -> Uuid {
+        self.inner
+    }
+
+    /// Returns the raw bytes of the UUID.
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        self.inner.as_bytes()
+    }
+
+    /// Returns the user-friendly form, e.g. `billing_invoice_550e8400-...`.
+    pub fn to_friendly_string(&self) -> String {
+        format!("{}_{}_{}", self.domain().prefix(), self.entity().prefix(), self.inner)
+    }
+}
+
+// Manual impl to hash like `Uuid` and avoid the bounds a derive would add.
+impl<D: UuidType, E: UuidType> Hash for TypedUuid2<D, E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+    }
+}
+
+impl<D: UuidType, E: UuidType> fmt::Debug for TypedUuid2<D, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedUuid2")
+            .field("uuid", &self.inner)
+            .field("domain", &self.domain())
+            .field("entity", &self.entity())
+            .finish()
+    }
+}
+
+impl<D: UuidType, E: UuidType> fmt::Display for TypedUuid2<D, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inner)
+    }
+}
+
+impl<D: UuidType, E: UuidType> FromStr for TypedUuid2<D, E> {
+    type Err = TypedUuidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let uuid = Uuid::parse_str(s)?;
+        Self::from_uuid(uuid)
+    }
+}
+
+impl<D: UuidType, E: UuidType> From<TypedUuid2<D, E>> for Uuid {
+    fn from(typed: TypedUuid2<D, E>) -> Self {
+        typed.inner
+    }
+}
+
+impl<D: UuidType, E: UuidType> Serialize for TypedUuid2<D, E> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.inner.serialize(serializer)
+    }
+}
+
+impl<'de, D: UuidType, E: UuidType> Deserialize<'de> for TypedUuid2<D, E> {
+    fn deserialize<De>(deserializer: De) -> Result<Self, De::Error>
+    where
+        De: Deserializer<'de>,
+    {
+        let uuid = Uuid::deserialize(deserializer)?;
+        Self::from_uuid(uuid).map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,153 @@
+use crate::traits::UuidType;
+
+/// Returns `true` if some variant of `T` uses this prefix. Used by `define_uuid_union!`.
+#[doc(hidden)]
+pub fn has_prefix<T: UuidType>(prefix: &str) -> bool {
+    T::from_prefix(prefix).is_some()
+}
+
+/// Defines an enum holding a `TypedUuid` of any of several `UuidType` enums.
+///
+/// Each arm names a variant and the `UuidType` enum it wraps. The generated enum gets:
+/// - `FromStr`, dispatching on the prefix to the enum that owns it
+/// - `Display` in the canonical user-friendly `{prefix}_{uuid}` format, with a hyphenated
+///   body whatever the [`FormatConfig`](crate::FormatConfig), so it always parses back
+/// - serde `Serialize`/`Deserialize` as that same string
+/// - `From<TypedUuid<_>>` and `From<UserFriendlyUuid<_>>` for every arm
+/// - `as_uuid()` and `prefix()` accessors, and `prefixes()` listing every accepted prefix
+///
+/// Arms are tried in the order listed, so if two share a prefix the first one wins.
+/// An unrecognized prefix fails with one [`UnknownPrefix`](crate::TypedUuidError::UnknownPrefix)
+/// error listing the prefixes of every arm, which makes the union the way to deserialize
+/// polymorphic fields such as a webhook's `resource_id`.
+///
+/// # Example
+/// ```
+/// use smart_uuid::{define_uuid_union, TypedUuid, UuidType};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+/// enum UserType { Retail, Business }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+/// enum DocumentType { Invoice, Receipt }
+///
+/// define_uuid_union! {
+///     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///     pub enum AnyEntityId {
+///         User(UserType),
+///         Doc(DocumentType),
+///     }
+/// }
+///
+/// let id: AnyEntityId = TypedUuid::new(DocumentType::Invoice).into();
+/// let parsed: AnyEntityId = id.to_string().parse().unwrap();
+/// assert_eq!(parsed, id);
+/// ```
+#[macro_export]
+macro_rules! define_uuid_union {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($variant:ident($ty:ty)),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($variant($crate::TypedUuid<$ty>),)+
+        }
+
+        impl $name {
+            /// Returns a reference to the underlying UUID.
+            pub fn as_uuid(&self) -> &$crate::Uuid {
+                match self {
+                    $(Self::$variant(id) => id.as_uuid(),)+
+                }
+            }
+
+            /// Returns the prefix of the variant encoded in this UUID.
+            pub fn prefix(&self) -> &'static str {
+                match self {
+                    $(Self::$variant(id) => $crate::UuidType::prefix(&id.variant_type()),)+
+                }
+            }
+
+            /// Returns every prefix parsing accepts, in the order the arms are tried.
+            pub fn prefixes() -> &'static [&'static str] {
+                static PREFIXES: ::std::sync::OnceLock<::std::vec::Vec<&'static str>> = ::std::sync::OnceLock::new();
+                PREFIXES.get_or_init(|| {
+                    let mut prefixes = ::std::vec::Vec::new();
+                    $(
+                        for &prefix in <$ty as $crate::UuidType>::prefixes() {
+                            if !prefixes.contains(&prefix) {
+                                prefixes.push(prefix);
+                            }
+                        }
+                    )+
+                    prefixes
+                })
+            }
+        }
+
+        $(
+            impl ::core::convert::From<$crate::TypedUuid<$ty>> for $name {
+                fn from(id: $crate::TypedUuid<$ty>) -> Self {
+                    Self::$variant(id)
+                }
+            }
+
+            impl ::core::convert::From<$crate::UserFriendlyUuid<$ty>> for $name {
+                fn from(id: $crate::UserFriendlyUuid<$ty>) -> Self {
+                    Self::$variant(id.into_typed_uuid())
+                }
+            }
+        )+
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    $(Self::$variant(id) => ::core::fmt::Display::fmt(&$crate::UserFriendlyUuid::from(*id).hyphenated(), f),)+
+                }
+            }
+        }
+
+        impl ::core::str::FromStr for $name {
+            type Err = $crate::TypedUuidError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                let (prefix, _) = $crate::__private::split_prefix(s)?;
+                $(
+                    if $crate::__private::has_prefix::<$ty>(prefix) {
+                        return $crate::UserFriendlyUuid::<$ty>::parse_str(s).map(Self::from);
+                    }
+                )+
+                ::core::result::Result::Err($crate::TypedUuidError::UnknownPrefix {
+                    prefix: prefix.to_string(),
+                    type_name: ::core::stringify!($name),
+                    expected: ::std::borrow::Cow::Borrowed(Self::prefixes()),
+                    did_you_mean: $crate::__private::did_you_mean(prefix, Self::prefixes().iter().copied()),
+                })
+            }
+        }
+
+        impl $crate::__private::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::__private::serde::Serializer,
+            {
+                match self {
+                    $(Self::$variant(id) => serializer.collect_str(&$crate::UserFriendlyUuid::from(*id).hyphenated()),)+
+                }
+            }
+        }
+
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: $crate::__private::serde::Deserializer<'de>,
+            {
+                let s = <::std::string::String as $crate::__private::serde::Deserialize>::deserialize(deserializer)?;
+                s.parse().map_err(<D::Error as $crate::__private::serde::de::Error>::custom)
+            }
+        }
+    };
+}
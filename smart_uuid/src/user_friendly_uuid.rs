@@ -1,12 +1,26 @@
+use std::borrow::{Borrow, Cow};
+use std::cmp::Ordering;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 use std::str::FromStr;
+use rand::{CryptoRng, RngCore};
 use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use uuid::Uuid;
 
 use crate::error::TypedUuidError;
+use crate::format::FormatConfig;
+use crate::migrate;
+use crate::prefixed_uuid::PrefixedUuid;
 use crate::traits::UuidType;
 use crate::typed_uuid::TypedUuid;
 
+/// The longest prefix accepted when parsing a user-friendly UUID, in bytes.
+pub const MAX_PREFIX_LEN: usize = 64;
+
+/// The longest UUID body accepted when parsing, in bytes (the `urn:uuid:` form).
+const MAX_BODY_LEN: usize = 45;
+
 /// A user-friendly representation of a TypedUuid with a human-readable prefix.
 ///
 /// Format: `{prefix}_{uuid}` where:
@@ -19,7 +33,7 @@ use crate::typed_uuid::TypedUuid;
 /// business_6ba7b810-9dad-11d1-80b4-00c04fd430c8
 /// org_f47ac10b-58cc-4372-a567-0e02b2c3d479
 /// ```
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub struct UserFriendlyUuid<T: UuidType> {
     typed_uuid: TypedUuid<T>,
     _marker: PhantomData<T>,
@@ -34,6 +48,18 @@ impl<T: UuidType> UserFriendlyUuid<T> {
         }
     }
 
+    /// Creates a new UserFriendlyUuid with random bits drawn from `rng`; see
+    /// [`TypedUuid::new_with_rng`].
+    pub fn new_with_rng<R: RngCore + ?Sized>(variant: T, rng: &mut R) -> Self {
+        Self::from_typed_uuid(TypedUuid::new_with_rng(variant, rng))
+    }
+
+    /// Like [`new_with_rng`](Self::new_with_rng), but only accepts cryptographically
+    /// secure RNGs; see [`TypedUuid::new_secure`].
+    pub fn new_secure<R: RngCore + CryptoRng + ?Sized>(variant: T, rng: &mut R) -> Self {
+        Self::from_typed_uuid(TypedUuid::new_secure(variant, rng))
+    }
+
     /// Creates a UserFriendlyUuid from an existing TypedUuid.
     pub fn from_typed_uuid(typed: TypedUuid<T>) -> Self {
         Self {
@@ -44,40 +70,82 @@ impl<T: UuidType> UserFriendlyUuid<T> {
 
     /// Parses a user-friendly string like "retail_550e8400-e29b-41d4-a716-446655440000".
     pub fn parse_str(s: &str) -> Result<Self, TypedUuidError> {
-        // Find the last underscore to split prefix from UUID.
-        // We use rfind because prefixes may contain underscores (e.g., "http_server"),
-        // but UUIDs never contain underscores (only hyphens).
-        let underscore_pos = s.rfind('_').ok_or_else(|| {
-            TypedUuidError::InvalidFormat(
-                "expected format 'prefix_uuid', no underscore found".to_string(),
-            )
-        })?;
+        Self::try_parse_ascii(s.as_bytes())
+    }
 
-        let prefix = &s[..underscore_pos];
-        let uuid_str = &s[underscore_pos + 1..];
+    /// Parses like [`parse_str`](Self::parse_str), then accepts the ID only if its
+    /// variant is one of `allowed`.
+    ///
+    /// Fails with [`TypedUuidError::VariantNotAllowed`] for a valid ID of another variant:
+    ///
+    /// ```text
+    /// let id = UserFriendlyUuid::parse_allowed(&path.id, &[ApiResource::User, ApiResource::Team])?;
+    /// ```
+    pub fn parse_allowed(s: &str, allowed: &[T]) -> Result<Self, TypedUuidError> {
+        let id = Self::parse_str(s)?;
+        id.typed_uuid.check_allowed(allowed)?;
+        Ok(id)
+    }
 
-        // Parse the UUID
-        let uuid = uuid::Uuid::parse_str(uuid_str)
-            .map_err(|e| TypedUuidError::ParseError(e.to_string()))?;
+    /// Parses a prefixed string, taking the variant from the prefix alone.
+    ///
+    /// The body may be any UUID, including a plain v4 from a system that does not
+    /// encode a discriminant in byte 0. Use [`PrefixedUuid::discriminant_matched`] to
+    /// tell whether the body also carries the encoding.
+    pub fn parse_prefix_authoritative(s: &str) -> Result<PrefixedUuid<T>, TypedUuidError> {
+        let (prefix, uuid_str) = split_prefix(s)?;
 
-        // Create TypedUuid (this validates the discriminant)
-        let typed_uuid: TypedUuid<T> = TypedUuid::from_uuid(uuid)?;
+        let variant = T::from_prefix(prefix).ok_or_else(|| TypedUuidError::unknown_prefix::<T>(prefix))?;
+        let uuid = uuid::Uuid::parse_str(uuid_str)?;
 
-        // Verify the prefix matches the variant encoded in the UUID
-        let expected_prefix = typed_uuid.variant_type().prefix();
-        if prefix != expected_prefix {
-            return Err(TypedUuidError::UnknownPrefix {
-                prefix: prefix.to_string(),
-                type_name: std::any::type_name::<T>(),
-            });
-        }
+        Ok(PrefixedUuid::new(variant, uuid))
+    }
 
+    /// Parses a user-friendly string given as ASCII bytes, without first converting to `&str`.
+    pub fn try_parse_ascii(input: &[u8]) -> Result<Self, TypedUuidError> {
+        let (_, typed_uuid) = Self::validate(input)?;
         Ok(Self {
             typed_uuid,
             _marker: PhantomData,
         })
     }
 
+    /// Validates `s` like [`parse_str`](Self::parse_str), returning a view that borrows it.
+    ///
+    /// Nothing is allocated unless the input is rejected, so routers can check and
+    /// forward IDs without copying them.
+    pub fn parse_ref(s: &str) -> Result<UserFriendlyUuidRef<'_, T>, TypedUuidError> {
+        let (separator, typed_uuid) = Self::validate(s.as_bytes())?;
+        Ok(UserFriendlyUuidRef {
+            input: s,
+            separator,
+            typed_uuid,
+        })
+    }
+
+    /// Checks `input` and returns the separator position and the decoded ID.
+    fn validate(input: &[u8]) -> Result<(usize, TypedUuid<T>), TypedUuidError> {
+        let underscore_pos = find_separator(input)?;
+        let uuid = uuid::Uuid::try_parse_ascii(&input[underscore_pos + 1..])?;
+        let prefix = &input[..underscore_pos];
+
+        // Decode the variant once and compare raw prefix bytes; nothing is allocated
+        // unless the input is rejected
+        let uuid = migrate::upgrade::<T>(uuid)?;
+        let discriminant = uuid.as_bytes()[0];
+        let variant = T::from_discriminant(discriminant).ok_or(TypedUuidError::InvalidDiscriminant {
+            found: discriminant,
+            type_name: std::any::type_name::<T>(),
+        })?;
+        if prefix != variant.prefix().as_bytes() {
+            return Err(TypedUuidError::unknown_prefix::<T>(&String::from_utf8_lossy(prefix)));
+        }
+
+        // SAFETY: the discriminant was validated above (after any schema migration)
+        let typed_uuid = unsafe { TypedUuid::from_uuid_unchecked(uuid) };
+        Ok((underscore_pos, typed_uuid))
+    }
+
     /// Returns the enum variant encoded in this UUID.
     pub fn variant_type(&self) -> T {
         self.typed_uuid.variant_type()
@@ -88,6 +156,26 @@ impl<T: UuidType> UserFriendlyUuid<T> {
         self.typed_uuid.variant_type().prefix()
     }
 
+    /// Returns `true` if this UUID encodes `variant`.
+    pub fn is_variant(&self, variant: T) -> bool {
+        self.typed_uuid.is_variant(variant)
+    }
+
+    /// Shorthand for [`is_variant`](Self::is_variant).
+    pub fn is(&self, variant: T) -> bool {
+        self.is_variant(variant)
+    }
+
+    /// Returns the same ID re-tagged as `new_variant`; see [`TypedUuid::replace_variant`].
+    pub fn replace_variant(self, new_variant: T) -> Self {
+        Self::from_typed_uuid(self.typed_uuid.replace_variant(new_variant))
+    }
+
+    /// Returns `true` if this UUID's prefix is exactly `prefix`.
+    pub fn has_prefix(&self, prefix: &str) -> bool {
+        self.prefix() == prefix
+    }
+
     /// Returns a reference to the underlying TypedUuid.
     pub fn as_typed_uuid(&self) -> &TypedUuid<T> {
         &self.typed_uuid
@@ -97,6 +185,307 @@ impl<T: UuidType> UserFriendlyUuid<T> {
     pub fn into_typed_uuid(self) -> TypedUuid<T> {
         self.typed_uuid
     }
+
+    /// Returns the `{prefix}_{uuid}` string as an owned `Cow`, for APIs taking
+    /// `impl Into<Cow<'static, str>>`.
+    pub fn to_cow(&self) -> Cow<'static, str> {
+        Cow::Owned(self.hyphenated().to_string())
+    }
+
+    /// Returns an adapter that displays the UUID body without hyphens:
+    /// `retail_550e8400e29b41d4a716446655440000`.
+    ///
+    /// [`parse_str`](Self::parse_str) accepts this form as well.
+    pub fn simple(&self) -> SimpleUserFriendlyUuid<T> {
+        SimpleUserFriendlyUuid(*self)
+    }
+
+    /// Returns an adapter that always displays the canonical
+    /// `retail_550e8400-e29b-41d4-a716-446655440000` form, whatever the [`FormatConfig`].
+    pub fn hyphenated(&self) -> HyphenatedUserFriendlyUuid<T> {
+        HyphenatedUserFriendlyUuid(*self)
+    }
+}
+
+/// Displays a [`UserFriendlyUuid`] with an unhyphenated body.
+///
+/// Created by [`UserFriendlyUuid::simple`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SimpleUserFriendlyUuid<T: UuidType>(UserFriendlyUuid<T>);
+
+impl<T: UuidType> fmt::Debug for SimpleUserFriendlyUuid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SimpleUserFriendlyUuid").field(&self.0).finish()
+    }
+}
+
+impl<T: UuidType> fmt::Display for SimpleUserFriendlyUuid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}_{}", self.0.prefix(), self.0.typed_uuid.simple())
+    }
+}
+
+/// Displays a [`UserFriendlyUuid`] in the canonical form, ignoring [`FormatConfig`].
+///
+/// Created by [`UserFriendlyUuid::hyphenated`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HyphenatedUserFriendlyUuid<T: UuidType>(UserFriendlyUuid<T>);
+
+impl<T: UuidType> fmt::Debug for HyphenatedUserFriendlyUuid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("HyphenatedUserFriendlyUuid").field(&self.0).finish()
+    }
+}
+
+impl<T: UuidType> fmt::Display for HyphenatedUserFriendlyUuid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}_{}", self.0.prefix(), self.0.typed_uuid.as_uuid())
+    }
+}
+
+/// A validated user-friendly UUID that borrows the string it was parsed from.
+///
+/// Created by [`UserFriendlyUuid::parse_ref`]. The prefix and body are slices of the
+/// original input, and [`Display`](fmt::Display) writes the input unchanged, so an ID
+/// can be inspected and forwarded exactly as received. Convert with
+/// [`to_owned_id`](Self::to_owned_id) to keep it past the input's lifetime.
+///
+/// Equality and hashing compare the decoded ID, so the simple and hyphenated forms
+/// of one ID are equal.
+#[derive(Clone, Copy)]
+pub struct UserFriendlyUuidRef<'a, T: UuidType> {
+    input: &'a str,
+    separator: usize,
+    typed_uuid: TypedUuid<T>,
+}
+
+impl<'a, T: UuidType> UserFriendlyUuidRef<'a, T> {
+    /// Returns the whole input string.
+    pub fn as_str(&self) -> &'a str {
+        self.input
+    }
+
+    /// Returns the prefix part of the input.
+    pub fn prefix(&self) -> &'a str {
+        &self.input[..self.separator]
+    }
+
+    /// Returns the UUID part of the input, in whichever form it was written.
+    pub fn body(&self) -> &'a str {
+        &self.input[self.separator + 1..]
+    }
+
+    /// Returns the enum variant encoded in this UUID.
+    pub fn variant_type(&self) -> T {
+        self.typed_uuid.variant_type()
+    }
+
+    /// Returns the decoded TypedUuid, after any schema migration.
+    pub fn typed_uuid(&self) -> TypedUuid<T> {
+        self.typed_uuid
+    }
+
+    /// Returns an owned UserFriendlyUuid for the same ID.
+    pub fn to_owned_id(&self) -> UserFriendlyUuid<T> {
+        UserFriendlyUuid::from_typed_uuid(self.typed_uuid)
+    }
+}
+
+impl<T: UuidType> PartialEq for UserFriendlyUuidRef<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.typed_uuid == other.typed_uuid
+    }
+}
+
+impl<T: UuidType> Eq for UserFriendlyUuidRef<'_, T> {}
+
+impl<T: UuidType> Hash for UserFriendlyUuidRef<'_, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.typed_uuid.hash(state);
+    }
+}
+
+impl<T: UuidType> fmt::Debug for UserFriendlyUuidRef<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UserFriendlyUuidRef")
+            .field("input", &self.input)
+            .field("typed_uuid", &self.typed_uuid)
+            .finish()
+    }
+}
+
+impl<T: UuidType> fmt::Display for UserFriendlyUuidRef<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.input)
+    }
+}
+
+impl<'a, T: UuidType> TryFrom<&'a str> for UserFriendlyUuidRef<'a, T> {
+    type Error = TypedUuidError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        UserFriendlyUuid::parse_ref(s)
+    }
+}
+
+impl<T: UuidType> From<UserFriendlyUuidRef<'_, T>> for UserFriendlyUuid<T> {
+    fn from(id: UserFriendlyUuidRef<'_, T>) -> Self {
+        id.to_owned_id()
+    }
+}
+
+impl<T: UuidType> From<UserFriendlyUuidRef<'_, T>> for TypedUuid<T> {
+    fn from(id: UserFriendlyUuidRef<'_, T>) -> Self {
+        id.typed_uuid
+    }
+}
+
+impl<T: UuidType> Serialize for UserFriendlyUuidRef<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.input)
+    }
+}
+
+/// Splits a `prefix_uuid` string into its prefix and UUID parts.
+#[doc(hidden)]
+pub fn split_prefix(s: &str) -> Result<(&str, &str), TypedUuidError> {
+    let underscore_pos = find_separator(s.as_bytes())?;
+
+    // '_' is ASCII, so both sides fall on char boundaries
+    Ok((&s[..underscore_pos], &s[underscore_pos + 1..]))
+}
+
+/// Finds the `_` separating the prefix from the UUID, checking both parts' lengths.
+fn find_separator(input: &[u8]) -> Result<usize, TypedUuidError> {
+    // Reject oversized input before scanning it
+    check_len(input.len())?;
+
+    // Fast path for the usual hyphenated body: the separator sits 37 bytes from the end.
+    // A simple body is 32 bytes, so that byte may instead be an underscore inside the
+    // prefix; only take it if no underscore follows.
+    let len = input.len();
+    let underscore_pos = if len > 36 && input[len - 37] == b'_' && !input[len - 36..].contains(&b'_') {
+        len - 37
+    } else {
+        // Find the last underscore to split prefix from UUID.
+        // We use the last one because prefixes may contain underscores (e.g., "http_server"),
+        // but UUIDs never contain underscores (only hyphens).
+        input.iter().rposition(|&b| b == b'_').ok_or_else(|| {
+            TypedUuidError::InvalidFormat(
+                "expected format 'prefix_uuid', no underscore found".to_string(),
+            )
+        })?
+    };
+
+    check_parts_len(underscore_pos, len - underscore_pos - 1)?;
+    Ok(underscore_pos)
+}
+
+fn check_len(len: usize) -> Result<(), TypedUuidError> {
+    if len > MAX_PREFIX_LEN + 1 + MAX_BODY_LEN {
+        return Err(TypedUuidError::InvalidFormat(format!(
+            "input is {} bytes, longer than any valid 'prefix_uuid'",
+            len
+        )));
+    }
+    Ok(())
+}
+
+fn check_parts_len(prefix_len: usize, body_len: usize) -> Result<(), TypedUuidError> {
+    if prefix_len > MAX_PREFIX_LEN {
+        return Err(TypedUuidError::InvalidFormat(format!(
+            "prefix is {} bytes, the maximum is {}",
+            prefix_len, MAX_PREFIX_LEN
+        )));
+    }
+    // Simple, hyphenated, braced and URN forms respectively
+    if !matches!(body_len, 32 | 36 | 38 | 45) {
+        return Err(TypedUuidError::InvalidFormat(format!(
+            "UUID part is {} bytes, expected 32, 36, 38 or 45",
+            body_len
+        )));
+    }
+    Ok(())
+}
+
+// Manual impl: hashing must match `TypedUuid` and `Uuid` for the `Borrow` impls, and
+// this avoids the `T: Hash` bound a derive would add.
+impl<T: UuidType> Hash for UserFriendlyUuid<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.typed_uuid.hash(state);
+    }
+}
+
+impl<T: UuidType> AsRef<Uuid> for UserFriendlyUuid<T> {
+    fn as_ref(&self) -> &Uuid {
+        self.typed_uuid.as_uuid()
+    }
+}
+
+impl<T: UuidType> AsRef<TypedUuid<T>> for UserFriendlyUuid<T> {
+    fn as_ref(&self) -> &TypedUuid<T> {
+        &self.typed_uuid
+    }
+}
+
+impl<T: UuidType> Borrow<Uuid> for UserFriendlyUuid<T> {
+    fn borrow(&self) -> &Uuid {
+        self.typed_uuid.as_uuid()
+    }
+}
+
+impl<T: UuidType> Borrow<TypedUuid<T>> for UserFriendlyUuid<T> {
+    fn borrow(&self) -> &TypedUuid<T> {
+        &self.typed_uuid
+    }
+}
+
+impl<T: UuidType> PartialOrd for UserFriendlyUuid<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders like the [`TypedUuid`]: by discriminant, then by the remaining bytes. This
+/// is not the order of the strings, which sort by prefix name.
+impl<T: UuidType> Ord for UserFriendlyUuid<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.typed_uuid.cmp(&other.typed_uuid)
+    }
+}
+
+/// Compares against the canonical `{prefix}_{uuid}` string, lowercase and hyphenated,
+/// whatever [`FormatConfig`] `Display` uses. Other spellings of the same ID are not equal.
+impl<T: UuidType> PartialEq<str> for UserFriendlyUuid<T> {
+    fn eq(&self, other: &str) -> bool {
+        let prefix = self.prefix();
+        let Some(body) = other.strip_prefix(prefix).and_then(|rest| rest.strip_prefix('_')) else {
+            return false;
+        };
+        let mut buffer = Uuid::encode_buffer();
+        body == self.typed_uuid.hyphenated().encode_lower(&mut buffer)
+    }
+}
+
+impl<T: UuidType> PartialEq<&str> for UserFriendlyUuid<T> {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl<T: UuidType> PartialEq<UserFriendlyUuid<T>> for str {
+    fn eq(&self, other: &UserFriendlyUuid<T>) -> bool {
+        *other == *self
+    }
+}
+
+impl<T: UuidType> PartialEq<UserFriendlyUuid<T>> for &str {
+    fn eq(&self, other: &UserFriendlyUuid<T>) -> bool {
+        *other == **self
+    }
 }
 
 impl<T: UuidType> fmt::Debug for UserFriendlyUuid<T> {
@@ -107,9 +496,18 @@ impl<T: UuidType> fmt::Debug for UserFriendlyUuid<T> {
     }
 }
 
+/// Follows the type's [`FormatConfig`], or the process-wide one; canonical by default.
 impl<T: UuidType> fmt::Display for UserFriendlyUuid<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}_{}", self.prefix(), self.typed_uuid.as_uuid())
+        FormatConfig::for_type::<T>().write(f, self.prefix(), self.typed_uuid.as_uuid())
+    }
+}
+
+/// Same compact form as `TypedUuid`: the discriminant stands in for the prefix.
+#[cfg(feature = "defmt")]
+impl<T: UuidType> defmt::Format for UserFriendlyUuid<T> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        self.typed_uuid.format(f)
     }
 }
 
@@ -121,6 +519,22 @@ impl<T: UuidType> FromStr for UserFriendlyUuid<T> {
     }
 }
 
+impl<T: UuidType> TryFrom<&str> for UserFriendlyUuid<T> {
+    type Error = TypedUuidError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::parse_str(s)
+    }
+}
+
+impl<T: UuidType> TryFrom<String> for UserFriendlyUuid<T> {
+    type Error = TypedUuidError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::parse_str(&s)
+    }
+}
+
 impl<T: UuidType> From<TypedUuid<T>> for UserFriendlyUuid<T> {
     fn from(typed: TypedUuid<T>) -> Self {
         Self::from_typed_uuid(typed)
@@ -133,12 +547,18 @@ impl<T: UuidType> From<UserFriendlyUuid<T>> for TypedUuid<T> {
     }
 }
 
+impl<T: UuidType> From<UserFriendlyUuid<T>> for String {
+    fn from(friendly: UserFriendlyUuid<T>) -> Self {
+        friendly.hyphenated().to_string()
+    }
+}
+
 impl<T: UuidType> Serialize for UserFriendlyUuid<T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        serializer.collect_str(&self.hyphenated())
     }
 }
 
@@ -0,0 +1,142 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use uuid::{Uuid, Variant};
+
+use crate::error::TypedUuidError;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::split_prefix;
+
+/// Byte of `rand_b` that holds the discriminant: the last one, so the timestamp,
+/// `rand_a` and the leading random bits keep their order.
+const DISCRIMINANT_BYTE: usize = 15;
+
+/// An RFC 9562 version 7 UUID tagged with a variant, as made by [`TypedUuid::wrap_v7`].
+///
+/// Version 7 puts its timestamp in byte 0, so the discriminant cannot go there; it
+/// replaces the last byte of the random `rand_b` field instead. The timestamp, version,
+/// variant and `rand_a` bits are untouched, so the UUID is still a valid v7 that sorts
+/// where the original did. For the same reason this is not a `TypedUuid`: only
+/// [`from_uuid`](Self::from_uuid) and parsing, not `TypedUuid::from_uuid`, read the tag.
+///
+/// Displays and parses as `{prefix}_{uuid}`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct TaggedV7<T: UuidType> {
+    uuid: Uuid,
+    _marker: PhantomData<T>,
+}
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Tags an externally generated v7 UUID, e.g. from Postgres 18's `uuidv7()`, with `variant`.
+    ///
+    /// Overwrites 8 of the 62 random bits. Fails with
+    /// [`TypedUuidError::InvalidFormat`] if `v7` is not an RFC 9562 version 7 UUID.
+    pub fn wrap_v7(variant: T, v7: Uuid) -> Result<TaggedV7<T>, TypedUuidError> {
+        check_v7(&v7)?;
+        let mut bytes = v7.into_bytes();
+        bytes[DISCRIMINANT_BYTE] = variant.discriminant();
+        Ok(TaggedV7 {
+            uuid: Uuid::from_bytes(bytes),
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T: UuidType> TaggedV7<T> {
+    /// Reads back a tagged v7 UUID, validating the version and discriminant.
+    pub fn from_uuid(uuid: Uuid) -> Result<Self, TypedUuidError> {
+        check_v7(&uuid)?;
+        let discriminant = uuid.as_bytes()[DISCRIMINANT_BYTE];
+        T::from_discriminant(discriminant).ok_or(TypedUuidError::InvalidDiscriminant {
+            found: discriminant,
+            type_name: std::any::type_name::<T>(),
+        })?;
+        Ok(Self {
+            uuid,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns the variant stored in `rand_b`.
+    pub fn variant_type(&self) -> T {
+        T::from_discriminant(self.uuid.as_bytes()[DISCRIMINANT_BYTE])
+            .expect("TaggedV7 contains invalid discriminant - this is a bug")
+    }
+
+    /// Returns the prefix string for this UUID's variant.
+    pub fn prefix(&self) -> &'static str {
+        self.variant_type().prefix()
+    }
+
+    /// Returns the creation time from the v7 timestamp.
+    pub fn timestamp(&self) -> SystemTime {
+        let bytes = self.uuid.as_bytes();
+        let millis = bytes[..6].iter().fold(0u64, |acc, &b| (acc << 8) | b as u64);
+        UNIX_EPOCH + Duration::from_millis(millis)
+    }
+
+    /// Returns a reference to the underlying UUID.
+    pub fn as_uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+
+    /// Consumes self and returns the underlying UUID.
+    pub fn into_uuid(self) -> Uuid {
+        self.uuid
+    }
+}
+
+fn check_v7(uuid: &Uuid) -> Result<(), TypedUuidError> {
+    if uuid.get_version_num() != 7 || uuid.get_variant() != Variant::RFC4122 {
+        return Err(TypedUuidError::InvalidFormat(format!(
+            "expected an RFC 9562 version 7 UUID, found {}",
+            uuid
+        )));
+    }
+    Ok(())
+}
+
+// Manual impl: a derive would require `T: Hash`
+impl<T: UuidType> Hash for TaggedV7<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.uuid.hash(state);
+    }
+}
+
+impl<T: UuidType> fmt::Debug for TaggedV7<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaggedV7")
+            .field("uuid", &self.uuid)
+            .field("variant", &self.variant_type())
+            .finish()
+    }
+}
+
+impl<T: UuidType> fmt::Display for TaggedV7<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}_{}", self.prefix(), self.uuid)
+    }
+}
+
+impl<T: UuidType> FromStr for TaggedV7<T> {
+    type Err = TypedUuidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (prefix, body) = split_prefix(s)?;
+        let tagged = Self::from_uuid(Uuid::parse_str(body)?)?;
+        if prefix != tagged.prefix() {
+            return Err(TypedUuidError::unknown_prefix::<T>(prefix));
+        }
+        Ok(tagged)
+    }
+}
+
+impl<T: UuidType> From<TaggedV7<T>> for Uuid {
+    fn from(tagged: TaggedV7<T>) -> Self {
+        tagged.uuid
+    }
+}
@@ -0,0 +1,81 @@
+//! Declarative validation of ID strings in request DTOs.
+//!
+//! These run the crate's own parser, so validation never drifts from what parsing
+//! accepts the way a hand-written regex does.
+//!
+//! With the `validator` feature:
+//!
+//! ```text
+//! #[derive(Validate)]
+//! struct CreateOrder {
+//!     #[validate(custom(function = smart_uuid::validate::validator::friendly_uuid::<UserType>))]
+//!     customer_id: String,
+//! }
+//! ```
+//!
+//! With the `garde` feature:
+//!
+//! ```text
+//! #[derive(Validate)]
+//! struct CreateOrder {
+//!     #[garde(custom(smart_uuid::validate::garde::friendly_uuid::<UserType>))]
+//!     customer_id: String,
+//! }
+//! ```
+
+/// Custom validation functions for the [`validator`](https://docs.rs/validator) crate.
+///
+/// Failures use the code `"smart_uuid"`, carry the parser's message, and set the
+/// `kind` parameter to the [`ErrorKind`](crate::ErrorKind) name.
+#[cfg(feature = "validator")]
+pub mod validator {
+    use std::borrow::Cow;
+
+    use ::validator::ValidationError;
+
+    use crate::error::TypedUuidError;
+    use crate::traits::UuidType;
+    use crate::typed_uuid::TypedUuid;
+    use crate::user_friendly_uuid::UserFriendlyUuid;
+
+    /// Accepts exactly the strings `UserFriendlyUuid::<T>::parse_str` accepts.
+    pub fn friendly_uuid<T: UuidType>(value: &str) -> Result<(), ValidationError> {
+        UserFriendlyUuid::<T>::parse_str(value).map(|_| ()).map_err(to_validation_error)
+    }
+
+    /// Accepts exactly the strings `TypedUuid::<T>::from_str` accepts.
+    pub fn typed_uuid<T: UuidType>(value: &str) -> Result<(), ValidationError> {
+        value.parse::<TypedUuid<T>>().map(|_| ()).map_err(to_validation_error)
+    }
+
+    fn to_validation_error(error: TypedUuidError) -> ValidationError {
+        let mut validation = ValidationError::new("smart_uuid").with_message(Cow::Owned(error.to_string()));
+        validation.add_param(Cow::Borrowed("kind"), &error.kind().name());
+        validation
+    }
+}
+
+/// Custom validation functions for the [`garde`](https://docs.rs/garde) crate.
+///
+/// Failures carry the parser's message.
+#[cfg(feature = "garde")]
+pub mod garde {
+    use crate::traits::UuidType;
+    use crate::typed_uuid::TypedUuid;
+    use crate::user_friendly_uuid::UserFriendlyUuid;
+
+    /// Accepts exactly the strings `UserFriendlyUuid::<T>::parse_str` accepts.
+    pub fn friendly_uuid<T: UuidType>(value: &str, _context: &()) -> ::garde::Result {
+        UserFriendlyUuid::<T>::parse_str(value)
+            .map(|_| ())
+            .map_err(|e| ::garde::Error::new(e.to_string()))
+    }
+
+    /// Accepts exactly the strings `TypedUuid::<T>::from_str` accepts.
+    pub fn typed_uuid<T: UuidType>(value: &str, _context: &()) -> ::garde::Result {
+        value
+            .parse::<TypedUuid<T>>()
+            .map(|_| ())
+            .map_err(|e| ::garde::Error::new(e.to_string()))
+    }
+}
@@ -0,0 +1,53 @@
+use crate::error::VanityError;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Creates a random TypedUuid whose hyphenated form starts with `hex_prefix`,
+    /// returning it with the number of attempts it took.
+    ///
+    /// Rejection-samples [`new`](Self::new) up to `max_attempts` times, for demo or
+    /// sandbox accounts that should be recognizable at a glance. The pattern is matched
+    /// case-insensitively against the whole rendered UUID, so it must agree with the
+    /// digits fixed by the layout: the discriminant (`00` for the first variant), the
+    /// version digit and the variant digit. Each free hex digit multiplies the
+    /// expected attempts by 16.
+    ///
+    /// ```text
+    /// let (id, attempts) = TypedUuid::new_vanity(UserType::Retail, "00dead", 50_000_000)?;
+    /// // id.to_string() == "00dead..."
+    /// ```
+    pub fn new_vanity(variant: T, hex_prefix: &str, max_attempts: u32) -> Result<(Self, u32), VanityError> {
+        let pattern = hex_prefix.to_ascii_lowercase();
+        if pattern.len() > 36 || !pattern.bytes().all(|b| b.is_ascii_hexdigit() || b == b'-') {
+            return Err(VanityError::InvalidPattern(hex_prefix.to_string()));
+        }
+
+        // Render the smallest and largest payloads: any digit outside their range
+        // is fixed by the layout and can never match
+        let low = Self::from_v8_bytes(variant, [0x00; 16]).to_string();
+        let high = Self::from_v8_bytes(variant, [0xFF; 16]).to_string();
+        let possible = pattern
+            .bytes()
+            .zip(low.bytes().zip(high.bytes()))
+            .all(|(c, (lo, hi))| (lo..=hi).contains(&c) && (c == b'-') == (lo == b'-'));
+        if !possible {
+            return Err(VanityError::Impossible {
+                pattern,
+                type_name: std::any::type_name::<T>(),
+            });
+        }
+
+        for attempt in 1..=max_attempts {
+            let id = Self::new(variant);
+            if id.hyphenated().to_string().starts_with(&pattern) {
+                return Ok((id, attempt));
+            }
+        }
+
+        Err(VanityError::Exhausted {
+            pattern,
+            attempts: max_attempts,
+        })
+    }
+}
@@ -0,0 +1,246 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use uuid::Uuid;
+
+use crate::error::TypedUuidError;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+use crate::user_friendly_uuid::UserFriendlyUuid;
+
+/// A [`TypedUuid`] known to hold one particular variant, the one with discriminant `D`.
+///
+/// `TypedUuid<UserType>` accepts any kind of user; `VariantUuid<UserType, 0>` accepts
+/// only retail users, so passing a business ID where a retail one is expected fails to
+/// compile. `#[uuid_type(variant_ids)]` on the enum names one per variant, e.g.
+/// `UserTypeRetailId`:
+///
+/// ```compile_fail
+/// use smart_uuid::{TypedUuid, UuidType};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+/// #[uuid_type(variant_ids)]
+/// enum UserType {
+///     Retail,
+///     Business,
+/// }
+///
+/// fn close_retail_account(id: UserTypeRetailId) {}
+/// close_retail_account(UserTypeBusinessId::new());
+/// ```
+///
+/// Converting into a `TypedUuid<T>` always succeeds; the other way checks the variant
+/// (see [`from_typed`](Self::from_typed)). It derefs to `TypedUuid<T>`, and displays,
+/// parses, serializes, hashes and orders exactly like it.
+///
+/// `D` is checked against [`UuidType::DISCRIMINANTS`] wherever an ID of the type is
+/// created, so a discriminant `T` does not have fails to compile. So does any `D` if `T`
+/// is implemented by hand without that list.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct VariantUuid<T: UuidType, const D: u8> {
+    typed_uuid: TypedUuid<T>,
+}
+
+impl<T: UuidType, const D: u8> VariantUuid<T, D> {
+    /// The discriminant in byte 0 of every ID of this type.
+    pub const DISCRIMINANT: u8 = {
+        assert!(
+            !T::DISCRIMINANTS.is_empty(),
+            "VariantUuid: the UuidType impl does not list its DISCRIMINANTS"
+        );
+        let mut i = 0;
+        while i < T::DISCRIMINANTS.len() && T::DISCRIMINANTS[i] != D {
+            i += 1;
+        }
+        assert!(i < T::DISCRIMINANTS.len(), "VariantUuid: D is not a discriminant of the type");
+        D
+    };
+
+    /// Creates a new random ID of the variant, like [`TypedUuid::new`].
+    pub fn new() -> Self {
+        Self {
+            typed_uuid: TypedUuid::new(Self::variant()),
+        }
+    }
+
+    /// Returns the variant every ID of this type holds.
+    pub fn variant() -> T {
+        T::from_discriminant(Self::DISCRIMINANT)
+            .expect("DISCRIMINANT is checked against T::DISCRIMINANTS - this is a bug")
+    }
+
+    /// Returns the prefix of the variant, e.g. `"retail"`.
+    pub fn prefix() -> &'static str {
+        Self::variant().prefix()
+    }
+
+    /// Narrows a typed ID to this variant.
+    ///
+    /// Fails with [`TypedUuidError::VariantNotAllowed`] if it holds another variant.
+    pub fn from_typed(typed_uuid: TypedUuid<T>) -> Result<Self, TypedUuidError> {
+        let typed_uuid = typed_uuid.check_allowed(&[Self::variant()])?;
+        Ok(Self { typed_uuid })
+    }
+
+    /// Creates an ID from an existing UUID, validating the discriminant like
+    /// [`TypedUuid::from_uuid`] and then the variant like [`from_typed`](Self::from_typed).
+    pub fn from_uuid(uuid: Uuid) -> Result<Self, TypedUuidError> {
+        Self::from_typed(TypedUuid::from_uuid(uuid)?)
+    }
+
+    /// Returns a reference to the typed ID.
+    pub fn as_typed_uuid(&self) -> &TypedUuid<T> {
+        &self.typed_uuid
+    }
+
+    /// Consumes self and returns the typed ID.
+    pub fn into_typed_uuid(self) -> TypedUuid<T> {
+        self.typed_uuid
+    }
+}
+
+impl<T: UuidType, const D: u8> Default for VariantUuid<T, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: UuidType, const D: u8> Deref for VariantUuid<T, D> {
+    type Target = TypedUuid<T>;
+
+    fn deref(&self) -> &TypedUuid<T> {
+        &self.typed_uuid
+    }
+}
+
+impl<T: UuidType, const D: u8> AsRef<TypedUuid<T>> for VariantUuid<T, D> {
+    fn as_ref(&self) -> &TypedUuid<T> {
+        &self.typed_uuid
+    }
+}
+
+impl<T: UuidType, const D: u8> AsRef<Uuid> for VariantUuid<T, D> {
+    fn as_ref(&self) -> &Uuid {
+        self.typed_uuid.as_uuid()
+    }
+}
+
+// Manual impl: a derive would require `T: Hash`
+impl<T: UuidType, const D: u8> Hash for VariantUuid<T, D> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.typed_uuid.hash(state);
+    }
+}
+
+impl<T: UuidType, const D: u8> PartialOrd for VariantUuid<T, D> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: UuidType, const D: u8> Ord for VariantUuid<T, D> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.typed_uuid.cmp(&other.typed_uuid)
+    }
+}
+
+impl<T: UuidType, const D: u8> PartialEq<TypedUuid<T>> for VariantUuid<T, D> {
+    fn eq(&self, other: &TypedUuid<T>) -> bool {
+        self.typed_uuid == *other
+    }
+}
+
+impl<T: UuidType, const D: u8> PartialEq<VariantUuid<T, D>> for TypedUuid<T> {
+    fn eq(&self, other: &VariantUuid<T, D>) -> bool {
+        *self == other.typed_uuid
+    }
+}
+
+impl<T: UuidType, const D: u8> fmt::Debug for VariantUuid<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VariantUuid")
+            .field("uuid", self.typed_uuid.as_uuid())
+            .field("variant", &Self::variant())
+            .finish()
+    }
+}
+
+impl<T: UuidType, const D: u8> fmt::Display for VariantUuid<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.typed_uuid, f)
+    }
+}
+
+impl<T: UuidType, const D: u8> FromStr for VariantUuid<T, D> {
+    type Err = TypedUuidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_typed(s.parse()?)
+    }
+}
+
+impl<T: UuidType, const D: u8> TryFrom<TypedUuid<T>> for VariantUuid<T, D> {
+    type Error = TypedUuidError;
+
+    fn try_from(typed_uuid: TypedUuid<T>) -> Result<Self, Self::Error> {
+        Self::from_typed(typed_uuid)
+    }
+}
+
+impl<T: UuidType, const D: u8> TryFrom<UserFriendlyUuid<T>> for VariantUuid<T, D> {
+    type Error = TypedUuidError;
+
+    fn try_from(friendly: UserFriendlyUuid<T>) -> Result<Self, Self::Error> {
+        Self::from_typed(friendly.into_typed_uuid())
+    }
+}
+
+impl<T: UuidType, const D: u8> TryFrom<Uuid> for VariantUuid<T, D> {
+    type Error = TypedUuidError;
+
+    fn try_from(uuid: Uuid) -> Result<Self, Self::Error> {
+        Self::from_uuid(uuid)
+    }
+}
+
+impl<T: UuidType, const D: u8> From<VariantUuid<T, D>> for TypedUuid<T> {
+    fn from(id: VariantUuid<T, D>) -> Self {
+        id.typed_uuid
+    }
+}
+
+impl<T: UuidType, const D: u8> From<VariantUuid<T, D>> for UserFriendlyUuid<T> {
+    fn from(id: VariantUuid<T, D>) -> Self {
+        id.typed_uuid.into()
+    }
+}
+
+impl<T: UuidType, const D: u8> From<VariantUuid<T, D>> for Uuid {
+    fn from(id: VariantUuid<T, D>) -> Self {
+        id.typed_uuid.into_uuid()
+    }
+}
+
+impl<T: UuidType, const D: u8> Serialize for VariantUuid<T, D> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.typed_uuid.serialize(serializer)
+    }
+}
+
+impl<'de, T: UuidType, const D: u8> Deserialize<'de> for VariantUuid<T, D> {
+    fn deserialize<De>(deserializer: De) -> Result<Self, De::Error>
+    where
+        De: Deserializer<'de>,
+    {
+        let typed_uuid = TypedUuid::deserialize(deserializer)?;
+        Self::from_typed(typed_uuid).map_err(serde::de::Error::custom)
+    }
+}
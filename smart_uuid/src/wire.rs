@@ -0,0 +1,30 @@
+//! Fixed-size binary encoding with a stability guarantee independent of serde.
+
+use uuid::Uuid;
+
+use crate::error::TypedUuidError;
+use crate::traits::UuidType;
+use crate::typed_uuid::TypedUuid;
+
+/// Version of the format produced by [`TypedUuid::to_wire_bytes`].
+///
+/// Version 1 is the 16 bytes of the UUID in RFC 9562 (big-endian) order, with nothing
+/// added: byte 0 is the discriminant. The layout of a version will never change; a new
+/// layout would get a new version and new methods, leaving these untouched.
+pub const WIRE_FORMAT_VERSION: u8 = 1;
+
+impl<T: UuidType> TypedUuid<T> {
+    /// Encodes this ID in wire format version [`WIRE_FORMAT_VERSION`].
+    ///
+    /// Unlike serde output, which depends on the format and its settings, these bytes
+    /// are fixed, so firmware can decode them without this crate.
+    pub fn to_wire_bytes(&self) -> [u8; 16] {
+        *self.as_bytes()
+    }
+
+    /// Decodes an ID written by [`to_wire_bytes`](Self::to_wire_bytes), validating the
+    /// discriminant.
+    pub fn from_wire_bytes(bytes: [u8; 16]) -> Result<Self, TypedUuidError> {
+        Self::from_uuid(Uuid::from_bytes(bytes))
+    }
+}
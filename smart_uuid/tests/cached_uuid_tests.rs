@@ -0,0 +1,57 @@
+//! Tests for `CachedUuid`, the typed ID that stores its decoded variant.
+
+use std::mem::size_of;
+
+use smart_uuid::{CachedUuid, TypedUuid, TypedUuidError, UserFriendlyUuid, Uuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    Business,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+#[test]
+fn caches_the_variant_of_the_wrapped_id() {
+    let typed = TypedUuid::new(UserType::Organization);
+    let cached = CachedUuid::from(typed);
+    assert_eq!(cached.variant_type(), UserType::Organization);
+    assert!(cached.is_variant(UserType::Organization));
+    assert_eq!(cached, typed);
+    assert_eq!(TypedUuid::from(cached), typed);
+    assert_eq!(cached.as_uuid(), typed.as_uuid());
+
+    assert_eq!(CachedUuid::new(UserType::Business).variant_type(), UserType::Business);
+}
+
+#[test]
+fn typed_uuid_keeps_its_size() {
+    assert_eq!(size_of::<TypedUuid<UserType>>(), size_of::<Uuid>());
+    assert!(size_of::<CachedUuid<UserType>>() > size_of::<Uuid>());
+}
+
+#[test]
+fn validates_raw_uuids_like_typed_uuid() {
+    let typed = TypedUuid::new(UserType::Retail);
+    assert_eq!(CachedUuid::<UserType>::try_from(typed.into_uuid()).unwrap(), typed);
+
+    let mut bytes = *typed.as_uuid().as_bytes();
+    bytes[0] = 9;
+    let err = CachedUuid::<UserType>::from_uuid(Uuid::from_bytes(bytes)).unwrap_err();
+    assert!(matches!(err, TypedUuidError::InvalidDiscriminant { found: 9, .. }));
+}
+
+#[test]
+fn formats_like_typed_uuid() {
+    let friendly = UserFriendlyUuid::new(UserType::Organization);
+    let cached = CachedUuid::from(friendly);
+    assert_eq!(cached.to_string(), friendly.as_typed_uuid().to_string());
+    assert_eq!(cached.to_string().parse::<CachedUuid<UserType>>().unwrap(), cached);
+    assert_eq!(UserFriendlyUuid::from(cached), friendly);
+
+    let json = serde_json::to_string(&cached).unwrap();
+    assert_eq!(json, serde_json::to_string(friendly.as_typed_uuid()).unwrap());
+    let parsed: CachedUuid<UserType> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.variant_type(), UserType::Organization);
+}
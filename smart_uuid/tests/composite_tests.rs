@@ -0,0 +1,73 @@
+//! Tests for two-part composite identifiers.
+
+use smart_uuid::{CompositeId, TypedUuid, TypedUuidError, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum OrderType {
+    Order,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum ItemType {
+    Item,
+}
+
+type OrderItemId = CompositeId<TypedUuid<OrderType>, TypedUuid<ItemType>>;
+
+#[test]
+fn composite_id_formats_and_parses() {
+    let order = TypedUuid::new(OrderType::Order);
+    let item = TypedUuid::new(ItemType::Item);
+    let id = OrderItemId::new(order, item);
+
+    let s = id.to_string();
+    assert_eq!(s, format!("order_{}.item_{}", order, item));
+
+    let parsed: OrderItemId = s.parse().unwrap();
+    assert_eq!(parsed, id);
+    assert_eq!(parsed.into_parts(), (order, item));
+}
+
+#[test]
+fn composite_id_with_integer_part() {
+    let order = TypedUuid::new(OrderType::Order);
+    let line = CompositeId::new(order, 3u64);
+
+    assert_eq!(line.to_string(), format!("order_{}.3", order));
+    assert_eq!(CompositeId::<TypedUuid<OrderType>, u64>::parse_str(&line.to_string()).unwrap(), line);
+    assert!(CompositeId::<TypedUuid<OrderType>, u64>::parse_str(&format!("order_{}.x", order)).is_err());
+}
+
+#[test]
+fn composite_id_rejects_bad_input() {
+    let order = TypedUuid::new(OrderType::Order);
+    let item = TypedUuid::new(ItemType::Item);
+
+    let result = OrderItemId::parse_str(&format!("order_{}", order));
+    assert!(matches!(result, Err(TypedUuidError::InvalidFormat(_))));
+
+    // Parts in the wrong order fail the prefix checks
+    let result = OrderItemId::parse_str(&format!("item_{}.order_{}", item, order));
+    assert!(result.is_err());
+}
+
+#[test]
+fn composite_id_orders_by_first_then_second() {
+    let order = TypedUuid::new(OrderType::Order);
+    let mut ids: Vec<_> = [5u64, 1, 3].iter().map(|&n| CompositeId::new(order, n)).collect();
+    ids.sort();
+
+    let lines: Vec<_> = ids.iter().map(|id| id.second()).collect();
+    assert_eq!(lines, vec![1, 3, 5]);
+}
+
+#[test]
+fn composite_id_serde_roundtrip() {
+    let id = OrderItemId::new(TypedUuid::new(OrderType::Order), TypedUuid::new(ItemType::Item));
+
+    let json = serde_json::to_string(&id).unwrap();
+    assert_eq!(json, format!("\"{}\"", id));
+
+    let back: OrderItemId = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, id);
+}
@@ -0,0 +1,43 @@
+//! Tests for the `chrono`, `time` and `jiff` timestamp accessors.
+//! Run with: cargo test -p smart_uuid --features chrono,time,jiff
+
+#![cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use smart_uuid::{TypedUuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum OrderType {
+    Order,
+}
+
+const MILLIS: u64 = 1_700_000_000_123;
+
+fn ordered() -> TypedUuid<OrderType> {
+    TypedUuid::new_ordered_at(OrderType::Order, UNIX_EPOCH + Duration::from_millis(MILLIS))
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_timestamp() {
+    let expected = chrono::DateTime::from_timestamp_millis(MILLIS as i64).unwrap();
+    assert_eq!(ordered().timestamp_chrono(), Some(expected));
+    assert_eq!(TypedUuid::new(OrderType::Order).timestamp_chrono(), None);
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_timestamp() {
+    let expected = time::OffsetDateTime::from_unix_timestamp_nanos(MILLIS as i128 * 1_000_000).unwrap();
+    assert_eq!(ordered().timestamp_time(), Some(expected));
+    assert_eq!(TypedUuid::new(OrderType::Order).timestamp_time(), None);
+}
+
+#[cfg(feature = "jiff")]
+#[test]
+fn jiff_timestamp() {
+    let expected = jiff::Timestamp::from_millisecond(MILLIS as i64).unwrap();
+    assert_eq!(ordered().timestamp_jiff(), Some(expected));
+    assert_eq!(TypedUuid::new(OrderType::Order).timestamp_jiff(), None);
+}
@@ -0,0 +1,99 @@
+//! Tests for the Diesel integration, run through diesel-async.
+//! Run with: cargo test -p smart_uuid --features diesel
+//!
+//! The query-building test runs anywhere. The round-trip tests are ignored by default;
+//! to run them, point `DATABASE_URL` at a Postgres database and pass `--ignored`, e.g.
+//! `DATABASE_URL=postgres://postgres@localhost/postgres cargo test ... -- --ignored`.
+
+#![cfg(feature = "diesel")]
+
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl, SimpleAsyncConnection};
+use smart_uuid::{TypedUuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+diesel::table! {
+    smart_uuid_users (id) {
+        id -> Uuid,
+        referrer -> Nullable<Uuid>,
+        name -> Text,
+    }
+}
+
+#[derive(Debug, PartialEq, Queryable, Insertable)]
+#[diesel(table_name = smart_uuid_users)]
+struct User {
+    id: TypedUuid<UserType>,
+    referrer: Option<TypedUuid<UserType>>,
+    name: String,
+}
+
+/// Connects to `DATABASE_URL` and creates an empty temporary table.
+async fn connect() -> AsyncPgConnection {
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set to run the database tests");
+    let mut conn = AsyncPgConnection::establish(&url).await.unwrap();
+    conn.batch_execute("CREATE TEMPORARY TABLE smart_uuid_users (id UUID PRIMARY KEY, referrer UUID, name TEXT NOT NULL)")
+        .await
+        .unwrap();
+    conn
+}
+
+#[test]
+fn typed_ids_bind_as_uuid_parameters() {
+    let id = TypedUuid::new(UserType::Retail);
+    let query = smart_uuid_users::table.filter(smart_uuid_users::id.eq(id));
+
+    let sql = diesel::debug_query::<Pg, _>(&query).to_string();
+    assert!(sql.contains("WHERE (\"smart_uuid_users\".\"id\" = $1)"), "{}", sql);
+    assert!(sql.contains(&id.as_uuid().to_string()), "{}", sql);
+}
+
+#[tokio::test(flavor = "current_thread")]
+#[ignore = "needs DATABASE_URL"]
+async fn typed_ids_round_trip_through_postgres() {
+    let mut conn = connect().await;
+
+    let org = TypedUuid::new(UserType::Organization);
+    let users = vec![
+        User { id: org, referrer: None, name: "acme".to_string() },
+        User { id: TypedUuid::new(UserType::Retail), referrer: Some(org), name: "ada".to_string() },
+    ];
+    diesel::insert_into(smart_uuid_users::table).values(&users).execute(&mut conn).await.unwrap();
+
+    let loaded: Vec<User> = smart_uuid_users::table
+        .order(smart_uuid_users::name)
+        .load(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(loaded, users);
+
+    let referred: TypedUuid<UserType> = smart_uuid_users::table
+        .filter(smart_uuid_users::referrer.eq(org))
+        .select(smart_uuid_users::id)
+        .first(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(referred, users[1].id);
+}
+
+#[tokio::test(flavor = "current_thread")]
+#[ignore = "needs DATABASE_URL"]
+async fn loading_rejects_mistyped_ids() {
+    let mut conn = connect().await;
+
+    conn.batch_execute("INSERT INTO smart_uuid_users VALUES ('ff000000-0000-8000-8000-000000000000', NULL, 'bad')")
+        .await
+        .unwrap();
+    let result = smart_uuid_users::table
+        .select(smart_uuid_users::id)
+        .first::<TypedUuid<UserType>>(&mut conn)
+        .await;
+    assert!(result.is_err());
+}
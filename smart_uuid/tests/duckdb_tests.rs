@@ -0,0 +1,97 @@
+//! Tests for the DuckDB integration.
+//! Run with: cargo test -p smart_uuid --features duckdb-bundled
+
+#![cfg(feature = "duckdb")]
+
+use duckdb::{params, Connection};
+use smart_uuid::{TypedUuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum OrderType {
+    Order,
+}
+
+fn connection() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch("CREATE TABLE orders (id UUID, customer UUID)").unwrap();
+    conn
+}
+
+#[test]
+fn typed_ids_append_and_read_as_native_uuids() {
+    let conn = connection();
+    let rows: Vec<_> = (0..3)
+        .map(|_| (TypedUuid::new(OrderType::Order), TypedUuid::new(UserType::Organization)))
+        .collect();
+
+    let mut appender = conn.appender("orders").unwrap();
+    for (id, customer) in &rows {
+        appender.append_row(params![id, customer]).unwrap();
+    }
+    appender.flush().unwrap();
+    drop(appender);
+
+    let column_type: String = conn
+        .query_row("SELECT typeof(customer) FROM orders LIMIT 1", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(column_type, "UUID");
+
+    let (id, customer) = rows[1];
+    let found: TypedUuid<UserType> = conn
+        .query_row("SELECT customer FROM orders WHERE id = ?", params![id], |row| row.get(0))
+        .unwrap();
+    assert_eq!(found, customer);
+}
+
+#[test]
+fn reading_rejects_mistyped_ids() {
+    let conn = connection();
+    let order = TypedUuid::new(OrderType::Order);
+    conn.execute("INSERT INTO orders VALUES (?, ?)", params![order, order]).unwrap();
+
+    // Order's discriminant 0 happens to be valid for UserType too; 0xff is valid for neither
+    conn.execute_batch("UPDATE orders SET customer = 'ff000000-0000-8000-8000-000000000000'").unwrap();
+    let result: duckdb::Result<TypedUuid<UserType>> =
+        conn.query_row("SELECT customer FROM orders", [], |row| row.get(0));
+    assert!(result.is_err());
+}
+
+#[test]
+fn macros_check_discriminants_in_sql() {
+    let conn = connection();
+    smart_uuid::duckdb::register_macros::<UserType>(&conn, "user_id").unwrap();
+
+    let org = TypedUuid::new(UserType::Organization);
+    conn.execute("INSERT INTO orders VALUES (gen_random_uuid(), ?)", params![org]).unwrap();
+    conn.execute_batch(
+        "INSERT INTO orders VALUES (gen_random_uuid(), 'ff000000-0000-8000-8000-000000000000');
+         INSERT INTO orders VALUES (gen_random_uuid(), NULL);
+         CREATE MACRO bad_customers() AS TABLE
+             SELECT customer FROM orders WHERE NOT user_id_valid(customer);",
+    )
+    .unwrap();
+
+    let (valid, prefix): (bool, String) = conn
+        .query_row("SELECT user_id_valid(customer), user_id_prefix(customer) FROM orders WHERE customer = ?", params![org], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert!(valid);
+    assert_eq!(prefix, "org");
+
+    let bad: Vec<String> = conn
+        .prepare("SELECT CAST(customer AS VARCHAR) FROM bad_customers()")
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(bad, vec!["ff000000-0000-8000-8000-000000000000"]);
+}
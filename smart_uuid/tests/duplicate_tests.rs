@@ -0,0 +1,49 @@
+use smart_uuid::{DuplicateGuard, SuspectedDuplicate, TypedUuid, UuidType, Uuid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum LedgerType {
+    Entry,
+    Transfer,
+}
+
+#[test]
+fn detects_repeated_values() {
+    let guard = DuplicateGuard::new(1_000, 0.001);
+    let id = TypedUuid::new(LedgerType::Entry);
+
+    assert!(!guard.contains(id.as_uuid()));
+    assert_eq!(guard.check(id.as_uuid()), Ok(()));
+    assert!(guard.contains(id.as_uuid()));
+    assert_eq!(guard.check(id.as_uuid()), Err(SuspectedDuplicate { uuid: *id.as_uuid() }));
+
+    guard.clear();
+    assert!(guard.insert(id.as_uuid()));
+}
+
+#[test]
+fn new_guarded_records_generated_ids() {
+    let guard = DuplicateGuard::new(1_000, 1e-9);
+    let ids: Vec<_> = (0..1_000)
+        .map(|_| TypedUuid::new_guarded(LedgerType::Entry, &guard).unwrap())
+        .collect();
+    assert!(ids.iter().all(|id| guard.contains(id.as_uuid())));
+}
+
+#[test]
+fn false_positives_stay_near_the_requested_rate() {
+    let guard = DuplicateGuard::new(10_000, 0.01);
+    assert!(guard.memory_bytes() < 16 * 1024, "{} bytes", guard.memory_bytes());
+
+    for _ in 0..10_000 {
+        guard.insert(TypedUuid::new(LedgerType::Transfer).as_uuid());
+    }
+    // Sequential values differ in few bits, unlike random ones
+    let false_positives = (0..10_000u128).filter(|&i| guard.contains(&Uuid::from_u128(i))).count();
+    assert!(false_positives < 300, "{} false positives", false_positives);
+}
+
+#[test]
+#[should_panic(expected = "false positive rate")]
+fn rejects_invalid_rate() {
+    DuplicateGuard::new(100, 1.0);
+}
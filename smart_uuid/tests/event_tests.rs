@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use smart_uuid::{CausationId, CorrelationId, EventEnvelope, TypedUuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum EventType {
+    #[uuid_type(prefix = "evt_order")]
+    OrderPlaced,
+    #[uuid_type(prefix = "evt_pay")]
+    PaymentTaken,
+    #[uuid_type(prefix = "evt_ship")]
+    Shipped,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Note(String);
+
+#[test]
+fn root_event_is_its_own_correlation_and_cause() {
+    let placed = EventEnvelope::new_root(EventType::OrderPlaced, Note("placed".into()));
+    assert!(placed.is_root());
+    assert_eq!(*placed.correlation_id.as_typed_uuid(), placed.event_id);
+    assert_eq!(*placed.causation_id.as_typed_uuid(), placed.event_id);
+}
+
+#[test]
+fn children_inherit_correlation_and_point_at_their_parent() {
+    let placed = EventEnvelope::new_root(EventType::OrderPlaced, Note("placed".into()));
+    let paid = EventEnvelope::caused_by(&placed, EventType::PaymentTaken, Note("paid".into()));
+    let shipped = EventEnvelope::caused_by(&paid, EventType::Shipped, 42u32);
+
+    assert!(!paid.is_root());
+    assert_eq!(shipped.correlation_id, CorrelationId::new_root(placed.event_id));
+    assert_eq!(shipped.causation_id, CausationId::from_parent(paid.event_id));
+    assert_eq!(paid.causation_id, CausationId::from_parent(placed.event_id));
+    assert_eq!(shipped.event_id.variant_type(), EventType::Shipped);
+}
+
+#[test]
+fn wrappers_serialize_and_display_like_the_inner_id() {
+    let placed = EventEnvelope::new_root(EventType::OrderPlaced, Note("placed".into()));
+    let json = serde_json::to_value(&placed).unwrap();
+    assert_eq!(json["correlation_id"], placed.event_id.to_string());
+    assert_eq!(json["causation_id"], placed.event_id.to_string());
+
+    let back: EventEnvelope<EventType, Note> = serde_json::from_value(json).unwrap();
+    assert_eq!(back, placed);
+
+    let correlation = placed.correlation_id;
+    assert_eq!(correlation.to_string(), placed.event_id.to_string());
+    assert_eq!(correlation.to_string().parse::<CorrelationId<EventType>>().unwrap(), correlation);
+    assert_eq!(TypedUuid::from(correlation), placed.event_id);
+}
@@ -0,0 +1,47 @@
+//! Tests for the `flatbuffers` feature. Run with: cargo test -p smart_uuid --features flatbuffers
+
+#![cfg(feature = "flatbuffers")]
+
+use flatbuffers::{Array, FlatBufferBuilder};
+use smart_uuid::{TypedUuid, UserFriendlyUuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+#[test]
+fn reads_the_struct_bytes_and_validates_them() {
+    let typed = TypedUuid::new(UserType::Organization);
+    let bytes = typed.to_wire_bytes();
+
+    // What the flatc-generated `bytes()` accessor returns for a `smart_uuid.Uuid` field.
+    // SAFETY: the buffer holds exactly 16 bytes.
+    let array = unsafe { Array::<u8, 16>::new(&bytes) };
+    assert_eq!(TypedUuid::from_flatbuffers(array).unwrap(), typed);
+
+    let mut invalid = bytes;
+    invalid[0] = 0xff;
+    // SAFETY: as above
+    let array = unsafe { Array::<u8, 16>::new(&invalid) };
+    assert!(TypedUuid::<UserType>::from_flatbuffers(array).unwrap_err().is_invalid_discriminant());
+}
+
+#[test]
+fn string_fallback_writes_the_friendly_form() {
+    let typed = TypedUuid::new(UserType::Retail);
+
+    let mut builder = FlatBufferBuilder::new();
+    let offset = typed.create_flatbuffers_string(&mut builder);
+    builder.finish_minimal(offset);
+
+    let read = flatbuffers::root::<&str>(builder.finished_data()).unwrap();
+    assert_eq!(UserFriendlyUuid::<UserType>::parse_str(read).unwrap().into_typed_uuid(), typed);
+}
+
+#[test]
+fn schema_snippet_defines_the_struct() {
+    assert!(smart_uuid::flatbuffers::SCHEMA.contains("struct Uuid {\n  bytes:[ubyte:16];\n}"));
+}
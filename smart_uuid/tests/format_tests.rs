@@ -0,0 +1,80 @@
+//! Tests for `FormatConfig`.
+//!
+//! The process-wide policy is shared by every test in this binary, so only
+//! `global_policy_changes_display_but_not_wire_formats` sets it, and the other tests
+//! use types with their own format.
+
+use smart_uuid::{BodyFormat, Case, FormatConfig, TypedUuid, UserFriendlyUuid, Uuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(format = FormatConfig::new().with_separator(':').with_case(Case::Upper).with_body(BodyFormat::Simple))]
+enum AccountType {
+    Checking,
+}
+
+smart_uuid::define_uuid_union! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum AnyId {
+        User(UserType),
+        Account(AccountType),
+    }
+}
+
+fn org_id() -> UserFriendlyUuid<UserType> {
+    TypedUuid::from_uuid(Uuid::parse_str("01123456-789a-8bcd-8ef0-123456789abc").unwrap())
+        .unwrap()
+        .into()
+}
+
+#[test]
+fn global_policy_changes_display_but_not_wire_formats() {
+    let id = org_id();
+    let canonical = "org_01123456-789a-8bcd-8ef0-123456789abc";
+    assert_eq!(FormatConfig::global(), FormatConfig::DEFAULT);
+    assert_eq!(id.to_string(), canonical);
+
+    FormatConfig::new().with_body(BodyFormat::Base32).set_global();
+    assert_eq!(FormatConfig::global().body(), BodyFormat::Base32);
+    assert_eq!(id.to_string(), "org_0128t5cy4thf6rxw0j6hb7h6nw");
+
+    FormatConfig::new().with_separator('/').with_case(Case::Upper).set_global();
+    assert_eq!(id.to_string(), "org/01123456-789A-8BCD-8EF0-123456789ABC");
+
+    // Machine-readable forms stay canonical
+    assert_eq!(id.hyphenated().to_string(), canonical);
+    assert_eq!(String::from(id), canonical);
+    assert_eq!(id.to_cow(), canonical);
+    assert_eq!(serde_json::to_string(&id).unwrap(), format!("\"{}\"", canonical));
+    assert_eq!(id.as_typed_uuid().cluster_key("user:{}"), format!("user:{{{}}}", canonical));
+
+    let any = AnyId::from(id);
+    assert_eq!(any.to_string(), canonical);
+    assert_eq!(any.to_string().parse::<AnyId>().unwrap(), any);
+    let account = AnyId::from(TypedUuid::new(AccountType::Checking));
+    assert_eq!(account.to_string().parse::<AnyId>().unwrap(), account);
+
+    // A type's own format wins over the global one
+    let account = UserFriendlyUuid::new(AccountType::Checking);
+    assert!(account.to_string().starts_with("checking:"));
+
+    FormatConfig::reset_global();
+    assert_eq!(id.to_string(), canonical);
+}
+
+#[test]
+fn per_type_format_is_used_by_display() {
+    let account = UserFriendlyUuid::new(AccountType::Checking);
+    let expected = format!("checking:{:X}", account.as_typed_uuid().simple());
+    assert_eq!(account.to_string(), expected);
+    assert_eq!(FormatConfig::for_type::<AccountType>().separator(), ':');
+
+    // Parsing still takes the canonical form
+    assert_eq!(account.hyphenated().to_string().parse::<UserFriendlyUuid<AccountType>>().unwrap(), account);
+}
@@ -0,0 +1,76 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+
+use smart_uuid::test::MockClock;
+use smart_uuid::{
+    Clock, Layout, OrderedGenerator, RandomGenerator, SequentialGenerator, SnowflakeGenerator, SnowflakeLayout, TypedUuid,
+    UserFriendlyUuid, UuidGenerator, UuidType,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+/// Mints IDs the way application code taking an injected generator would.
+fn mint_pair(generator: &impl UuidGenerator) -> (TypedUuid<UserType>, UserFriendlyUuid<UserType>) {
+    (
+        TypedUuid::new_with(generator, UserType::Retail),
+        UserFriendlyUuid::new_with(generator, UserType::Organization),
+    )
+}
+
+#[test]
+fn each_generator_produces_its_layout() {
+    let (retail, org) = mint_pair(&RandomGenerator);
+    assert_eq!(retail.variant_type(), UserType::Retail);
+    assert_eq!(org.variant_type(), UserType::Organization);
+    assert_eq!(retail.layout(), Layout::V8);
+
+    let clock = MockClock::new(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+    let (retail, org) = mint_pair(&OrderedGenerator::with_clock(&clock));
+    assert_eq!(retail.layout(), Layout::TimeOrdered);
+    assert_eq!(retail.timestamp(), Some(clock.now()));
+    assert_eq!(org.timestamp(), Some(clock.now()));
+
+    let snowflakes = SnowflakeGenerator::new(SnowflakeLayout::TWITTER, 7u16);
+    let (retail, _) = mint_pair(&snowflakes);
+    assert_eq!(retail.layout(), Layout::TimeOrdered);
+    assert_eq!(snowflakes.state().worker_id(), 7);
+}
+
+#[test]
+fn sequential_ids_are_reproducible() {
+    let generator = SequentialGenerator::new();
+    let (retail, org) = mint_pair(&generator);
+    assert_eq!(retail.to_string(), "00000000-0000-8000-8000-000000000001");
+    assert_eq!(org.to_string(), "org_01000000-0000-8000-8000-000000000002");
+    assert_eq!(retail, mint_pair(&SequentialGenerator::new()).0);
+
+    let generator = SequentialGenerator::starting_at(0xabc);
+    assert_eq!(
+        TypedUuid::new_with(&generator, UserType::Retail).to_string(),
+        "00000000-0000-8000-8000-000000000abc"
+    );
+}
+
+#[test]
+fn shared_generators_never_repeat() {
+    let generator = Arc::new(SequentialGenerator::new());
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let generator = Arc::clone(&generator);
+            std::thread::spawn(move || {
+                (0..250)
+                    .map(|_| TypedUuid::new_with(&generator, UserType::Retail))
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    let ids: HashSet<_> = handles.into_iter().flat_map(|h| h.join().unwrap()).collect();
+    assert_eq!(ids.len(), 1000);
+}
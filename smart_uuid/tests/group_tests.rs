@@ -0,0 +1,50 @@
+use smart_uuid::{TypedUuid, TypedUuidIterExt, UserFriendlyUuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    Business,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+fn sample() -> Vec<TypedUuid<UserType>> {
+    [UserType::Business, UserType::Retail, UserType::Business, UserType::Business]
+        .into_iter()
+        .map(TypedUuid::new)
+        .collect()
+}
+
+#[test]
+fn group_by_variant_keeps_order_within_groups() {
+    let ids = sample();
+    let groups = ids.clone().into_iter().group_by_variant();
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups.variants().collect::<Vec<_>>(), [UserType::Retail, UserType::Business]);
+    assert_eq!(groups.get(UserType::Retail), Some(&vec![ids[1]]));
+    assert_eq!(groups.get(UserType::Business), Some(&vec![ids[0], ids[2], ids[3]]));
+    assert_eq!(groups.get(UserType::Organization), None);
+
+    let owned: Vec<_> = groups.into_iter().map(|(variant, ids)| (variant, ids.len())).collect();
+    assert_eq!(owned, [(UserType::Retail, 1), (UserType::Business, 3)]);
+}
+
+#[test]
+fn count_by_variant_works_on_references_and_friendly_ids() {
+    let ids = sample();
+    let counts = ids.iter().count_by_variant();
+    assert_eq!(counts.iter().collect::<Vec<_>>(), [(UserType::Retail, &1), (UserType::Business, &3)]);
+
+    let friendly: Vec<UserFriendlyUuid<UserType>> = ids.iter().copied().map(Into::into).collect();
+    assert_eq!(friendly.iter().count_by_variant(), counts);
+    assert!(Vec::<TypedUuid<UserType>>::new().into_iter().count_by_variant().is_empty());
+}
+
+#[test]
+fn filter_variant_keeps_matching_ids() {
+    let ids = sample();
+    let business: Vec<_> = ids.iter().filter_variant(UserType::Business).collect();
+    assert_eq!(business, [&ids[0], &ids[2], &ids[3]]);
+    assert_eq!(ids.into_iter().filter_variant(UserType::Organization).count(), 0);
+}
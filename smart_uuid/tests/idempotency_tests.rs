@@ -0,0 +1,93 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use smart_uuid::test::MockClock;
+use smart_uuid::{IdempotencyKey, TypedUuidError, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum KeyType {
+    #[uuid_type(prefix = "idem_pay")]
+    Payment,
+    #[uuid_type(prefix = "idem_refund")]
+    Refund,
+}
+
+fn at(secs: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+#[test]
+fn formats_and_parses_with_and_without_binding() {
+    let unbound = IdempotencyKey::new_at(KeyType::Payment, at(1_700_000_000), None);
+    let s = unbound.to_string();
+    assert!(s.starts_with("idem_pay_") && s.ends_with(".1700000000"), "{}", s);
+    assert_eq!(s.parse::<IdempotencyKey<KeyType>>().unwrap(), unbound);
+
+    let bound = IdempotencyKey::new_at(KeyType::Refund, at(1_700_000_000), Some(b"{\"amount\":100}"));
+    let s = bound.to_string();
+    assert_eq!(s.rsplit('.').next().unwrap().len(), 16);
+    assert_eq!(s.parse::<IdempotencyKey<KeyType>>().unwrap(), bound);
+
+    let json = serde_json::to_string(&bound).unwrap();
+    assert_eq!(serde_json::from_str::<IdempotencyKey<KeyType>>(&json).unwrap(), bound);
+}
+
+#[test]
+fn expiry_and_request_binding() {
+    let key = IdempotencyKey::new_at(KeyType::Payment, at(1_000), Some(b"body"));
+    assert_eq!(key.expires_at(), at(1_000));
+    assert!(!key.is_expired_at(at(999)));
+    assert!(key.is_expired_at(at(1_000)));
+
+    assert!(key.is_bound());
+    assert!(key.matches_request(b"body"));
+    assert!(!key.matches_request(b"other body"));
+
+    let unbound = IdempotencyKey::new(KeyType::Payment, Duration::from_secs(60));
+    assert!(!unbound.is_expired());
+    assert!(unbound.matches_request(b"anything"));
+
+    let bound = IdempotencyKey::bound_to(KeyType::Payment, Duration::from_secs(60), b"body");
+    assert!(bound.matches_request(b"body") && !bound.is_expired());
+}
+
+#[test]
+fn rejects_malformed_keys() {
+    let key = IdempotencyKey::new_at(KeyType::Payment, at(1_000), Some(b"body")).to_string();
+    let id = key.split('.').next().unwrap();
+
+    for bad in [
+        id.to_string(),
+        format!("{}.", id),
+        format!("{}.+1000", id),
+        format!("{}.1000.abc", id),
+        format!("{}.1000.0123456789abcdef.1", id),
+        format!("{}.{}", id, u64::MAX),
+        "no separator".to_string(),
+    ] {
+        let err = bad.parse::<IdempotencyKey<KeyType>>().unwrap_err();
+        assert!(err.is_invalid_format(), "{}: {:?}", bad, err);
+    }
+
+    // Far-future expiries parse as long as `SystemTime` can represent them
+    let far = format!("{}.{}", id, 1u64 << 40).parse::<IdempotencyKey<KeyType>>().unwrap();
+    assert!(!far.is_expired());
+
+    let wrong_type = key.replacen("idem_pay", "idem_other", 1);
+    assert!(matches!(
+        wrong_type.parse::<IdempotencyKey<KeyType>>(),
+        Err(TypedUuidError::UnknownPrefix { .. })
+    ));
+}
+
+#[test]
+fn expiry_follows_the_clock() {
+    let clock = MockClock::new(at(1_700_000_000));
+    let key = IdempotencyKey::new_with_clock(KeyType::Payment, Duration::from_secs(60), &clock, None);
+    assert_eq!(key.expires_at(), at(1_700_000_060));
+    assert!(!key.is_expired_with_clock(&clock));
+
+    clock.advance(Duration::from_secs(59));
+    assert!(!key.is_expired_with_clock(&clock));
+    clock.advance(Duration::from_secs(1));
+    assert!(key.is_expired_with_clock(&clock));
+}
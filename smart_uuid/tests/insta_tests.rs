@@ -0,0 +1,64 @@
+//! Tests for the insta snapshot redaction helpers.
+//! Run with: cargo test -p smart_uuid --features insta
+
+#![cfg(feature = "insta")]
+
+use serde::Serialize;
+use smart_uuid::test::insta_settings;
+use smart_uuid::{TypedUuid, UserFriendlyUuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+#[derive(Serialize)]
+struct Response {
+    id: UserFriendlyUuid<UserType>,
+    owner: TypedUuid<UserType>,
+    members: Vec<UserFriendlyUuid<UserType>>,
+    name: &'static str,
+    note: &'static str,
+}
+
+fn response() -> Response {
+    Response {
+        id: TypedUuid::new(UserType::Organization).into(),
+        owner: TypedUuid::new(UserType::Retail),
+        members: vec![TypedUuid::new(UserType::Retail).into()],
+        name: "acme_corp",
+        note: "not_an-id",
+    }
+}
+
+#[test]
+fn redactions_replace_ids_at_any_depth() {
+    insta_settings().bind(|| {
+        insta::assert_json_snapshot!(response(), @r#"
+        {
+          "id": "org_[typed_uuid]",
+          "owner": "[typed_uuid]",
+          "members": [
+            "retail_[typed_uuid]"
+          ],
+          "name": "acme_corp",
+          "note": "not_an-id"
+        }
+        "#);
+    });
+}
+
+#[test]
+fn filters_replace_ids_in_text() {
+    let org = UserFriendlyUuid::from(TypedUuid::new(UserType::Organization));
+    let owner = TypedUuid::new(UserType::Retail);
+
+    insta_settings().bind(|| {
+        insta::assert_snapshot!(
+            format!("created {} owned by {}", org, owner),
+            @"created org_[typed_uuid] owned by [typed_uuid]"
+        );
+    });
+}
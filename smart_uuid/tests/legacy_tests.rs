@@ -0,0 +1,61 @@
+use smart_uuid::{LegacyIdMap, LegacyPrefixedId, TypedUuid, TypedUuidError, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum BillingType {
+    #[uuid_type(prefix = "cus")]
+    Customer,
+    #[uuid_type(prefix = "sub_sched")]
+    SubscriptionSchedule,
+}
+
+#[test]
+fn parses_stripe_style_ids() {
+    let legacy: LegacyPrefixedId<BillingType> = "cus_NffrFeUfNV2Hib".parse().unwrap();
+    assert_eq!(legacy.variant_type(), BillingType::Customer);
+    assert_eq!(legacy.random(), "NffrFeUfNV2Hib");
+    assert_eq!(legacy.to_string(), "cus_NffrFeUfNV2Hib");
+
+    let legacy = LegacyPrefixedId::<BillingType>::parse_str("sub_sched_1MowQVLkdIwHu7ix").unwrap();
+    assert_eq!(legacy.prefix(), "sub_sched");
+
+    let json = serde_json::to_string(&legacy).unwrap();
+    assert_eq!(json, "\"sub_sched_1MowQVLkdIwHu7ix\"");
+    assert_eq!(serde_json::from_str::<LegacyPrefixedId<BillingType>>(&json).unwrap(), legacy);
+}
+
+#[test]
+fn rejects_malformed_ids() {
+    let parse = LegacyPrefixedId::<BillingType>::parse_str;
+    assert!(parse("prod_NffrFeUfNV2Hib").unwrap_err().is_unknown_prefix());
+    for bad in ["NffrFeUfNV2Hib", "cus_", "cus_Nffr-FeUf", &format!("cus_{}", "a".repeat(65))] {
+        assert!(matches!(parse(bad), Err(TypedUuidError::InvalidFormat(_))), "{}", bad);
+    }
+}
+
+#[test]
+fn map_mints_stable_replacements() {
+    let customer = LegacyPrefixedId::<BillingType>::parse_str("cus_NffrFeUfNV2Hib").unwrap();
+    let mut map = LegacyIdMap::new();
+    assert!(map.is_empty());
+
+    let id = map.replace(&customer);
+    assert_eq!(id.variant_type(), BillingType::Customer);
+    assert_eq!(map.replace(&customer), id);
+    assert_eq!(map.get(&customer), Some(id));
+    assert_eq!(map.legacy_for(&id), Some(&customer));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn map_reloads_pairs_and_rejects_conflicts() {
+    let customer = LegacyPrefixedId::<BillingType>::parse_str("cus_NffrFeUfNV2Hib").unwrap();
+    let id = TypedUuid::new(BillingType::Customer);
+
+    let mut map = LegacyIdMap::new();
+    map.insert(customer.clone(), id).unwrap();
+    map.insert(customer.clone(), id).unwrap();
+    assert_eq!(map.replace(&customer), id);
+
+    assert!(map.insert(customer.clone(), TypedUuid::new(BillingType::Customer)).is_err());
+    assert!(map.insert(customer, TypedUuid::new(BillingType::SubscriptionSchedule)).is_err());
+}
@@ -0,0 +1,53 @@
+//! Tests for the Leptos router params.
+//! Run with: cargo test -p smart_uuid --features leptos
+
+#![cfg(feature = "leptos")]
+
+use leptos_router::params::{IntoParam, ParamsError};
+use smart_uuid::{TypedUuid, UserFriendlyUuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+/// Parses params the way a `#[derive(Params)]` struct with `id` and `parent` fields does.
+fn from_params(
+    id: Option<&str>,
+    parent: Option<&str>,
+) -> Result<(UserFriendlyUuid<UserType>, Option<TypedUuid<UserType>>), ParamsError> {
+    Ok((IntoParam::into_param(id, "id")?, IntoParam::into_param(parent, "parent")?))
+}
+
+#[test]
+fn route_params_parse_into_typed_ids() {
+    let id = UserFriendlyUuid::new(UserType::Organization);
+    let parent = TypedUuid::new(UserType::Retail);
+
+    let parsed = from_params(Some(&id.to_string()), Some(&parent.to_string())).unwrap();
+    assert_eq!(parsed, (id, Some(parent)));
+
+    let parsed = from_params(Some(&id.to_string()), None).unwrap();
+    assert_eq!(parsed, (id, None));
+}
+
+#[test]
+fn invalid_or_missing_params_are_rejected() {
+    assert_eq!(
+        from_params(None, None).unwrap_err(),
+        ParamsError::MissingParam("id".to_string())
+    );
+
+    let wrong_prefix = format!("retail_{}", TypedUuid::new(UserType::Organization));
+    for input in [wrong_prefix.as_str(), "org_not-a-uuid"] {
+        let err = from_params(Some(input), None).unwrap_err();
+        assert!(matches!(err, ParamsError::Params(_)), "{:?} should be rejected", input);
+    }
+
+    // The raw form is for `TypedUuid` params only
+    let raw = TypedUuid::new(UserType::Retail).to_string();
+    assert!(from_params(Some(&raw), None).is_err());
+    assert!(from_params(Some(&UserFriendlyUuid::new(UserType::Retail).to_string()), Some("retail_x")).is_err());
+}
@@ -0,0 +1,76 @@
+//! Tests for the `metrics` feature. Run with: cargo test -p smart_uuid --features metrics
+
+#![cfg(feature = "metrics")]
+
+use std::sync::Mutex;
+
+use metrics::{Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+use smart_uuid::{TypedUuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+type Labels = Vec<(String, String)>;
+
+/// Remembers the name and labels of every counter registration.
+#[derive(Default)]
+struct CapturingRecorder {
+    counters: Mutex<Vec<(String, Labels)>>,
+}
+
+impl Recorder for CapturingRecorder {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        let labels = key.labels().map(|l| (l.key().to_string(), l.value().to_string())).collect();
+        self.counters.lock().unwrap().push((key.name().to_string(), labels));
+        Counter::noop()
+    }
+
+    fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+        Histogram::noop()
+    }
+}
+
+fn labels(pairs: &[(&str, &str)]) -> Labels {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+#[test]
+fn record_generated_labels_by_variant() {
+    let recorder = CapturingRecorder::default();
+    metrics::with_local_recorder(&recorder, || {
+        smart_uuid::metrics::record_generated(UserType::Organization);
+    });
+
+    let counters = recorder.counters.lock().unwrap();
+    assert_eq!(
+        *counters,
+        vec![(
+            "smart_uuid_generated_total".to_string(),
+            labels(&[("type", "UserType"), ("variant", "org")]),
+        )]
+    );
+}
+
+#[test]
+fn record_parse_error_labels_by_kind_only() {
+    let err = "retail_not-a-uuid".parse::<TypedUuid<UserType>>().unwrap_err();
+
+    let recorder = CapturingRecorder::default();
+    metrics::with_local_recorder(&recorder, || smart_uuid::metrics::record_parse_error(&err));
+
+    let counters = recorder.counters.lock().unwrap();
+    assert_eq!(counters[0].0, "smart_uuid_parse_errors_total");
+    assert_eq!(counters[0].1, labels(&[("kind", "parse")]));
+}
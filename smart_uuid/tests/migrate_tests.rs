@@ -0,0 +1,218 @@
+//! Tests for schema-versioned IDs and their migrators.
+
+use smart_uuid::migrate::retag;
+use smart_uuid::{Layout, Migrator, RetagError, TypedUuid, TypedUuidError, UserFriendlyUuid, Uuid, UuidType};
+
+/// Version 1, and the IDs from before versioning (version 0), had `Business` at 0 and
+/// `Retail` at 1; version 2 swapped them.
+struct AccountTypeMigrator;
+
+impl Migrator<AccountType> for AccountTypeMigrator {
+    fn current_version(&self) -> u8 {
+        2
+    }
+
+    fn migrate(&self, from_version: u8, uuid: Uuid) -> Result<Uuid, TypedUuidError> {
+        assert!(from_version <= 1);
+        let mut bytes = *uuid.as_bytes();
+        bytes[0] = match bytes[0] {
+            0 => 1,
+            1 => 0,
+            other => other,
+        };
+        Ok(Uuid::from_bytes(bytes))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(migrator = AccountTypeMigrator)]
+enum AccountType {
+    Retail,
+    Business,
+}
+
+fn with_header(discriminant: u8, version: u8) -> Uuid {
+    let mut bytes = *Uuid::new_v4().as_bytes();
+    bytes[0] = discriminant;
+    bytes[1] = version;
+    Uuid::from_bytes(bytes)
+}
+
+#[test]
+fn new_ids_carry_current_version() {
+    let id = TypedUuid::new(AccountType::Business);
+    assert_eq!(id.as_bytes()[0], 1);
+    assert_eq!(id.as_bytes()[1], 2);
+    assert_eq!(TypedUuid::<AccountType>::from_uuid(id.into_uuid()).unwrap(), id);
+
+    assert_eq!(TypedUuid::new_v4_compatible(AccountType::Retail).as_bytes()[1], 2);
+    assert_eq!(TypedUuid::new_opaque(AccountType::Retail).as_bytes()[1], 2);
+}
+
+#[test]
+fn opaque_ids_round_trip() {
+    // About one in 64 opaque IDs has the bits of an unmarked v8 ID by chance
+    for _ in 0..20_000 {
+        let id = TypedUuid::new_opaque(AccountType::Business);
+        assert_eq!(id.as_bytes()[1], 2);
+        assert_eq!(TypedUuid::<AccountType>::from_uuid(id.into_uuid()).unwrap(), id);
+        assert_eq!(id.to_string().parse::<TypedUuid<AccountType>>().unwrap(), id);
+    }
+}
+
+#[test]
+fn old_ids_are_migrated_on_read() {
+    let stored = with_header(0, 1);
+
+    let id = TypedUuid::<AccountType>::from_uuid(stored).unwrap();
+    assert_eq!(id.variant_type(), AccountType::Business);
+    assert_eq!(id.as_bytes()[1], 2);
+    assert_eq!(id.as_bytes()[2..], stored.as_bytes()[2..]);
+
+    let parsed: UserFriendlyUuid<AccountType> = format!("business_{}", stored).parse().unwrap();
+    assert_eq!(parsed.into_typed_uuid(), id);
+}
+
+#[test]
+fn new_v8_ids_are_marked_as_versioned() {
+    let id = TypedUuid::new(AccountType::Retail);
+    assert_eq!(id.as_bytes()[8] & 0xE0, 0xC0);
+    assert_eq!(id.layout(), Layout::V8);
+    assert_eq!(id.as_uuid().get_version_num(), 8);
+
+    // Recasting into a versioned enum marks the ID too
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+    enum Plain {
+        Only,
+    }
+    let plain = TypedUuid::new(Plain::Only);
+    assert_eq!(plain.as_bytes()[8] & 0xC0, 0x80);
+    let recast = plain.map_type(|_| AccountType::Business);
+    assert_eq!(TypedUuid::<AccountType>::from_uuid(recast.into_uuid()).unwrap(), recast);
+}
+
+#[test]
+fn v8_ids_from_before_opting_in_are_read_as_version_zero() {
+    // Byte 1 was random before the opt-in, here newer than the current version
+    let mut bytes = [0x5Au8; 16];
+    bytes[0] = 0;
+    bytes[1] = 7;
+    let stored = Uuid::new_v8(bytes);
+
+    let id = TypedUuid::<AccountType>::from_uuid(stored).unwrap();
+    assert_eq!(id.variant_type(), AccountType::Business);
+    assert_eq!(id.as_bytes()[1], 2);
+    assert_eq!(id.as_bytes()[8] & 0xE0, 0xC0);
+
+    // The migrated ID is marked, so reading it again does not migrate it twice
+    assert_eq!(TypedUuid::<AccountType>::from_uuid(id.into_uuid()).unwrap(), id);
+}
+
+#[test]
+fn ids_the_migrator_leaves_alone_are_kept_as_stored() {
+    struct Identity;
+
+    impl Migrator<LedgerType> for Identity {
+        fn current_version(&self) -> u8 {
+            1
+        }
+
+        fn migrate(&self, _from_version: u8, uuid: Uuid) -> Result<Uuid, TypedUuidError> {
+            Ok(uuid)
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+    #[uuid_type(migrator = Identity)]
+    enum LedgerType {
+        Entry,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+    enum Unversioned {
+        Entry,
+    }
+
+    for stored in [TypedUuid::new(Unversioned::Entry), TypedUuid::new_ordered(Unversioned::Entry)] {
+        let id = TypedUuid::<LedgerType>::from_uuid(stored.into_uuid()).unwrap();
+        assert_eq!(id.as_bytes(), stored.as_bytes());
+        assert_eq!(id.shard_for(64), stored.shard_for(64));
+        assert_eq!(id.to_string().parse::<TypedUuid<LedgerType>>().unwrap(), id);
+    }
+}
+
+#[test]
+fn time_ordered_ids_from_before_opting_in_are_read_as_version_zero() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+    enum Unversioned {
+        Business,
+        Retail,
+    }
+
+    let stored = TypedUuid::new_ordered(Unversioned::Business);
+    assert_eq!(stored.as_bytes()[1], 0);
+
+    let id = TypedUuid::<AccountType>::from_uuid(stored.into_uuid()).unwrap();
+    assert_eq!(id.variant_type(), AccountType::Business);
+    assert_eq!(id.as_bytes()[1], 2);
+    assert_eq!(id.layout(), Layout::TimeOrdered);
+    assert_eq!(id.timestamp_millis(), stored.timestamp_millis());
+    assert_eq!(TypedUuid::<AccountType>::from_uuid(id.into_uuid()).unwrap(), id);
+}
+
+#[test]
+fn newer_versions_are_rejected() {
+    let err = TypedUuid::<AccountType>::from_uuid(with_header(0, 3)).unwrap_err();
+    assert!(matches!(err, TypedUuidError::UnsupportedSchemaVersion { found: 3, current: 2, .. }));
+}
+
+#[test]
+fn unversioned_types_ignore_byte_one() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+    enum Plain {
+        Only,
+    }
+
+    assert!(Plain::migrator().is_none());
+    assert!(TypedUuid::<Plain>::from_uuid(with_header(0, 200)).is_ok());
+}
+
+fn swap_retail_business(discriminant: u8) -> Option<u8> {
+    match discriminant {
+        0 => Some(1),
+        1 => Some(0),
+        _ => None,
+    }
+}
+
+#[test]
+fn retag_returns_every_pair_in_order() {
+    let ids = [with_header(0, 1), with_header(1, 1), with_header(0, 1)];
+
+    let pairs = retag(ids, swap_retail_business).unwrap();
+    assert_eq!(pairs.len(), 3);
+    for ((old, new), id) in pairs.iter().zip(ids) {
+        assert_eq!(*old, id);
+        assert_eq!(new.as_bytes()[0], 1 - id.as_bytes()[0]);
+        assert_eq!(new.as_bytes()[1..], id.as_bytes()[1..]);
+    }
+}
+
+#[test]
+fn retag_rejects_unmapped_discriminants() {
+    let stray = with_header(5, 1);
+    let err = retag([with_header(0, 1), stray], swap_retail_business).unwrap_err();
+    assert_eq!(err, RetagError::Unmapped { uuid: stray, discriminant: 5 });
+}
+
+#[test]
+fn retag_rejects_collisions() {
+    let a = with_header(0, 1);
+    let mut bytes = *a.as_bytes();
+    bytes[0] = 1;
+    let b = Uuid::from_bytes(bytes);
+
+    // Merging both variants into one makes the two IDs identical
+    let err = retag([a, b], |_| Some(0)).unwrap_err();
+    assert_eq!(err, RetagError::Collision { first: a, second: b, retagged: a });
+}
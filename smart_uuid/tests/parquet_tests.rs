@@ -0,0 +1,105 @@
+//! Tests for the `parquet` feature. Run with: cargo test -p smart_uuid --features parquet
+
+#![cfg(feature = "parquet")]
+
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use parquet::basic::{LogicalType, Repetition, Type as PhysicalType};
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::types::Type;
+use smart_uuid::parquet::{read_column, uuid_column_type, write_column};
+use smart_uuid::{TypedUuid, TypedUuidError, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum DocumentType {
+    #[uuid_type(prefix = "inv")]
+    Invoice = 7,
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("smart_uuid_{}_{}.parquet", name, std::process::id()))
+}
+
+fn write_file(name: &str, ids: &[TypedUuid<UserType>]) -> PathBuf {
+    let schema = Type::group_type_builder("users")
+        .with_fields(vec![Arc::new(uuid_column_type("user_id"))])
+        .build()
+        .unwrap();
+
+    let path = temp_path(name);
+    let props = Arc::new(WriterProperties::builder().build());
+    let mut writer = SerializedFileWriter::new(File::create(&path).unwrap(), Arc::new(schema), props).unwrap();
+    let mut row_group = writer.next_row_group().unwrap();
+    write_column(&mut row_group, ids).unwrap();
+    row_group.close().unwrap();
+    writer.close().unwrap();
+    path
+}
+
+#[test]
+fn columns_use_the_uuid_logical_type() {
+    let column = uuid_column_type("user_id");
+    let info = column.get_basic_info();
+    assert_eq!(info.repetition(), Repetition::REQUIRED);
+    assert_eq!(info.logical_type(), Some(LogicalType::Uuid));
+    assert_eq!(column.get_physical_type(), PhysicalType::FIXED_LEN_BYTE_ARRAY);
+}
+
+#[test]
+fn round_trips_a_column() {
+    let ids: Vec<_> = (0..100)
+        .map(|i| TypedUuid::new(if i % 3 == 0 { UserType::Organization } else { UserType::Retail }))
+        .collect();
+    let path = write_file("round_trip", &ids);
+
+    let reader = SerializedFileReader::new(File::open(&path).unwrap()).unwrap();
+    let row_group = reader.get_row_group(0).unwrap();
+    assert_eq!(read_column::<UserType>(row_group.as_ref(), 0).unwrap(), ids);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn reading_validates_discriminants() {
+    let path = write_file("validate", &[TypedUuid::new(UserType::Organization)]);
+
+    let reader = SerializedFileReader::new(File::open(&path).unwrap()).unwrap();
+    let row_group = reader.get_row_group(0).unwrap();
+    let err = read_column::<DocumentType>(row_group.as_ref(), 0).unwrap_err();
+    let ParquetError::External(source) = err else { panic!("unexpected error {err}") };
+    assert!(source.downcast_ref::<TypedUuidError>().unwrap().is_invalid_discriminant());
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn writing_rejects_non_uuid_columns() {
+    let schema = Type::group_type_builder("users")
+        .with_fields(vec![Arc::new(
+            Type::primitive_type_builder("user_id", PhysicalType::FIXED_LEN_BYTE_ARRAY)
+                .with_repetition(Repetition::REQUIRED)
+                .with_length(16)
+                .build()
+                .unwrap(),
+        )])
+        .build()
+        .unwrap();
+
+    let props = Arc::new(WriterProperties::builder().build());
+    let mut writer = SerializedFileWriter::new(Vec::new(), Arc::new(schema), props).unwrap();
+    let mut row_group = writer.next_row_group().unwrap();
+    let err = write_column(&mut row_group, &[TypedUuid::new(UserType::Retail)]).unwrap_err();
+    assert!(err.to_string().contains("'user_id' is not a required FIXED_LEN_BYTE_ARRAY(16) UUID column"));
+}
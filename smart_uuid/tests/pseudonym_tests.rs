@@ -0,0 +1,42 @@
+//! Tests for keyed pseudonymization.
+//! Run with: cargo test -p smart_uuid --features pseudonymize
+
+#![cfg(feature = "pseudonymize")]
+
+use smart_uuid::{TypedUuid, Uuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    Business,
+}
+
+#[test]
+fn pseudonyms_are_stable_per_key() {
+    let id = TypedUuid::<UserType>::from_uuid(Uuid::parse_str("01123456-789a-8bcd-8ef0-123456789abc").unwrap()).unwrap();
+
+    let pseudonym = id.pseudonymize(b"export-key");
+    // Golden value: exports from different versions must keep joining
+    assert_eq!(pseudonym.to_string(), "01e4d87e-4f6a-881e-8150-94bcc011c92d");
+    assert_eq!(pseudonym.variant_type(), UserType::Business);
+    assert_eq!(pseudonym.as_uuid().get_version_num(), 8);
+    assert_eq!(pseudonym.timestamp_millis(), None);
+
+    assert_eq!(id.pseudonymize(b"export-key"), pseudonym);
+    assert_ne!(id.pseudonymize(b"other-key"), pseudonym);
+    assert_ne!(TypedUuid::new(UserType::Business).pseudonymize(b"export-key"), pseudonym);
+}
+
+#[test]
+fn batch_matches_single() {
+    let ids: Vec<_> = (0..10)
+        .map(|i| TypedUuid::new(if i % 2 == 0 { UserType::Retail } else { UserType::Business }))
+        .collect();
+    let pseudonyms = TypedUuid::pseudonymize_all(&ids, b"export-key");
+
+    assert_eq!(pseudonyms.len(), ids.len());
+    for (id, pseudonym) in ids.iter().zip(&pseudonyms) {
+        assert_eq!(*pseudonym, id.pseudonymize(b"export-key"));
+        assert_eq!(pseudonym.variant_type(), id.variant_type());
+    }
+}
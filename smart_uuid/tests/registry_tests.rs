@@ -0,0 +1,341 @@
+//! Tests for resolving IDs across multiple UuidType enums at runtime.
+
+use smart_uuid::{AnyTypedUuid, RegistryError, TypedUuid, TypedUuidError, UserFriendlyUuid, UuidRegistry, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    Business,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum DocumentType {
+    #[uuid_type(prefix = "inv")]
+    Invoice,
+    #[uuid_type(prefix = "rcpt")]
+    Receipt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum TenantType {
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+fn registry() -> UuidRegistry {
+    let mut registry = UuidRegistry::new();
+    registry
+        .register::<UserType>()
+        .unwrap()
+        .register::<DocumentType>()
+        .unwrap();
+    registry
+}
+
+#[test]
+fn registry_resolves_prefix_to_the_right_enum() {
+    let registry = registry();
+    let user = UserFriendlyUuid::new(UserType::Organization);
+    let doc = UserFriendlyUuid::new(DocumentType::Receipt);
+
+    let parsed_user = registry.parse(&user.to_string()).unwrap();
+    let parsed_doc = registry.parse(&doc.to_string()).unwrap();
+
+    assert!(parsed_user.is::<UserType>());
+    assert_eq!(parsed_user.downcast::<UserType>(), Some(user.into_typed_uuid()));
+    assert_eq!(parsed_user.downcast::<DocumentType>(), None);
+    assert_eq!(parsed_doc.downcast::<DocumentType>(), Some(doc.into_typed_uuid()));
+    assert_eq!(parsed_doc.to_string(), doc.to_string());
+}
+
+#[test]
+fn registry_rejects_unknown_prefix() {
+    let registry = registry();
+    let typed = TypedUuid::new(UserType::Retail);
+
+    let result = registry.parse(&format!("nope_{}", typed));
+    assert!(matches!(result, Err(TypedUuidError::UnknownPrefix { .. })));
+
+    // Every registered prefix is listed, in registration and discriminant order
+    let err = result.unwrap_err();
+    assert!(matches!(&err, TypedUuidError::UnknownPrefix { expected, .. } if **expected == ["retail", "business", "org", "inv", "rcpt"][..]));
+    assert!(err.to_string().ends_with("expected one of: retail, business, org, inv, rcpt"));
+}
+
+#[test]
+fn registry_validates_with_the_owning_enum() {
+    let registry = registry();
+    // UserType UUIDs presented with DocumentType prefixes are checked against DocumentType
+    let org = TypedUuid::new(UserType::Organization);
+    let retail = TypedUuid::new(UserType::Retail);
+
+    let result = registry.parse(&format!("inv_{}", org));
+    assert!(matches!(result, Err(TypedUuidError::InvalidDiscriminant { found: 2, .. })));
+    let result = registry.parse(&format!("rcpt_{}", retail));
+    assert!(matches!(result, Err(TypedUuidError::UnknownPrefix { .. })));
+}
+
+#[test]
+fn registry_generates_by_prefix() {
+    let registry = registry();
+
+    let id = registry.generate("rcpt").unwrap();
+    assert_eq!(id.downcast::<DocumentType>().unwrap().variant_type(), DocumentType::Receipt);
+    assert_eq!(registry.parse(&id.to_string()).unwrap(), id);
+
+    assert!(registry.generate("nope").is_none());
+}
+
+#[test]
+fn registry_rejects_prefix_collisions() {
+    let mut registry = registry();
+
+    let result = registry.register::<TenantType>();
+    assert!(matches!(result, Err(RegistryError::DuplicatePrefix { prefix: "org", .. })));
+
+    // Re-registering the same type is fine
+    assert!(registry.register::<UserType>().is_ok());
+}
+
+#[test]
+fn any_typed_uuid_from_typed() {
+    let typed = TypedUuid::new(DocumentType::Invoice);
+    let any = AnyTypedUuid::from(typed);
+
+    assert_eq!(any.prefix(), "inv");
+    assert_eq!(any.as_uuid(), typed.as_uuid());
+    assert_eq!(any.downcast::<DocumentType>(), Some(typed));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(id_space = "ledger", discriminant = "hash")]
+enum AccountType {
+    Checking,
+    Savings,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(id_space = "ledger", discriminant = "hash")]
+enum EntryType {
+    Debit,
+    Credit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(id_space = "ledger")]
+enum TransferType {
+    Wire,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(id_space = "ledger")]
+enum PayoutType {
+    Payout,
+}
+
+#[test]
+fn verify_ignores_enums_outside_a_shared_id_space() {
+    // UserType and DocumentType both start at discriminant 0, but are stored apart
+    assert_eq!(UserType::id_space(), None);
+    assert!(registry().verify().is_ok());
+}
+
+#[test]
+fn verify_detects_discriminant_overlap_within_an_id_space() {
+    let mut registry = registry();
+    registry.register::<AccountType>().unwrap().register::<EntryType>().unwrap();
+    assert_eq!(AccountType::id_space(), Some("ledger"));
+    assert!(registry.verify().is_ok());
+
+    // Positional discriminants both start at 0, like UserType's; only the ledger enums clash
+    registry.register::<TransferType>().unwrap();
+    assert!(registry.verify().is_ok());
+    registry.register::<PayoutType>().unwrap();
+    let result = registry.verify();
+    assert!(matches!(result, Err(RegistryError::DiscriminantOverlap { id_space: "ledger", discriminant: 0, .. })));
+}
+
+#[test]
+fn any_typed_uuid_exposes_type_info() {
+    let any = AnyTypedUuid::from(TypedUuid::new(UserType::Organization));
+
+    assert_eq!(any.enum_name(), "UserType");
+    assert_eq!(any.variant_name(), "Organization");
+    assert_eq!(any.prefix(), "org");
+    assert_eq!(any.type_info().discriminant(), 2);
+    assert!(std::ptr::eq(any.type_info(), UserType::Organization.type_info()));
+}
+
+#[test]
+fn any_typed_uuid_mixes_enums_in_one_collection() {
+    let user = TypedUuid::new(UserType::Retail);
+    let doc = TypedUuid::new(DocumentType::Invoice);
+    let log: Vec<AnyTypedUuid> = vec![user.into(), doc.into()];
+
+    let users: Vec<_> = log.iter().filter_map(|id| id.downcast::<UserType>()).collect();
+    let docs: Vec<_> = log.iter().filter_map(|id| id.downcast::<DocumentType>()).collect();
+    assert_eq!(users, vec![user]);
+    assert_eq!(docs, vec![doc]);
+
+    // Same raw UUID bytes in different enums are different IDs
+    let same_bytes = AnyTypedUuid::from(TypedUuid::<DocumentType>::from_uuid(user.into_uuid()).unwrap());
+    assert_ne!(log[0], same_bytes);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ManualType {
+    Widget,
+}
+
+impl UuidType for ManualType {
+    fn discriminant(&self) -> u8 {
+        0
+    }
+
+    fn from_discriminant(value: u8) -> Option<Self> {
+        (value == 0).then_some(Self::Widget)
+    }
+
+    fn prefix(&self) -> &'static str {
+        "wdg"
+    }
+}
+
+#[test]
+fn manual_impl_gets_cached_type_info() {
+    let info = ManualType::Widget.type_info();
+
+    assert_eq!(info.enum_name(), "ManualType");
+    assert_eq!(info.variant_name(), "Widget");
+    assert_eq!(info.prefix(), "wdg");
+    assert!(std::ptr::eq(info, ManualType::Widget.type_info()));
+    assert_eq!(ManualType::prefixes(), &["wdg"]);
+}
+
+// ============================================================================
+// define_uuid_union! Tests
+// ============================================================================
+
+smart_uuid::define_uuid_union! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AnyEntityId {
+        User(UserType),
+        Doc(DocumentType),
+    }
+}
+
+#[test]
+fn uuid_union_dispatches_on_prefix() {
+    let user = UserFriendlyUuid::new(UserType::Business);
+    let doc = UserFriendlyUuid::new(DocumentType::Invoice);
+
+    let parsed_user: AnyEntityId = user.to_string().parse().unwrap();
+    let parsed_doc: AnyEntityId = doc.to_string().parse().unwrap();
+
+    assert_eq!(parsed_user, AnyEntityId::User(user.into_typed_uuid()));
+    assert_eq!(parsed_doc, AnyEntityId::Doc(doc.into_typed_uuid()));
+    assert_eq!(parsed_doc.prefix(), "inv");
+    assert_eq!(parsed_doc.to_string(), doc.to_string());
+}
+
+#[test]
+fn uuid_union_rejects_unknown_prefix() {
+    let typed = TypedUuid::new(UserType::Retail);
+
+    let result = format!("nope_{}", typed).parse::<AnyEntityId>();
+    assert!(matches!(result, Err(TypedUuidError::UnknownPrefix { type_name: "AnyEntityId", .. })));
+}
+
+#[test]
+fn uuid_union_suggests_prefix_from_any_member() {
+    let typed = TypedUuid::new(DocumentType::Receipt);
+
+    let result = format!("rcp_{}", typed).parse::<AnyEntityId>();
+    assert!(matches!(result, Err(TypedUuidError::UnknownPrefix { did_you_mean: Some("rcpt"), .. })));
+}
+
+#[test]
+fn uuid_union_error_lists_every_arm_prefix() {
+    assert_eq!(AnyEntityId::prefixes(), &["retail", "business", "org", "inv", "rcpt"][..]);
+
+    let typed = TypedUuid::new(UserType::Retail);
+    let err = format!("nope_{}", typed).parse::<AnyEntityId>().unwrap_err();
+    assert!(matches!(&err, TypedUuidError::UnknownPrefix { expected, .. } if *expected == AnyEntityId::prefixes()));
+    assert!(err.to_string().ends_with("expected one of: retail, business, org, inv, rcpt"));
+}
+
+#[test]
+fn uuid_union_deserializes_polymorphic_fields() {
+    #[derive(Debug, serde::Deserialize)]
+    struct Webhook {
+        resource_id: AnyEntityId,
+    }
+
+    let doc = TypedUuid::new(DocumentType::Invoice);
+    let json = format!(r#"{{"resource_id": "{}"}}"#, UserFriendlyUuid::from(doc));
+    let webhook: Webhook = serde_json::from_str(&json).unwrap();
+    assert_eq!(webhook.resource_id, AnyEntityId::Doc(doc));
+
+    let json = format!(r#"{{"resource_id": "tenant_{}"}}"#, doc);
+    let err = serde_json::from_str::<Webhook>(&json).unwrap_err();
+    assert!(err.to_string().contains("expected one of: retail, business, org, inv, rcpt"));
+}
+
+smart_uuid::define_uuid_union! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum OverlappingId {
+        User(UserType),
+        Tenant(TenantType),
+    }
+}
+
+#[test]
+fn uuid_union_tries_arms_in_order() {
+    assert_eq!(OverlappingId::prefixes(), &["retail", "business", "org"][..]);
+
+    let user_org = UserFriendlyUuid::new(UserType::Organization);
+    let parsed: OverlappingId = user_org.to_string().parse().unwrap();
+    assert_eq!(parsed, OverlappingId::User(user_org.into_typed_uuid()));
+}
+
+#[test]
+fn uuid_union_serde_roundtrip() {
+    let id = AnyEntityId::from(TypedUuid::new(DocumentType::Receipt));
+
+    let json = serde_json::to_string(&id).unwrap();
+    assert!(json.starts_with("\"rcpt_"));
+
+    let back: AnyEntityId = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, id);
+}
+
+// ============================================================================
+// UuidTypeDyn Tests
+// ============================================================================
+
+#[test]
+fn uuid_type_dyn_describes_kinds_at_runtime() {
+    use smart_uuid::UuidTypeDyn;
+
+    let kinds: Vec<Box<dyn UuidTypeDyn>> = vec![
+        Box::new(UserType::Retail),
+        Box::new(DocumentType::Invoice),
+    ];
+
+    let names: Vec<_> = kinds.iter().map(|k| k.enum_name()).collect();
+    assert_eq!(names, vec!["UserType", "DocumentType"]);
+
+    let user_kind = &kinds[0];
+    let prefixes: Vec<_> = user_kind.variant_infos().iter().map(|i| i.prefix()).collect();
+    assert_eq!(prefixes, vec!["retail", "business", "org"]);
+    assert_eq!(user_kind.prefix_for(2), Some("org"));
+    assert_eq!(user_kind.discriminant_for("business"), Some(1));
+    assert_eq!(user_kind.discriminant_for("inv"), None);
+
+    let doc = UserFriendlyUuid::new(DocumentType::Receipt);
+    let parsed = kinds[1].parse_any(&doc.to_string()).unwrap();
+    assert_eq!(parsed.downcast::<DocumentType>(), Some(doc.into_typed_uuid()));
+    assert!(user_kind.parse_any(&doc.to_string()).is_err());
+}
@@ -0,0 +1,60 @@
+//! Tests for the rusqlite SQL functions.
+//! Run with: cargo test -p smart_uuid --features rusqlite
+
+#![cfg(feature = "rusqlite")]
+
+use rusqlite::Connection;
+use smart_uuid::{TypedUuid, UserFriendlyUuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+fn connection() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    smart_uuid::rusqlite::register_functions::<UserType>(&conn).unwrap();
+    conn
+}
+
+#[test]
+fn functions_validate_and_extract_prefixes() {
+    let conn = connection();
+    let id = UserFriendlyUuid::new(UserType::Organization).to_string();
+
+    let (valid, prefix): (bool, Option<String>) = conn
+        .query_row("SELECT smart_uuid_valid(?1), smart_uuid_prefix(?1)", [&id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap();
+    assert!(valid);
+    assert_eq!(prefix.as_deref(), Some("org"));
+
+    let wrong = format!("retail_{}", TypedUuid::new(UserType::Organization));
+    for input in [wrong.as_str(), "org_not-a-uuid", ""] {
+        let (valid, prefix): (bool, Option<String>) = conn
+            .query_row("SELECT smart_uuid_valid(?1), smart_uuid_prefix(?1)", [input], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert!(!valid, "{:?} should be invalid", input);
+        assert_eq!(prefix, None);
+    }
+
+    let (valid, prefix): (Option<bool>, Option<String>) = conn
+        .query_row("SELECT smart_uuid_valid(NULL), smart_uuid_prefix(42)", [], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap();
+    assert_eq!((valid, prefix), (None, None));
+}
+
+#[test]
+fn check_constraint_rejects_invalid_ids() {
+    let conn = connection();
+    conn.execute_batch("CREATE TABLE users (id TEXT PRIMARY KEY CHECK (smart_uuid_valid(id)))").unwrap();
+
+    let id = UserFriendlyUuid::new(UserType::Retail).to_string();
+    conn.execute("INSERT INTO users (id) VALUES (?1)", [&id]).unwrap();
+
+    let err = conn.execute("INSERT INTO users (id) VALUES ('retail_nope')", []).unwrap_err();
+    assert!(err.to_string().contains("CHECK constraint failed"));
+}
@@ -0,0 +1,90 @@
+//! Tests for the `serde_with` feature. Run with: cargo test -p smart_uuid --features serde_with
+
+#![cfg(feature = "serde_with")]
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use smart_uuid::serde_with::{Base32, FriendlyString, RawBytes};
+use smart_uuid::{TypedUuid, Uuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+#[serde_as]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Order {
+    #[serde_as(as = "FriendlyString")]
+    customer: TypedUuid<UserType>,
+    #[serde_as(as = "Option<RawBytes>")]
+    referrer: Option<TypedUuid<UserType>>,
+    #[serde_as(as = "Vec<Base32>")]
+    related: Vec<TypedUuid<UserType>>,
+    #[serde_as(as = "HashMap<FriendlyString, _>")]
+    notes: HashMap<TypedUuid<UserType>, String>,
+}
+
+fn id(variant: UserType, uuid: &str) -> TypedUuid<UserType> {
+    let typed = TypedUuid::from_uuid(Uuid::parse_str(uuid).unwrap()).unwrap();
+    assert_eq!(typed.variant_type(), variant);
+    typed
+}
+
+#[test]
+fn adapters_compose_with_containers() {
+    let customer = TypedUuid::new(UserType::Organization);
+    let order = Order {
+        customer,
+        referrer: Some(TypedUuid::new(UserType::Retail)),
+        related: vec![TypedUuid::new(UserType::Retail), TypedUuid::new(UserType::Organization)],
+        notes: HashMap::from([(customer, "vip".to_string())]),
+    };
+
+    let json = serde_json::to_value(&order).unwrap();
+    assert_eq!(json["customer"], format!("org_{}", customer));
+    assert_eq!(json["referrer"].as_array().unwrap().len(), 16);
+    assert_eq!(json["related"][0].as_str().unwrap().len(), 26);
+    assert_eq!(json["notes"][format!("org_{}", customer)], "vip");
+
+    let back: Order = serde_json::from_value(json).unwrap();
+    assert_eq!(back, order);
+}
+
+#[test]
+fn base32_is_crockford_and_case_insensitive() {
+    #[serde_as]
+    #[derive(Serialize, Deserialize)]
+    struct Wrapper(#[serde_as(as = "Base32")] TypedUuid<UserType>);
+
+    let typed = id(UserType::Organization, "01ffffff-ffff-8fff-bfff-ffffffffffff");
+    let json = serde_json::to_string(&Wrapper(typed)).unwrap();
+    assert_eq!(json, "\"01zzzzzzzzhzzvzzzzzzzzzzzz\"");
+
+    let upper: Wrapper = serde_json::from_str(&json.to_uppercase()).unwrap();
+    assert_eq!(upper.0, typed);
+
+    // Overflowing and malformed values are rejected
+    assert!(serde_json::from_str::<Wrapper>("\"81zzzzzzzzhzzvzzzzzzzzzzzz\"").is_err());
+    assert!(serde_json::from_str::<Wrapper>("\"01zzzzzzzzhzzvzzzzzzzzzzzu\"").is_err());
+    assert!(serde_json::from_str::<Wrapper>("\"01zz\"").is_err());
+}
+
+#[test]
+fn adapters_validate_the_discriminant() {
+    #[serde_as]
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Bytes(#[serde_as(as = "RawBytes")] TypedUuid<UserType>);
+
+    let mut raw = vec![0u8; 16];
+    raw[0] = 0xff;
+    assert!(serde_json::from_value::<Bytes>(serde_json::json!(raw)).is_err());
+    assert!(serde_json::from_value::<Bytes>(serde_json::json!(vec![0u8; 15])).is_err());
+    assert!(serde_json::from_value::<Bytes>(serde_json::json!(vec![0u8; 17])).is_err());
+    assert!(serde_json::from_value::<Bytes>(serde_json::json!(vec![0u8; 16])).is_ok());
+}
@@ -0,0 +1,180 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::time::{Duration, UNIX_EPOCH};
+
+use smart_uuid::test::MockClock;
+use smart_uuid::{
+    FileStateStore, GeneratorState, GeneratorStateError, Layout, SnowflakeError, SnowflakeGenerator, SnowflakeLayout,
+    StateStore, TypedUuid, UuidType,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum TweetType {
+    #[uuid_type(prefix = "tweet")]
+    Tweet,
+    #[uuid_type(prefix = "dm")]
+    DirectMessage,
+}
+
+#[test]
+fn existing_snowflakes_round_trip() {
+    // Created 2013-01-08T05:51:48.473Z by worker 678, sequence 0
+    let snowflake = 288_586_056_245_862_400;
+    let id = SnowflakeLayout::TWITTER.encode(TweetType::Tweet, snowflake).unwrap();
+
+    assert_eq!(id.variant_type(), TweetType::Tweet);
+    assert_eq!(id.layout(), Layout::TimeOrdered);
+    assert_eq!(
+        id.timestamp(),
+        Some(UNIX_EPOCH + Duration::from_millis(1_288_834_974_657 + (snowflake >> 22)))
+    );
+    assert_eq!(SnowflakeLayout::TWITTER.decode(&id), Some(snowflake));
+
+    for snowflake in [0, 1, (1 << 22) - 1, i64::MAX as u64] {
+        let id = SnowflakeLayout::TWITTER.encode(TweetType::DirectMessage, snowflake).unwrap();
+        assert_eq!(SnowflakeLayout::TWITTER.decode(&id), Some(snowflake));
+    }
+}
+
+#[test]
+fn rejects_what_does_not_fit() {
+    assert_eq!(SnowflakeLayout::TWITTER.encode(TweetType::Tweet, 1 << 63), Err(SnowflakeError::SignBitSet(1 << 63)));
+
+    let plain = TypedUuid::new(TweetType::Tweet);
+    assert_eq!(SnowflakeLayout::TWITTER.decode(&plain), None);
+
+    // Created before the layout's epoch
+    let early = SnowflakeLayout::with_epoch_millis(0).encode(TweetType::Tweet, 1 << 22).unwrap();
+    assert_eq!(SnowflakeLayout::TWITTER.decode(&early), None);
+}
+
+#[test]
+fn encode_rejects_times_past_the_time_ordered_range() {
+    // The last millisecond a time-ordered ID can hold
+    let max_millis = (1 << 44) - 1;
+    let layout = SnowflakeLayout::with_epoch_millis(max_millis - 1);
+    let id = layout.encode(TweetType::Tweet, 1 << 22).unwrap();
+    assert_eq!(id.timestamp(), Some(UNIX_EPOCH + Duration::from_millis(max_millis)));
+    assert_eq!(layout.decode(&id), Some(1 << 22));
+
+    assert_eq!(
+        layout.encode(TweetType::Tweet, 2 << 22),
+        Err(SnowflakeError::TimestampOutOfRange { epoch_millis: max_millis - 1, timestamp: 2 })
+    );
+    let layout = SnowflakeLayout::with_epoch_millis(u64::MAX);
+    assert!(matches!(layout.encode(TweetType::Tweet, 1 << 22), Err(SnowflakeError::TimestampOutOfRange { .. })));
+}
+
+#[test]
+fn generator_fails_once_the_last_millisecond_is_used_up() {
+    // Past the 41-bit range, the clock is clamped to its last millisecond
+    let max_timestamp = (1 << 41) - 1;
+    let clock = MockClock::new(UNIX_EPOCH + Duration::from_millis(1_288_834_974_657 + max_timestamp + 10));
+    let generator = SnowflakeGenerator::with_clock(SnowflakeLayout::TWITTER, 3u16, &clock);
+
+    let last = (0..4096).map(|_| generator.generate(TweetType::Tweet).unwrap()).last().unwrap();
+    assert_eq!(SnowflakeLayout::TWITTER.decode(&last), Some((max_timestamp << 22) | (3 << 12) | 4095));
+
+    assert_eq!(generator.next_snowflake(), Err(SnowflakeError::Exhausted));
+    assert_eq!(generator.generate(TweetType::Tweet), Err(SnowflakeError::Exhausted));
+    assert_eq!(generator.state().sequence(), 4095);
+}
+
+#[test]
+fn generator_is_unique_and_ordered() {
+    let generator = SnowflakeGenerator::new(SnowflakeLayout::TWITTER, 42u16);
+    let snowflakes: Vec<u64> = (0..10_000).map(|_| generator.next_snowflake().unwrap()).collect();
+
+    assert!(snowflakes.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(snowflakes.iter().all(|snowflake| (snowflake >> 12) & 0x3FF == 42));
+
+    let ids: HashSet<_> = (0..1_000).map(|_| generator.generate(TweetType::Tweet).unwrap()).collect();
+    assert_eq!(ids.len(), 1_000);
+    for id in &ids {
+        let snowflake = SnowflakeLayout::TWITTER.decode(id).unwrap();
+        assert_eq!((snowflake >> 12) & 0x3FF, 42);
+    }
+}
+
+#[test]
+fn worker_id_comes_from_provider() {
+    let generator = SnowflakeGenerator::new(SnowflakeLayout::TWITTER, || 7);
+    assert_eq!(generator.worker_id(), 7);
+    assert_eq!(generator.layout(), SnowflakeLayout::TWITTER);
+}
+
+#[test]
+#[should_panic(expected = "worker ID 1024")]
+fn rejects_oversized_worker_id() {
+    SnowflakeGenerator::new(SnowflakeLayout::TWITTER, SnowflakeLayout::MAX_WORKER_ID + 1);
+}
+
+#[test]
+fn generator_reads_time_from_clock() {
+    let clock = MockClock::new(UNIX_EPOCH + Duration::from_millis(1_288_834_974_657 + 1_000));
+    let generator = SnowflakeGenerator::with_clock(SnowflakeLayout::TWITTER, 3u16, &clock);
+    let worker = 3 << 12;
+
+    assert_eq!(generator.next_snowflake().unwrap(), (1_000 << 22) | worker);
+    assert_eq!(generator.next_snowflake().unwrap(), (1_000 << 22) | worker | 1);
+
+    // Exhausting the sequence borrows the next millisecond
+    let last = (0..4095).map(|_| generator.next_snowflake().unwrap()).last().unwrap();
+    assert_eq!(last, (1_001 << 22) | worker);
+
+    // A clock going backwards does not go back in sequence
+    clock.set(UNIX_EPOCH + Duration::from_millis(1_288_834_974_657));
+    assert_eq!(generator.next_snowflake().unwrap(), (1_001 << 22) | worker | 1);
+
+    clock.advance(Duration::from_secs(2));
+    assert_eq!(generator.next_snowflake().unwrap(), (2_000 << 22) | worker);
+}
+
+#[test]
+fn restored_state_survives_clock_going_back() {
+    let clock = MockClock::new(UNIX_EPOCH + Duration::from_millis(1_288_834_974_657 + 5_000));
+    let before = SnowflakeGenerator::with_clock(SnowflakeLayout::TWITTER, 3u16, &clock);
+    let last = (0..10).map(|_| before.next_snowflake().unwrap()).last().unwrap();
+    let state = before.state();
+    assert_eq!((state.timestamp(), state.sequence()), (5_000, 9));
+
+    // After the restart the clock reads earlier than before
+    clock.set(UNIX_EPOCH + Duration::from_millis(1_288_834_974_657 + 4_000));
+    let after = SnowflakeGenerator::with_clock(SnowflakeLayout::TWITTER, 3u16, &clock);
+    after.restore(&state).unwrap();
+    assert_eq!(after.next_snowflake().unwrap(), last + 1);
+
+    // Restoring an older state does not rewind
+    after.restore(&GeneratorState::from_str("1288834974657:3:1:0").unwrap()).unwrap();
+    assert_eq!(after.next_snowflake().unwrap(), last + 2);
+}
+
+#[test]
+fn restore_rejects_other_generators_state() {
+    let state: GeneratorState = "0:3:1000:0".parse().unwrap();
+    let generator = SnowflakeGenerator::new(SnowflakeLayout::TWITTER, 3u16);
+    assert_eq!(
+        generator.restore(&state),
+        Err(GeneratorStateError::EpochMismatch { expected: 1_288_834_974_657, found: 0 })
+    );
+
+    let generator = SnowflakeGenerator::new(SnowflakeLayout::with_epoch_millis(0), 4u16);
+    assert_eq!(generator.restore(&state), Err(GeneratorStateError::WorkerMismatch { expected: 4, found: 3 }));
+}
+
+#[test]
+fn state_round_trips_through_strings_serde_and_files() {
+    let state: GeneratorState = "1288834974657:3:5000:9".parse().unwrap();
+    assert_eq!(state.to_string(), "1288834974657:3:5000:9");
+    assert_eq!(serde_json::from_str::<GeneratorState>(&serde_json::to_string(&state).unwrap()).unwrap(), state);
+    for bad in ["", "1:2:3", "1:2:3:4:5", "1:70000:3:4", "a:b:c:d"] {
+        assert!(matches!(bad.parse::<GeneratorState>(), Err(GeneratorStateError::InvalidFormat(_))), "{}", bad);
+    }
+
+    let path = std::env::temp_dir().join(format!("smart_uuid_state_{}", std::process::id()));
+    let store = FileStateStore::new(&path);
+    assert_eq!(store.load().unwrap(), None);
+    store.save(&state).unwrap();
+    assert_eq!(store.load().unwrap(), Some(state));
+    std::fs::remove_file(&path).unwrap();
+}
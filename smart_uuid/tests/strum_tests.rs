@@ -0,0 +1,59 @@
+use smart_uuid::{impl_uuid_type_from_strum, TypedUuid, UserFriendlyUuid, UuidType, VariantUuid};
+use strum::{EnumIter, FromRepr, IntoStaticStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, FromRepr, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+#[repr(u8)]
+enum LegacyKind {
+    Customer = 3,
+    SupportAgent,
+    #[strum(serialize = "org")]
+    Organization = 20,
+}
+
+impl_uuid_type_from_strum!(LegacyKind);
+
+// Without `#[repr(u8)]`, strum's `from_repr` takes another integer type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, FromRepr, IntoStaticStr)]
+enum ImplicitKind {
+    First,
+    Second,
+}
+
+impl_uuid_type_from_strum!(ImplicitKind);
+
+#[test]
+fn reuses_strum_discriminants_and_names() {
+    assert_eq!(LegacyKind::Customer.discriminant(), 3);
+    assert_eq!(LegacyKind::SupportAgent.discriminant(), 4);
+    assert_eq!(LegacyKind::from_discriminant(20), Some(LegacyKind::Organization));
+    assert_eq!(LegacyKind::from_discriminant(0), None);
+
+    assert_eq!(LegacyKind::SupportAgent.prefix(), "support_agent");
+    assert_eq!(LegacyKind::from_prefix("org"), Some(LegacyKind::Organization));
+    assert_eq!(LegacyKind::from_prefix("organization"), None);
+    assert_eq!(LegacyKind::prefixes(), &["customer", "support_agent", "org"]);
+}
+
+#[test]
+fn round_trips_through_user_friendly_form() {
+    let typed = TypedUuid::new(LegacyKind::SupportAgent);
+    let friendly = UserFriendlyUuid::from(typed).to_string();
+    assert!(friendly.starts_with("support_agent_04"));
+
+    let parsed = UserFriendlyUuid::<LegacyKind>::parse_str(&friendly).unwrap();
+    assert_eq!(parsed.into_typed_uuid(), typed);
+}
+
+#[test]
+fn lists_discriminants_for_const_checks() {
+    assert_eq!(LegacyKind::DISCRIMINANTS, &[3, 4, 20]);
+    assert_eq!(ImplicitKind::DISCRIMINANTS, &[0, 1]);
+
+    const ORG: TypedUuid<LegacyKind> =
+        TypedUuid::from_bytes_const(smart_uuid::uuid::uuid!("14000000-0000-8000-8000-000000000001").into_bytes());
+    assert_eq!(ORG.variant_type(), LegacyKind::Organization);
+
+    let agent = VariantUuid::<LegacyKind, 4>::new();
+    assert_eq!(agent.variant_type(), LegacyKind::SupportAgent);
+}
@@ -0,0 +1,118 @@
+//! Tests for the experimental const-generic `TaggedUuid`.
+
+use smart_uuid::{Migrator, PrefixTag, TaggedUuid, TypedUuid, TypedUuidError, Uuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    Business,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+enum Org {}
+
+impl PrefixTag for Org {
+    const PREFIX: &'static str = "org";
+}
+
+enum Company {}
+
+impl PrefixTag for Company {
+    const PREFIX: &'static str = "company";
+}
+
+type OrgId = TaggedUuid<2, Org>;
+
+const ROOT_ORG: OrgId = TaggedUuid::from_u128(0x0200_0000_0000_8000_8000_0000_0000_0001);
+
+#[test]
+fn new_ids_carry_the_discriminant() {
+    let id = OrgId::new();
+    assert_eq!(id.as_uuid().as_bytes()[0], OrgId::DISCRIMINANT);
+    assert_eq!(id.as_uuid().get_version_num(), 8);
+    assert_eq!(id.prefix(), "org");
+    assert_ne!(id, OrgId::new());
+}
+
+#[test]
+fn const_ids_display_and_parse() {
+    let s = ROOT_ORG.to_string();
+    assert_eq!(s, "org_02000000-0000-8000-8000-000000000001");
+    assert_eq!(s.parse::<OrgId>().unwrap(), ROOT_ORG);
+    assert_eq!(serde_json::to_string(&ROOT_ORG).unwrap(), format!("\"{}\"", s));
+    assert_eq!(serde_json::from_str::<OrgId>(&format!("\"{}\"", s)).unwrap(), ROOT_ORG);
+
+    let err = "orgg_02000000-0000-8000-8000-000000000001".parse::<OrgId>().unwrap_err();
+    assert!(err.is_unknown_prefix());
+    assert!(err.to_string().contains("did you mean 'org'?"), "{}", err);
+
+    let err = "org_00000000-0000-8000-8000-000000000001".parse::<OrgId>().unwrap_err();
+    assert!(matches!(err, TypedUuidError::InvalidDiscriminant { found: 0, .. }));
+}
+
+#[test]
+fn converts_to_and_from_typed_uuid() {
+    let typed = TypedUuid::new(UserType::Organization);
+    let tagged: OrgId = typed.to_tagged().unwrap();
+    assert_eq!(tagged.as_uuid(), typed.as_uuid());
+    assert_eq!(tagged.to_typed::<UserType>().unwrap(), typed);
+    assert_eq!(TypedUuid::<UserType>::try_from(tagged).unwrap(), typed);
+
+    // Another variant
+    let retail = TypedUuid::new(UserType::Retail);
+    assert!(OrgId::try_from(retail).unwrap_err().is_invalid_discriminant());
+
+    // The right discriminant under another prefix
+    let err = typed.to_tagged::<2, Company>().unwrap_err();
+    assert!(err.is_unknown_prefix());
+    let company = TaggedUuid::<1, Company>::new();
+    assert!(company.to_typed::<UserType>().unwrap_err().is_unknown_prefix());
+
+    // A discriminant the enum does not have
+    let unknown = TaggedUuid::<9, Org>::new();
+    assert!(unknown.to_typed::<UserType>().unwrap_err().is_invalid_discriminant());
+    assert!(OrgId::try_from(Uuid::nil()).is_err());
+}
+
+/// Version 1 swapped the discriminants of the IDs from before versioning.
+struct SwapMigrator;
+
+impl Migrator<VersionedType> for SwapMigrator {
+    fn current_version(&self) -> u8 {
+        1
+    }
+
+    fn migrate(&self, _from_version: u8, uuid: Uuid) -> Result<Uuid, TypedUuidError> {
+        let mut bytes = *uuid.as_bytes();
+        bytes[0] ^= 1;
+        Ok(Uuid::from_bytes(bytes))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(migrator = SwapMigrator)]
+enum VersionedType {
+    Retail,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+#[test]
+fn versioned_ids_convert_like_they_parse() {
+    for _ in 0..1_000 {
+        let typed = TypedUuid::new(VersionedType::Organization);
+        let tagged: TaggedUuid<1, Org> = typed.to_tagged().unwrap();
+        let back = tagged.to_typed::<VersionedType>().unwrap();
+        assert_eq!(back, typed);
+        assert_eq!(back.to_string().parse::<TypedUuid<VersionedType>>().unwrap(), back);
+    }
+
+    // An unversioned tagged ID is migrated first, exactly as parsing its UUID would
+    let unversioned = TaggedUuid::<0, Org>::new();
+    let typed = unversioned.to_typed::<VersionedType>().unwrap();
+    assert_eq!(typed, TypedUuid::<VersionedType>::from_uuid(unversioned.into_uuid()).unwrap());
+    assert_eq!(typed.variant_type(), VersionedType::Organization);
+    assert_eq!(typed.to_string().parse::<TypedUuid<VersionedType>>().unwrap(), typed);
+    assert!(TaggedUuid::<1, Org>::new().to_typed::<VersionedType>().unwrap_err().is_unknown_prefix());
+}
@@ -0,0 +1,110 @@
+//! Tests for the tokio-postgres integration and binary COPY loader.
+//! Run with: cargo test -p smart_uuid --features tokio-postgres
+//!
+//! The encoding tests run anywhere. The database tests are ignored by default; to run
+//! them, point `DATABASE_URL` at a Postgres database and pass `--ignored`, e.g.
+//! `DATABASE_URL=postgres://postgres@localhost/postgres cargo test ... -- --ignored`.
+
+#![cfg(feature = "tokio-postgres")]
+
+use smart_uuid::tokio_postgres::{copy_ids, copy_in};
+use bytes::BytesMut;
+use smart_uuid::{TypedUuid, UuidType};
+use tokio_postgres::types::{FromSql, IsNull, ToSql, Type};
+use tokio_postgres::{Client, NoTls};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+/// Connects to `DATABASE_URL` and creates an empty temporary table.
+async fn connect() -> Client {
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set to run the database tests");
+    let (client, connection) = tokio_postgres::connect(&url, NoTls).await.unwrap();
+    tokio::spawn(connection);
+    client
+        .batch_execute("CREATE TEMPORARY TABLE users (id UUID PRIMARY KEY, name TEXT NOT NULL)")
+        .await
+        .unwrap();
+    client
+}
+
+#[test]
+fn typed_ids_encode_as_binary_uuids() {
+    let id = TypedUuid::new(UserType::Organization);
+
+    let mut buf = BytesMut::new();
+    assert!(matches!(id.to_sql_checked(&Type::UUID, &mut buf).unwrap(), IsNull::No));
+    assert_eq!(&buf[..], id.as_bytes());
+    assert_eq!(TypedUuid::<UserType>::from_sql(&Type::UUID, &buf).unwrap(), id);
+
+    assert!(id.to_sql_checked(&Type::TEXT, &mut BytesMut::new()).is_err());
+    assert!(!<TypedUuid<UserType> as FromSql>::accepts(&Type::TEXT));
+}
+
+#[test]
+fn decoding_rejects_mistyped_ids() {
+    let mut bytes = *TypedUuid::new(UserType::Retail).as_bytes();
+    bytes[0] = 0xFF;
+    assert!(TypedUuid::<UserType>::from_sql(&Type::UUID, &bytes).is_err());
+    assert!(TypedUuid::<UserType>::from_sql(&Type::UUID, &bytes[..15]).is_err());
+}
+
+#[tokio::test(flavor = "current_thread")]
+#[ignore = "needs DATABASE_URL"]
+async fn typed_ids_round_trip_as_parameters() {
+    let client = connect().await;
+
+    let id = TypedUuid::new(UserType::Organization);
+    client.execute("INSERT INTO users VALUES ($1, 'acme')", &[&id]).await.unwrap();
+
+    let row = client.query_one("SELECT id FROM users WHERE id = $1", &[&id]).await.unwrap();
+    assert_eq!(row.get::<_, TypedUuid<UserType>>(0), id);
+
+    client
+        .execute("INSERT INTO users VALUES ('ff000000-0000-8000-8000-000000000000', 'bad')", &[])
+        .await
+        .unwrap();
+    let row = client.query_one("SELECT id FROM users WHERE name = 'bad'", &[]).await.unwrap();
+    assert!(row.try_get::<_, TypedUuid<UserType>>(0).is_err());
+}
+
+#[tokio::test(flavor = "current_thread")]
+#[ignore = "needs DATABASE_URL"]
+async fn copy_in_streams_ids_with_companion_columns() {
+    let client = connect().await;
+
+    let users: Vec<_> = (0..1000).map(|i| (TypedUuid::new(UserType::Retail), format!("user {}", i))).collect();
+    let rows = users.iter().map(|(id, name)| [id as &(dyn ToSql + Sync), name]);
+    let copied = copy_in(&client, "COPY users (id, name) FROM STDIN (FORMAT binary)", &[Type::UUID, Type::TEXT], rows)
+        .await
+        .unwrap();
+    assert_eq!(copied, 1000);
+
+    let (id, name) = &users[500];
+    let row = client.query_one("SELECT name FROM users WHERE id = $1", &[id]).await.unwrap();
+    assert_eq!(row.get::<_, String>(0), *name);
+}
+
+#[tokio::test(flavor = "current_thread")]
+#[ignore = "needs DATABASE_URL"]
+async fn copy_ids_fills_a_single_column() {
+    let client = connect().await;
+    client.batch_execute("CREATE TEMPORARY TABLE orgs (id UUID)").await.unwrap();
+
+    let ids: Vec<_> = (0..100).map(|_| TypedUuid::new(UserType::Organization)).collect();
+    let copied = copy_ids(&client, "COPY orgs (id) FROM STDIN (FORMAT binary)", &ids).await.unwrap();
+    assert_eq!(copied, 100);
+
+    let loaded: Vec<TypedUuid<UserType>> = client
+        .query("SELECT id FROM orgs", &[])
+        .await
+        .unwrap()
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+    assert_eq!(loaded, ids);
+}
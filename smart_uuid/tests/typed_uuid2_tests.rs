@@ -0,0 +1,64 @@
+//! Tests for TypedUuid2, the two-level (domain + entity) typed UUID.
+
+use smart_uuid::{TypedUuid2, TypedUuidError, Uuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum Domain {
+    Billing,
+    Shipping,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum Entity {
+    Invoice,
+    Payment,
+    #[uuid_type(prefix = "parcel_label")]
+    ParcelLabel,
+}
+
+type PlatformId = TypedUuid2<Domain, Entity>;
+
+#[test]
+fn encodes_domain_and_entity() {
+    let id = PlatformId::new(Domain::Billing, Entity::Payment);
+    assert_eq!(id.domain(), Domain::Billing);
+    assert_eq!(id.entity(), Entity::Payment);
+    assert_eq!(id.as_bytes()[0], 0);
+    assert_eq!(id.as_bytes()[1], 1);
+    assert_eq!(id.as_uuid().get_version_num(), 8);
+}
+
+#[test]
+fn round_trips_through_strings_and_serde() {
+    let id = PlatformId::new(Domain::Shipping, Entity::Invoice);
+
+    assert_eq!(id.to_string().parse::<PlatformId>().unwrap(), id);
+
+    let json = serde_json::to_string(&id).unwrap();
+    assert_eq!(json, format!("\"{}\"", id.as_uuid()));
+    assert_eq!(serde_json::from_str::<PlatformId>(&json).unwrap(), id);
+}
+
+#[test]
+fn friendly_form_joins_both_prefixes() {
+    let id = PlatformId::new(Domain::Shipping, Entity::ParcelLabel);
+    let friendly = id.to_friendly_string();
+    assert_eq!(friendly, format!("shipping_parcel_label_{}", id.as_uuid()));
+    assert_eq!(PlatformId::parse_friendly(&friendly).unwrap(), id);
+
+    let wrong = format!("billing_parcel_label_{}", id.as_uuid());
+    assert!(PlatformId::parse_friendly(&wrong).unwrap_err().is_unknown_prefix());
+}
+
+#[test]
+fn from_uuid_validates_both_bytes() {
+    let mut bytes = *PlatformId::new(Domain::Billing, Entity::Invoice).as_bytes();
+    bytes[1] = 9;
+    let err = PlatformId::from_uuid(Uuid::from_bytes(bytes)).unwrap_err();
+    assert!(matches!(err, TypedUuidError::InvalidDiscriminant { found: 9, .. }));
+
+    bytes[1] = 0;
+    bytes[0] = 7;
+    let err = PlatformId::from_uuid(Uuid::from_bytes(bytes)).unwrap_err();
+    assert!(matches!(err, TypedUuidError::InvalidDiscriminant { found: 7, .. }));
+}
@@ -2,7 +2,8 @@
 //!
 //! These tests are written FIRST (TDD) before implementation.
 
-use smart_uuid::{TypedUuid, UserFriendlyUuid, UuidType, TypedUuidError, Uuid};
+use smart_uuid::{assert_same_id, assert_variant, variant_matches, TypedUuid, UserFriendlyUuid, UuidType, TypedUuidError, Uuid};
+use smart_uuid::test::FixtureFactory;
 
 // ============================================================================
 // Test Enum - uses derive macro
@@ -36,6 +37,11 @@ fn derive_macro_from_discriminant_works() {
     assert_eq!(UserType::from_discriminant(255), None);
 }
 
+#[test]
+fn derive_macro_generates_prefix_list() {
+    assert_eq!(UserType::prefixes(), &["retail", "business", "org"]);
+}
+
 #[test]
 fn derive_macro_generates_correct_prefixes() {
     // Default snake_case for Retail and Business
@@ -61,6 +67,115 @@ fn typed_uuid_new_preserves_variant_type() {
     assert_eq!(typed.variant_type(), UserType::Organization);
 }
 
+#[test]
+fn variant_predicates() {
+    let typed = TypedUuid::new(UserType::Business);
+    assert!(typed.is_variant(UserType::Business));
+    assert!(!typed.is_variant(UserType::Retail));
+    assert!(typed.is(UserType::Business) && !typed.is(UserType::Organization));
+
+    let friendly = UserFriendlyUuid::from(TypedUuid::new(UserType::Organization));
+    assert!(friendly.is_variant(UserType::Organization));
+    assert!(friendly.is(UserType::Organization) && !friendly.is(UserType::Retail));
+    assert!(friendly.has_prefix("org"));
+    assert!(!friendly.has_prefix("organization"));
+
+    assert!(variant_matches!(typed, UserType::Retail | UserType::Business));
+    assert!(!variant_matches!(friendly, UserType::Retail | UserType::Business));
+    assert!(variant_matches!(friendly, v if v.prefix().len() == 3));
+}
+
+#[test]
+fn replace_variant_keeps_every_other_byte() {
+    let draft = TypedUuid::new_ordered(UserType::Retail);
+    let promoted = draft.replace_variant(UserType::Business);
+    assert_eq!(promoted.variant_type(), UserType::Business);
+    assert_eq!(promoted.as_bytes()[1..], draft.as_bytes()[1..]);
+    assert_eq!(promoted.timestamp_millis(), draft.timestamp_millis());
+    assert_eq!(promoted.replace_variant(UserType::Retail), draft);
+    assert_eq!(draft.replace_variant(UserType::Retail), draft);
+
+    let friendly = UserFriendlyUuid::from(draft).replace_variant(UserType::Organization);
+    assert!(friendly.has_prefix("org"));
+    assert_eq!(friendly.as_typed_uuid().as_bytes()[1..], draft.as_bytes()[1..]);
+}
+
+#[test]
+fn map_type_recasts_between_enums() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+    enum AccountType {
+        Personal,
+        Company,
+    }
+
+    let ids = [TypedUuid::new_ordered(UserType::Retail), TypedUuid::new(UserType::Organization)];
+    let accounts = ids.map(|id| {
+        id.map_type(|user| match user {
+            UserType::Retail => AccountType::Personal,
+            UserType::Business | UserType::Organization => AccountType::Company,
+        })
+    });
+
+    assert_eq!(accounts[0].variant_type(), AccountType::Personal);
+    assert_eq!(accounts[1].variant_type(), AccountType::Company);
+    assert_eq!(accounts[0].as_bytes()[1..], ids[0].as_bytes()[1..]);
+    assert_eq!(accounts[0].timestamp_millis(), ids[0].timestamp_millis());
+    assert_eq!(accounts[1].map_type(|_| UserType::Organization), ids[1]);
+}
+
+#[test]
+fn nil_and_max_bound_every_id_of_the_variant() {
+    let nil = TypedUuid::nil(UserType::Business);
+    let max = TypedUuid::max(UserType::Business);
+    assert_eq!(nil.to_string(), "01000000-0000-8000-8000-000000000000");
+    assert_eq!(max.to_string(), "01ffffff-ffff-8fff-ffff-ffffffffffff");
+    assert_eq!(nil.variant_type(), UserType::Business);
+    assert_eq!(max.as_uuid().get_version_num(), 8);
+    assert_eq!(TypedUuid::<UserType>::from_uuid(*max.as_uuid()).unwrap(), max);
+
+    for id in [TypedUuid::new(UserType::Business), TypedUuid::new_ordered(UserType::Business)] {
+        assert!(nil.as_uuid() < id.as_uuid() && id.as_uuid() < max.as_uuid());
+    }
+    assert!(max.as_uuid() < TypedUuid::nil(UserType::Organization).as_uuid());
+}
+
+#[test]
+fn range_for_covers_every_layout_of_the_variant() {
+    let (low, high) = TypedUuid::range_for(UserType::Business);
+    assert_eq!(low.to_string(), "01000000-0000-0000-0000-000000000000");
+    assert_eq!(high.to_string(), "01ffffff-ffff-ffff-ffff-ffffffffffff");
+
+    for id in [
+        TypedUuid::new(UserType::Business),
+        TypedUuid::new_ordered(UserType::Business),
+        TypedUuid::new_v4_compatible(UserType::Business),
+        TypedUuid::new_opaque(UserType::Business),
+        TypedUuid::nil(UserType::Business),
+        TypedUuid::max(UserType::Business),
+    ] {
+        assert!(low <= *id.as_uuid() && *id.as_uuid() <= high);
+    }
+
+    let (next_low, _) = TypedUuid::range_for(UserType::Organization);
+    assert!(high < next_low);
+}
+
+#[test]
+fn ids_order_by_discriminant_then_bytes() {
+    let org = TypedUuid::new(UserType::Organization);
+    let retail = TypedUuid::new(UserType::Retail);
+    let earlier = TypedUuid::new_ordered_at(UserType::Business, std::time::UNIX_EPOCH);
+    let later = TypedUuid::new_ordered(UserType::Business);
+
+    let set: std::collections::BTreeSet<_> = [org, later, retail, earlier].into_iter().collect();
+    assert_eq!(set.into_iter().collect::<Vec<_>>(), [retail, earlier, later, org]);
+
+    // Friendly IDs sort like their typed IDs, not like their strings ("org" < "retail")
+    let mut friendly: Vec<UserFriendlyUuid<UserType>> = vec![org.into(), retail.into()];
+    friendly.sort();
+    assert_eq!(friendly, [UserFriendlyUuid::from(retail), org.into()]);
+}
+
 #[test]
 fn typed_uuid_discriminant_stored_in_byte_0() {
     let typed = TypedUuid::new(UserType::Retail);
@@ -104,6 +219,79 @@ fn typed_uuid_from_uuid_rejects_invalid_discriminant() {
     assert!(matches!(result, Err(TypedUuidError::InvalidDiscriminant { found: 255, .. })));
 }
 
+#[test]
+fn typed_uuid_u128_round_trips() {
+    let id = TypedUuid::<UserType>::from_uuid(Uuid::parse_str("02123456-789a-8bcd-8ef0-123456789abc").unwrap()).unwrap();
+    assert_eq!(id.as_u128(), 0x0212_3456_789a_8bcd_8ef0_1234_5678_9abc);
+    assert_eq!(id.to_u128_le() as u8, 2);
+    assert_eq!(TypedUuid::try_from_u128(id.as_u128()).unwrap(), id);
+    assert_eq!(TypedUuid::try_from_u128_le(id.to_u128_le()).unwrap(), id);
+
+    assert!(matches!(
+        TypedUuid::<UserType>::try_from_u128(0xff << 120),
+        Err(TypedUuidError::InvalidDiscriminant { found: 255, .. })
+    ));
+    assert!(TypedUuid::<UserType>::try_from_u128_le(id.as_u128()).is_err());
+}
+
+#[test]
+fn typed_uuid_round_trips_through_guid_byte_order() {
+    let id = TypedUuid::<UserType>::from_uuid(Uuid::parse_str("01123456-789a-8bcd-8ef0-123456789abc").unwrap()).unwrap();
+    let guid_bytes = id.to_bytes_le();
+    assert_eq!(guid_bytes[..8], [0x56, 0x34, 0x12, 0x01, 0x9a, 0x78, 0xcd, 0x8b]);
+    assert_eq!(TypedUuid::from_bytes_le(guid_bytes).unwrap(), id);
+
+    // What .NET shows after `new Guid(bytes)` on our big-endian bytes
+    assert_eq!(id.to_guid_string(), "56341201-9a78-cd8b-8ef0-123456789abc");
+    assert_eq!(TypedUuid::parse_guid("56341201-9a78-cd8b-8ef0-123456789abc").unwrap(), id);
+    assert_eq!(TypedUuid::parse_guid("{56341201-9A78-CD8B-8EF0-123456789ABC}").unwrap(), id);
+
+    // The discriminant is read from byte 3 of GUID-ordered bytes
+    assert!(matches!(
+        TypedUuid::<UserType>::from_bytes_le(*id.as_bytes()),
+        Err(TypedUuidError::InvalidDiscriminant { found: 0x56, .. })
+    ));
+}
+
+#[test]
+fn typed_uuid_from_bytes_const_builds_statics() {
+    static ROOT_ORG: TypedUuid<UserType> =
+        TypedUuid::from_bytes_const(smart_uuid::uuid::uuid!("02000000-0000-8000-8000-000000000001").into_bytes());
+
+    assert_eq!(UserType::DISCRIMINANTS, [0, 1, 2]);
+    assert_eq!(ROOT_ORG.variant_type(), UserType::Organization);
+    assert_eq!(ROOT_ORG, TypedUuid::from_bytes(*ROOT_ORG.as_bytes()).unwrap());
+}
+
+#[test]
+#[should_panic(expected = "not a discriminant")]
+fn typed_uuid_from_bytes_const_rejects_unknown_discriminants() {
+    let mut bytes = [0u8; 16];
+    bytes[0] = 3;
+    TypedUuid::<UserType>::from_bytes_const(bytes);
+}
+
+#[test]
+fn typed_uuid_from_raw_bytes_validates() {
+    let id = TypedUuid::new(UserType::Business);
+    let bytes = *id.as_bytes();
+    assert_eq!(TypedUuid::<UserType>::from_bytes(bytes).unwrap(), id);
+    assert_eq!(TypedUuid::<UserType>::from_slice(&bytes).unwrap(), id);
+    assert_eq!(TypedUuid::<UserType>::from_bytes_with_layout(bytes, smart_uuid::Layout::V8).unwrap(), id);
+
+    let mut invalid = bytes;
+    invalid[0] = 255;
+    assert!(matches!(
+        TypedUuid::<UserType>::from_bytes(invalid),
+        Err(TypedUuidError::InvalidDiscriminant { found: 255, .. })
+    ));
+    assert!(matches!(TypedUuid::<UserType>::from_slice(&bytes[..15]), Err(TypedUuidError::ParseError(_))));
+    assert!(matches!(
+        TypedUuid::<UserType>::from_bytes_with_layout(bytes, smart_uuid::Layout::TimeOrdered),
+        Err(TypedUuidError::InvalidFormat(_))
+    ));
+}
+
 #[test]
 fn typed_uuid_parse_str_works() {
     let original = TypedUuid::new(UserType::Business);
@@ -123,6 +311,334 @@ fn typed_uuid_display_is_standard_uuid_format() {
     assert!(display.chars().filter(|c| *c == '-').count() == 4);
 }
 
+#[test]
+fn typed_uuid_display_adapters() {
+    let typed = TypedUuid::new(UserType::Retail);
+    let uuid = *typed.as_uuid();
+
+    assert_eq!(typed.simple().to_string(), uuid.simple().to_string());
+    assert_eq!(typed.hyphenated().to_string(), typed.to_string());
+    assert_eq!(typed.braced().to_string(), format!("{{{}}}", uuid));
+    assert_eq!(typed.urn().to_string(), format!("urn:uuid:{}", uuid));
+
+    for form in [
+        typed.simple().to_string(),
+        typed.braced().to_string(),
+        typed.urn().to_string(),
+        format!("{:X}", typed.braced()),
+    ] {
+        assert_eq!(form.parse::<TypedUuid<UserType>>().unwrap(), typed, "{}", form);
+    }
+}
+
+#[test]
+fn typed_uuid_derive_child_is_deterministic() {
+    let parent = TypedUuid::new(UserType::Organization);
+
+    let child = parent.derive_child(UserType::Retail, b"member-1");
+    assert_eq!(child, parent.derive_child(UserType::Retail, b"member-1"));
+    assert_eq!(child.variant_type(), UserType::Retail);
+    assert_eq!(child.as_uuid().get_version_num(), 8);
+
+    assert_ne!(child, parent.derive_child(UserType::Retail, b"member-2"));
+    assert_ne!(child.as_bytes()[1..], parent.derive_child(UserType::Business, b"member-1").as_bytes()[1..]);
+}
+
+#[test]
+fn typed_uuid_is_child_of() {
+    let parent = TypedUuid::new(UserType::Organization);
+    let other = TypedUuid::new(UserType::Organization);
+    let child = parent.derive_child(UserType::Business, b"salt");
+
+    assert!(child.is_child_of(&parent, b"salt"));
+    assert!(!child.is_child_of(&parent, b"other-salt"));
+    assert!(!child.is_child_of(&other, b"salt"));
+}
+
+#[test]
+fn new_vanity_matches_the_pattern() {
+    let (id, attempts) = TypedUuid::new_vanity(UserType::Organization, "02A", 100_000).unwrap();
+    assert!(id.to_string().starts_with("02a"));
+    assert_eq!(id.variant_type(), UserType::Organization);
+    assert!(attempts >= 1);
+}
+
+#[test]
+fn new_vanity_fails_gracefully() {
+    use smart_uuid::VanityError;
+
+    let impossible = TypedUuid::new_vanity(UserType::Retail, "01", 10).unwrap_err();
+    assert!(matches!(impossible, VanityError::Impossible { .. }));
+
+    // The version digit is always 8
+    let impossible = TypedUuid::new_vanity(UserType::Retail, "00000000-0000-4", 10).unwrap_err();
+    assert!(matches!(impossible, VanityError::Impossible { .. }));
+
+    let invalid = TypedUuid::new_vanity(UserType::Retail, "00xyz", 10).unwrap_err();
+    assert!(matches!(invalid, VanityError::InvalidPattern(_)));
+
+    let exhausted = TypedUuid::new_vanity(UserType::Retail, "00000000-0000", 3).unwrap_err();
+    assert_eq!(exhausted, VanityError::Exhausted { pattern: "00000000-0000".to_string(), attempts: 3 });
+}
+
+#[test]
+fn new_sampled_follows_the_weights() {
+    use rand::SeedableRng;
+    use smart_uuid::VariantDistribution;
+
+    let mix = VariantDistribution::new(&[
+        (UserType::Retail, 90.0),
+        (UserType::Business, 10.0),
+        (UserType::Organization, 0.0),
+    ])
+    .unwrap();
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    let ids: Vec<_> = (0..2000).map(|_| TypedUuid::new_sampled(&mut rng, &mix)).collect();
+    let retail = ids.iter().filter(|id| id.variant_type() == UserType::Retail).count();
+    assert!((1700..1900).contains(&retail), "{} retail IDs", retail);
+    assert!(ids.iter().all(|id| id.variant_type() != UserType::Organization));
+
+    // Same seed, same data set
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    assert_eq!(TypedUuid::new_sampled(&mut rng, &mix), ids[0]);
+}
+
+#[test]
+fn new_with_rng_accepts_any_rng_core() {
+    use rand::SeedableRng;
+
+    /// Stands in for a hardware TRNG driver that only implements `rand_core`.
+    struct CountingTrng(u8);
+
+    impl rand::RngCore for CountingTrng {
+        fn next_u32(&mut self) -> u32 {
+            self.next_u64() as u32
+        }
+        fn next_u64(&mut self) -> u64 {
+            let mut bytes = [0u8; 8];
+            self.fill_bytes(&mut bytes);
+            u64::from_le_bytes(bytes)
+        }
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for byte in dest {
+                self.0 = self.0.wrapping_add(1);
+                *byte = self.0;
+            }
+        }
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    let id = TypedUuid::new_with_rng(UserType::Business, &mut CountingTrng(0));
+    assert_eq!(id.to_string(), "01020304-0506-8708-890a-0b0c0d0e0f10");
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+    let secure = TypedUuid::new_secure(UserType::Retail, &mut rng);
+    let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+    assert_eq!(TypedUuid::new_with_rng(UserType::Retail, &mut rng), secure);
+
+    let friendly = UserFriendlyUuid::new_with_rng(UserType::Business, &mut CountingTrng(0));
+    assert_eq!(friendly.to_string(), "business_01020304-0506-8708-890a-0b0c0d0e0f10");
+    let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+    assert_eq!(UserFriendlyUuid::new_secure(UserType::Retail, &mut rng).into_typed_uuid(), secure);
+}
+
+#[test]
+fn variant_distribution_rejects_bad_weights() {
+    use smart_uuid::VariantDistribution;
+
+    assert!(VariantDistribution::<UserType>::new(&[]).is_none());
+    assert!(VariantDistribution::new(&[(UserType::Retail, 0.0)]).is_none());
+    assert!(VariantDistribution::new(&[(UserType::Retail, -1.0)]).is_none());
+    assert!(VariantDistribution::new(&[(UserType::Retail, f64::NAN)]).is_none());
+
+    assert_eq!(smart_uuid::sample_variant(&[(UserType::Business, 1.0)]), UserType::Business);
+}
+
+#[test]
+fn uniqueness_auditor_counts_and_finds_duplicates() {
+    use smart_uuid::UniquenessAuditor;
+
+    let ids: Vec<_> = (0..1000).map(|i| TypedUuid::new(if i % 10 == 0 { UserType::Business } else { UserType::Retail })).collect();
+    let mut auditor = UniquenessAuditor::<UserType>::new(2000, 0.0001);
+    auditor.observe_all(ids.iter().copied());
+    assert!(auditor.report().is_clean());
+
+    assert!(auditor.observe(ids[3].into_uuid()));
+    let mut bytes = *ids[4].as_bytes();
+    bytes[0] = 200;
+    auditor.observe(Uuid::from_bytes(bytes));
+
+    let report = auditor.report();
+    assert_eq!(report.total, 1002);
+    assert_eq!(report.count(UserType::Retail), 901);
+    assert_eq!(report.count(UserType::Business), 100);
+    assert_eq!(report.count(UserType::Organization), 0);
+    assert_eq!(report.invalid, 1);
+    assert!(report.duplicates.contains(ids[3].as_uuid()));
+    assert!(!report.is_clean());
+}
+
+#[test]
+fn uniqueness_auditor_reads_lines() {
+    use smart_uuid::UniquenessAuditor;
+
+    let friendly = UserFriendlyUuid::new(UserType::Organization);
+    let input = format!("{}\n\n{}\nnot an id\n", friendly, friendly.as_typed_uuid());
+
+    let mut auditor = UniquenessAuditor::<UserType>::new(10, 0.01);
+    auditor.observe_reader(input.as_bytes()).unwrap();
+
+    let report = auditor.report();
+    assert_eq!(report.total, 2);
+    assert_eq!(report.unparseable, 1);
+    assert_eq!(report.duplicates, vec![*friendly.as_typed_uuid().as_uuid()]);
+}
+
+#[test]
+fn typed_uuid_shard_for_is_stable_across_versions() {
+    // Golden values: these must never change outside a major version
+    let cases = [
+        ("00123456-789a-8bcd-8ef0-123456789abc", [0, 1, 6, 15, 69, 893]),
+        ("02ffffff-ffff-8fff-bfff-ffffffffffff", [0, 1, 1, 15, 64, 111]),
+        ("01000000-0000-8000-8000-000000000001", [0, 1, 5, 5, 5, 5]),
+    ];
+
+    for (uuid_str, expected) in cases {
+        let id: TypedUuid<UserType> = uuid_str.parse().unwrap();
+        let shards = [1, 2, 7, 16, 100, 1024].map(|n| id.shard_for(n));
+        assert_eq!(shards, expected, "shards changed for {}", uuid_str);
+    }
+}
+
+#[test]
+fn typed_uuid_shard_for_ignores_discriminant() {
+    let retail = TypedUuid::new(UserType::Retail);
+    let mut bytes = *retail.as_bytes();
+    bytes[0] = UserType::Organization.discriminant();
+    let org = TypedUuid::<UserType>::try_from(bytes).unwrap();
+
+    assert_eq!(retail.shard_for(64), org.shard_for(64));
+}
+
+#[test]
+fn typed_uuid_shard_for_moves_few_ids_when_growing() {
+    let ids: Vec<_> = (0..1000).map(|_| TypedUuid::new(UserType::Retail)).collect();
+
+    let moved = ids.iter().filter(|id| id.shard_for(10) != id.shard_for(11)).count();
+    for id in &ids {
+        assert!(id.shard_for(11) < 11);
+    }
+    // Expect about 1/11 of the IDs to move; allow plenty of slack for randomness
+    assert!(moved < 200, "{} of 1000 IDs moved", moved);
+}
+
+#[test]
+fn variant_label_is_the_prefix() {
+    let typed = TypedUuid::new(UserType::Organization);
+    assert_eq!(typed.variant_label(), "org");
+    assert_eq!(UserFriendlyUuid::from(typed).variant_label(), "org");
+    assert_eq!(smart_uuid::AnyTypedUuid::from(typed).variant_label(), "org");
+}
+
+#[test]
+fn partition_key_is_the_same_for_typed_and_friendly() {
+    let typed = TypedUuid::new(UserType::Business);
+    let friendly = UserFriendlyUuid::from(typed);
+
+    assert_eq!(typed.partition_key_bytes(), typed.as_bytes());
+    assert_eq!(friendly.partition_key_bytes(), typed.partition_key_bytes());
+}
+
+#[test]
+fn storage_key_is_stable_and_round_trips() {
+    // Golden value: the directory layout must never change outside a major version
+    let id: TypedUuid<UserType> = "02123456-789a-8bcd-8ef0-123456789abc".parse().unwrap();
+    assert_eq!(id.storage_key(), "org/2a/c4/02123456-789a-8bcd-8ef0-123456789abc");
+    assert_eq!(id.storage_key_with_depth(0), "org/02123456-789a-8bcd-8ef0-123456789abc");
+
+    for depth in [0, 1, 2, 8] {
+        let key = id.storage_key_with_depth(depth);
+        assert_eq!(TypedUuid::<UserType>::parse_storage_key(&key).unwrap(), id);
+    }
+}
+
+#[test]
+fn parse_storage_key_rejects_mismatches() {
+    let id = TypedUuid::new(UserType::Retail);
+    let key = id.storage_key();
+
+    let wrong_prefix = key.replacen("retail", "org", 1);
+    assert!(TypedUuid::<UserType>::parse_storage_key(&wrong_prefix).unwrap_err().is_unknown_prefix());
+
+    let wrong_shard = format!("retail/zz/{}", &key[10..]);
+    assert!(TypedUuid::<UserType>::parse_storage_key(&wrong_shard).unwrap_err().is_invalid_format());
+
+    assert!(TypedUuid::<UserType>::parse_storage_key("retail").unwrap_err().is_invalid_format());
+}
+
+#[test]
+fn cluster_keys_share_one_hash_tag() {
+    let id: TypedUuid<UserType> = "02123456-789a-8bcd-8ef0-123456789abc".parse().unwrap();
+    let friendly = UserFriendlyUuid::from(id);
+
+    assert_eq!(id.hash_tag(), "{org_02123456-789a-8bcd-8ef0-123456789abc}");
+    assert_eq!(id.cluster_key("orders:{}:items"), "orders:{org_02123456-789a-8bcd-8ef0-123456789abc}:items");
+    assert_eq!(
+        friendly.cluster_keys(&["orders:{}", "carts:{}:lines"]),
+        vec![id.cluster_key("orders:{}"), id.cluster_key("carts:{}:lines")]
+    );
+
+    // Golden value: CRC16 of the tag, as `CLUSTER KEYSLOT` computes it
+    assert_eq!(id.cluster_slot(), 391);
+    assert_eq!(friendly.cluster_slot(), 391);
+}
+
+#[test]
+fn cluster_key_rejects_patterns_without_the_tag_first() {
+    let id = TypedUuid::new(UserType::Retail);
+    assert!(panic_message(move || drop(id.cluster_key("orders:items"))).contains("must contain {}"));
+    assert!(panic_message(move || drop(id.cluster_key("{tenant}:{}"))).contains("must contain {}"));
+}
+
+#[test]
+fn subject_tokens_are_nats_safe_and_round_trip() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+    enum EventType {
+        Order,
+        #[uuid_type(prefix = "acme.order")]
+        AcmeOrder,
+    }
+
+    let id: TypedUuid<UserType> = "02123456-789a-8bcd-8ef0-123456789abc".parse().unwrap();
+    assert_eq!(id.to_subject_token(), "org_02123456789a8bcd8ef0123456789abc");
+    assert_eq!(TypedUuid::<UserType>::parse_subject_token(&id.to_subject_token()).unwrap(), id);
+
+    let dotted = UserFriendlyUuid::new(EventType::AcmeOrder);
+    let token = dotted.to_subject_token();
+    assert!(token.starts_with("acme~2eorder_"));
+    assert!(!token.contains(['.', '*', '>', ' ']));
+    assert_eq!(UserFriendlyUuid::<EventType>::parse_subject_token(&token).unwrap(), dotted);
+
+    assert!(TypedUuid::<EventType>::parse_subject_token("acme~2order_0").unwrap_err().is_invalid_format());
+    assert!(TypedUuid::<EventType>::parse_subject_token("acme~+eorder_0").unwrap_err().is_invalid_format());
+}
+
+#[cfg(feature = "rdkafka")]
+#[test]
+fn rdkafka_keys_use_raw_bytes() {
+    use rdkafka::message::ToBytes;
+
+    let typed = TypedUuid::new(UserType::Business);
+    let friendly = UserFriendlyUuid::from(typed);
+
+    assert_eq!(typed.to_bytes(), &typed.as_bytes()[..]);
+    assert_eq!(friendly.to_bytes(), typed.to_bytes());
+}
+
 // ============================================================================
 // UserFriendlyUuid Tests
 // ============================================================================
@@ -155,6 +671,36 @@ fn user_friendly_uuid_parse_str_works() {
     assert_eq!(parsed.variant_type(), UserType::Business);
 }
 
+#[test]
+fn user_friendly_uuid_simple_body_roundtrip() {
+    let friendly = UserFriendlyUuid::new(UserType::Organization);
+    let simple = friendly.simple().to_string();
+
+    assert_eq!(simple, format!("org_{}", friendly.as_typed_uuid().simple()));
+    assert!(!simple.contains('-'));
+    assert_eq!(UserFriendlyUuid::<UserType>::parse_str(&simple).unwrap(), friendly);
+}
+
+#[test]
+fn user_friendly_uuid_simple_body_with_underscored_prefix() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+    enum KeyType {
+        #[uuid_type(prefix = "api_key")]
+        ApiKey,
+    }
+
+    // The `_` in `api_key` sits 37 bytes from the end, where a hyphenated body's separator would
+    let friendly = UserFriendlyUuid::new(KeyType::ApiKey);
+    let simple = friendly.simple().to_string();
+    assert_eq!(simple.as_bytes()[simple.len() - 37], b'_');
+
+    assert_eq!(UserFriendlyUuid::<KeyType>::parse_str(&simple).unwrap(), friendly);
+    assert_eq!(UserFriendlyUuid::<KeyType>::parse_str(&friendly.to_string()).unwrap(), friendly);
+
+    let token = friendly.to_subject_token();
+    assert_eq!(UserFriendlyUuid::<KeyType>::parse_subject_token(&token).unwrap(), friendly);
+}
+
 #[test]
 fn user_friendly_uuid_parse_rejects_unknown_prefix() {
     // Create a valid TypedUuid for Retail
@@ -167,6 +713,82 @@ fn user_friendly_uuid_parse_rejects_unknown_prefix() {
     assert!(matches!(result, Err(TypedUuidError::UnknownPrefix { .. })));
 }
 
+#[test]
+fn unknown_prefix_error_lists_expected_prefixes() {
+    let typed = TypedUuid::new(UserType::Retail);
+
+    let err = UserFriendlyUuid::<UserType>::parse_str(&format!("wrong_{}", typed)).unwrap_err();
+    match &err {
+        TypedUuidError::UnknownPrefix { expected, .. } => {
+            assert_eq!(*expected, &["retail", "business", "org"][..]);
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert!(err.to_string().ends_with("expected one of: retail, business, org"));
+}
+
+#[test]
+fn uuid_type_does_not_require_static() {
+    // A type that borrows cannot be `'static`, yet still formats and parses
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Scoped<'a>(&'a str);
+
+    impl<'a> UuidType for Scoped<'a> {
+        fn discriminant(&self) -> u8 {
+            0
+        }
+
+        fn from_discriminant(value: u8) -> Option<Self> {
+            (value == 0).then_some(Scoped("scoped"))
+        }
+
+        fn prefix(&self) -> &'static str {
+            "scp"
+        }
+    }
+
+    let name = String::from("scoped");
+    let id = UserFriendlyUuid::new(Scoped(&name));
+    assert_eq!(id.to_string().parse::<UserFriendlyUuid<Scoped<'_>>>().unwrap(), id);
+
+    let err = UserFriendlyUuid::<Scoped<'_>>::parse_str(&format!("wrong_{}", id.as_typed_uuid())).unwrap_err();
+    assert!(err.to_string().ends_with("expected one of: scp"));
+}
+
+#[test]
+fn unknown_prefix_error_suggests_closest_prefix() {
+    let typed = TypedUuid::new(UserType::Retail);
+
+    for (typo, suggestion) in [("rtail", "retail"), ("reatil", "retail"), ("bussiness", "business"), ("ogr", "org")] {
+        let err = UserFriendlyUuid::<UserType>::parse_str(&format!("{}_{}", typo, typed)).unwrap_err();
+        assert!(
+            matches!(err, TypedUuidError::UnknownPrefix { did_you_mean: Some(s), .. } if s == suggestion),
+            "{} should suggest {}: {:?}",
+            typo,
+            suggestion,
+            err
+        );
+        assert!(err.to_string().contains(&format!("(did you mean '{}'?)", suggestion)));
+    }
+
+    let err = UserFriendlyUuid::<UserType>::parse_str(&format!("wrong_{}", typed)).unwrap_err();
+    assert!(matches!(err, TypedUuidError::UnknownPrefix { did_you_mean: None, .. }));
+    assert!(!err.to_string().contains("did you mean"));
+}
+
+#[test]
+fn parse_error_keeps_uuid_error_as_source() {
+    use std::error::Error;
+
+    let err = "retail_zzzzzzzz-e29b-41d4-a716-446655440000".parse::<UserFriendlyUuid<UserType>>().unwrap_err();
+    let source = err.source().expect("ParseError should expose its source");
+    assert!(source.downcast_ref::<smart_uuid::uuid::Error>().is_some());
+
+    let err = "not-a-uuid".parse::<TypedUuid<UserType>>().unwrap_err();
+    assert!(matches!(err, TypedUuidError::ParseError(_)));
+    assert!(err.source().is_some());
+}
+
 #[test]
 fn user_friendly_uuid_parse_rejects_invalid_format() {
     // Missing underscore separator
@@ -174,6 +796,481 @@ fn user_friendly_uuid_parse_rejects_invalid_format() {
     assert!(matches!(result, Err(TypedUuidError::InvalidFormat(_))));
 }
 
+#[test]
+fn from_uuid_unchecked_trusts_valid_input() {
+    let typed = TypedUuid::new(UserType::Organization);
+    // SAFETY: the UUID came from a TypedUuid<UserType>
+    let unchecked = unsafe { TypedUuid::<UserType>::from_uuid_unchecked(typed.into_uuid()) };
+    assert_eq!(unchecked, typed);
+    assert_eq!(unchecked.variant_type(), UserType::Organization);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "invalid discriminant")]
+fn from_uuid_unchecked_checks_in_debug() {
+    let mut bytes = *TypedUuid::new(UserType::Retail).as_bytes();
+    bytes[0] = 200;
+    // SAFETY: deliberately violated to exercise the debug assertion
+    let _ = unsafe { TypedUuid::<UserType>::from_uuid_unchecked(Uuid::from_bytes(bytes)) };
+}
+
+#[test]
+fn try_parse_ascii_from_bytes() {
+    let typed = TypedUuid::new(UserType::Business);
+    let parsed = TypedUuid::<UserType>::try_parse_ascii(typed.to_string().as_bytes()).unwrap();
+    assert_eq!(parsed, typed);
+
+    let friendly = UserFriendlyUuid::from(typed);
+    let parsed = UserFriendlyUuid::<UserType>::try_parse_ascii(friendly.to_string().as_bytes()).unwrap();
+    assert_eq!(parsed, friendly);
+
+    let wrong = format!("retail_{}", typed);
+    let err = UserFriendlyUuid::<UserType>::try_parse_ascii(wrong.as_bytes()).unwrap_err();
+    assert!(err.is_unknown_prefix());
+
+    assert!(TypedUuid::<UserType>::try_parse_ascii(b"\xff\xfe").unwrap_err().is_parse());
+    assert!(UserFriendlyUuid::<UserType>::try_parse_ascii(b"no-underscore").unwrap_err().is_invalid_format());
+}
+
+#[test]
+fn parse_ref_borrows_the_input() {
+    let friendly = UserFriendlyUuid::new(UserType::Organization);
+    let input = friendly.simple().to_string();
+
+    let id = UserFriendlyUuid::<UserType>::parse_ref(&input).unwrap();
+    assert_eq!(id.prefix(), "org");
+    assert_eq!(id.body(), friendly.as_typed_uuid().simple().to_string());
+    assert!(std::ptr::eq(id.as_str(), input.as_str()));
+    assert_eq!(id.to_string(), input);
+    assert_eq!(id.variant_type(), UserType::Organization);
+    assert_eq!(id.to_owned_id(), friendly);
+
+    // Equality is by ID, not by spelling
+    let hyphenated = friendly.to_string();
+    assert_eq!(UserFriendlyUuid::<UserType>::parse_ref(&hyphenated).unwrap(), id);
+
+    let wrong = format!("retail_{}", friendly.as_typed_uuid());
+    assert!(UserFriendlyUuid::<UserType>::parse_ref(&wrong).unwrap_err().is_unknown_prefix());
+}
+
+// ============================================================================
+// Layout Tests
+// ============================================================================
+
+#[test]
+fn v4_compatible_ids_round_trip() {
+    use smart_uuid::Layout;
+
+    let id = TypedUuid::new_v4_compatible(UserType::Business);
+    assert_eq!(id.as_uuid().get_version_num(), 4);
+    assert_eq!(id.variant_type(), UserType::Business);
+    assert_eq!(id.layout(), Layout::V4Compatible);
+
+    let friendly = UserFriendlyUuid::from(id);
+    let parsed: UserFriendlyUuid<UserType> = friendly.to_string().parse().unwrap();
+    assert_eq!(parsed.into_typed_uuid(), id);
+    assert_eq!(TypedUuid::<UserType>::from_uuid(id.into_uuid()).unwrap(), id);
+}
+
+#[test]
+fn opaque_ids_round_trip() {
+    use smart_uuid::Layout;
+
+    for _ in 0..64 {
+        let id = TypedUuid::new_opaque(UserType::Retail);
+        assert_eq!(id.variant_type(), UserType::Retail);
+        assert_eq!(id.to_string().parse::<TypedUuid<UserType>>().unwrap(), id);
+        let bytes = id.as_bytes();
+        let expected = match (bytes[6] >> 4, bytes[8] >> 5) {
+            (8, 0b111) => Layout::TimeOrdered,
+            (8, 0b110) | (8, 0b100 | 0b101) => Layout::V8,
+            (4, 0b100 | 0b101) => Layout::V4Compatible,
+            (_, 0b100 | 0b101) => Layout::Other,
+            _ => Layout::Opaque,
+        };
+        assert_eq!(id.layout(), expected, "{}", id);
+    }
+
+    // Each rule on fixed bytes: version nibble, then variant field
+    let layout_of = |version: u8, variant: u8| {
+        let mut bytes = [0x11u8; 16];
+        bytes[0] = 0;
+        bytes[6] = version << 4;
+        bytes[8] = variant << 5;
+        TypedUuid::<UserType>::from_bytes(bytes).unwrap().layout()
+    };
+    assert_eq!(layout_of(8, 0b111), Layout::TimeOrdered);
+    assert_eq!(layout_of(8, 0b110), Layout::V8);
+    assert_eq!(layout_of(8, 0b100), Layout::V8);
+    assert_eq!(layout_of(4, 0b101), Layout::V4Compatible);
+    assert_eq!(layout_of(3, 0b100), Layout::Other);
+    assert_eq!(layout_of(4, 0b111), Layout::Opaque);
+    assert_eq!(layout_of(4, 0b110), Layout::Opaque);
+    assert_eq!(layout_of(8, 0b011), Layout::Opaque);
+    assert_eq!(layout_of(0, 0b000), Layout::Opaque);
+
+    // Version and variant bits are not forced, so they vary across IDs
+    let versions: std::collections::HashSet<_> =
+        (0..256).map(|_| TypedUuid::new_opaque(UserType::Retail).as_bytes()[6] >> 4).collect();
+    assert!(versions.len() > 1);
+}
+
+#[test]
+fn inspect_reports_variant_and_layout() {
+    use smart_uuid::Layout;
+
+    let inspection = TypedUuid::new(UserType::Organization).inspect();
+    assert_eq!(inspection.variant_type(), UserType::Organization);
+    assert_eq!(inspection.discriminant(), 2);
+    assert_eq!(inspection.layout(), Layout::V8);
+
+    let mut bytes = *Uuid::new_v5(&Uuid::NAMESPACE_OID, b"adopted").as_bytes();
+    bytes[0] = 0;
+    let adopted = TypedUuid::<UserType>::from_uuid(Uuid::from_bytes(bytes)).unwrap();
+    assert_eq!(adopted.inspect().layout(), Layout::Other);
+}
+
+#[test]
+fn random_ids_from_before_the_ordered_layout_are_not_time_ordered() {
+    use smart_uuid::Layout;
+    use std::time::UNIX_EPOCH;
+
+    // Built the way the first `TypedUuid::new` did: random bytes under `new_v8`, with
+    // bit 0x20 of byte 8 left random. Here it is set, as in about half of those IDs.
+    let mut bytes = [0xA5u8; 16];
+    bytes[0] = 1;
+    bytes[8] = 0x3C;
+    let legacy = TypedUuid::<UserType>::from_uuid(Uuid::new_v8(bytes)).unwrap();
+    assert_ne!(legacy.as_bytes()[8] & 0x20, 0);
+
+    assert_eq!(legacy.layout(), Layout::V8);
+    assert_eq!(legacy.timestamp_millis(), None);
+    assert_eq!(legacy.timestamp(), None);
+    assert_eq!(legacy.age(std::time::SystemTime::now()), None);
+    assert!(!legacy.created_after(UNIX_EPOCH));
+}
+
+#[test]
+fn ordered_ids_carry_their_timestamp() {
+    use smart_uuid::Layout;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let at = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+    let id = TypedUuid::new_ordered_at(UserType::Business, at);
+    assert_eq!(id.layout(), Layout::TimeOrdered);
+    assert_eq!(id.variant_type(), UserType::Business);
+    assert_eq!(id.timestamp_millis(), Some(1_700_000_000_123));
+    assert_eq!(id.timestamp(), Some(at));
+    assert_eq!(id.to_string().parse::<TypedUuid<UserType>>().unwrap(), id);
+
+    assert_eq!(TypedUuid::new(UserType::Business).timestamp_millis(), None);
+    assert_eq!(TypedUuid::new_v4_compatible(UserType::Business).timestamp_millis(), None);
+
+    let friendly = UserFriendlyUuid::from(id);
+    assert_eq!(friendly.timestamp_millis(), Some(1_700_000_000_123));
+    assert_eq!(friendly.timestamp(), Some(at));
+    assert_eq!(UserFriendlyUuid::new(UserType::Retail).timestamp(), None);
+}
+
+#[test]
+fn ordered_ids_sort_by_time() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let mut ids: Vec<_> = (0..50u64)
+        .map(|i| TypedUuid::new_ordered_at(UserType::Retail, UNIX_EPOCH + Duration::from_millis(1_000 + i * 300)))
+        .collect();
+    let expected = ids.clone();
+    ids.reverse();
+    ids.sort_by_key(|id| id.into_uuid());
+    assert_eq!(ids, expected);
+}
+
+#[test]
+fn ordered_friendly_ids_sort_by_time_within_a_variant() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let mut ids: Vec<_> = (0..20u64)
+        .map(|i| UserFriendlyUuid::new_ordered_at(UserType::Organization, UNIX_EPOCH + Duration::from_millis(5_000 + i * 7)))
+        .collect();
+    let expected = ids.clone();
+    ids.reverse();
+    ids.sort_by_key(|id| id.to_string());
+    assert_eq!(ids, expected);
+
+    let now = UserFriendlyUuid::new_ordered(UserType::Retail);
+    assert!(now.timestamp_millis().is_some());
+}
+
+#[test]
+fn ordered_ids_interop_with_uuid_timestamps() {
+    use std::cell::Cell;
+    use smart_uuid::uuid::{ClockSequence, Timestamp};
+
+    struct Counter(Cell<u16>);
+
+    impl ClockSequence for Counter {
+        type Output = u16;
+
+        fn generate_sequence(&self, _seconds: u64, _subsec_nanos: u32) -> u16 {
+            self.0.replace(self.0.get() + 1)
+        }
+
+        fn usable_bits(&self) -> usize {
+            14
+        }
+    }
+
+    let counter = Counter(Cell::new(0x3FFE));
+    let ids: Vec<_> = (0..2)
+        .map(|_| TypedUuid::new_ordered_from_timestamp(UserType::Organization, Timestamp::from_unix(&counter, 1_700_000_000, 123_456_789)))
+        .collect();
+
+    assert!(ids[0].as_uuid() < ids[1].as_uuid());
+    assert_eq!(ids[0].timestamp_millis(), Some(1_700_000_000_123));
+    let timestamp = ids[1].uuid_timestamp().unwrap();
+    assert_eq!(timestamp.to_unix(), (1_700_000_000, 123_000_000));
+    assert_eq!(timestamp.to_gregorian().1, 0x3FFF);
+
+    let now = TypedUuid::new_ordered_with_context(UserType::Organization, &counter);
+    assert_eq!(now.uuid_timestamp().unwrap().to_gregorian().1, 0);
+    assert_eq!(TypedUuid::new(UserType::Organization).uuid_timestamp(), None);
+}
+
+#[test]
+fn age_and_created_after() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let created = UNIX_EPOCH + Duration::from_secs(1_000);
+    let id = TypedUuid::new_ordered_at(UserType::Retail, created);
+
+    assert_eq!(id.age(created + Duration::from_secs(90)), Some(Duration::from_secs(90)));
+    assert_eq!(id.age(created - Duration::from_secs(5)), Some(Duration::ZERO));
+    assert!(id.created_after(created - Duration::from_millis(1)));
+    assert!(!id.created_after(created));
+
+    let random = TypedUuid::new(UserType::Retail);
+    assert_eq!(random.age(created), None);
+    assert!(!random.created_after(UNIX_EPOCH));
+}
+
+#[test]
+fn bounds_for_covers_exactly_the_range() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let t = |millis| UNIX_EPOCH + Duration::from_millis(millis);
+    let (low, high) = TypedUuid::bounds_for(UserType::Retail, t(10_000)..t(20_000));
+    let within = |id: TypedUuid<UserType>| (low..=high).contains(id.as_uuid());
+
+    assert!(within(TypedUuid::new_ordered_at(UserType::Retail, t(10_000))));
+    assert!(within(TypedUuid::new_ordered_at(UserType::Retail, t(19_999))));
+    assert!(!within(TypedUuid::new_ordered_at(UserType::Retail, t(9_999))));
+    assert!(!within(TypedUuid::new_ordered_at(UserType::Retail, t(20_000))));
+    assert!(!within(TypedUuid::new_ordered_at(UserType::Business, t(15_000))));
+
+    let (low, high) = TypedUuid::bounds_for(UserType::Retail, t(5)..t(5));
+    assert!(low > high);
+}
+
+// ============================================================================
+// Error Tests
+// ============================================================================
+
+#[test]
+fn error_kind_and_helpers() {
+    use smart_uuid::ErrorKind;
+
+    let typed = TypedUuid::new(UserType::Retail);
+
+    let parse = "retail_zzzzzzzz-e29b-41d4-a716-446655440000".parse::<UserFriendlyUuid<UserType>>().unwrap_err();
+    assert_eq!(parse.kind(), ErrorKind::Parse);
+    assert!(parse.is_parse());
+    assert!(!parse.is_unknown_prefix());
+
+    let prefix = format!("nope_{}", typed).parse::<UserFriendlyUuid<UserType>>().unwrap_err();
+    assert_eq!(prefix.kind(), ErrorKind::UnknownPrefix);
+    assert!(prefix.is_unknown_prefix());
+
+    let format = "nope".parse::<UserFriendlyUuid<UserType>>().unwrap_err();
+    assert!(format.is_invalid_format());
+
+    let mut bytes = *typed.as_bytes();
+    bytes[0] = 200;
+    let discriminant = TypedUuid::<UserType>::try_from(bytes).unwrap_err();
+    assert!(discriminant.is_invalid_discriminant());
+}
+
+#[test]
+fn parse_rejects_oversized_input_early() {
+    let uuid = TypedUuid::new(UserType::Retail).to_string();
+
+    let huge = format!("{}_{}", "a".repeat(4 * 1024 * 1024), uuid);
+    assert!(huge.parse::<UserFriendlyUuid<UserType>>().unwrap_err().is_invalid_format());
+    assert!(UserFriendlyUuid::<UserType>::try_parse_ascii(huge.as_bytes()).unwrap_err().is_invalid_format());
+
+    let long_prefix = format!("{}_{}", "a".repeat(smart_uuid::MAX_PREFIX_LEN + 1), uuid);
+    assert!(long_prefix.parse::<UserFriendlyUuid<UserType>>().unwrap_err().is_invalid_format());
+
+    let short_body = format!("retail_{}", &uuid[..35]);
+    assert!(short_body.parse::<UserFriendlyUuid<UserType>>().unwrap_err().is_invalid_format());
+}
+
+#[test]
+fn parse_fuzz_regressions() {
+    // Inputs that have broken prefix parsers before; none may panic
+    let cases = [
+        "",
+        "_",
+        "__",
+        "retail_",
+        "_00000000-0000-8000-8000-000000000000",
+        "retail__00000000-0000-8000-8000-000000000000",
+        "retail_00000000-0000-8000-8000-000000000000_",
+        "é_00000000-0000-8000-8000-000000000000",
+        "retail_0000000é-0000-8000-8000-000000000000",
+        "retail_{00000000-0000-8000-8000-000000000000",
+        "retail_urn:uuid:00000000-0000-8000-8000-00000000000",
+        "\0\0\0_\0",
+    ];
+    for case in cases {
+        assert!(case.parse::<UserFriendlyUuid<UserType>>().is_err(), "accepted {:?}", case);
+        assert!(UserFriendlyUuid::<UserType>::try_parse_ascii(case.as_bytes()).is_err(), "accepted {:?}", case);
+    }
+}
+
+#[test]
+fn typed_uuid_parse_explains_friendly_input() {
+    let friendly = UserFriendlyUuid::new(UserType::Business);
+
+    let err = friendly.to_string().parse::<TypedUuid<UserType>>().unwrap_err();
+    assert_eq!(err, TypedUuidError::UnexpectedPrefix { prefix: "business".to_string() });
+    assert!(err.to_string().contains("UserFriendlyUuid::parse_str"));
+
+    // Garbage with an underscore is still a plain parse error
+    assert!("retail_nope".parse::<TypedUuid<UserType>>().unwrap_err().is_parse());
+}
+
+#[test]
+fn typed_uuid_parse_lenient_accepts_both_forms() {
+    let friendly = UserFriendlyUuid::new(UserType::Organization);
+    let typed = friendly.into_typed_uuid();
+
+    assert_eq!(TypedUuid::<UserType>::parse_lenient(&friendly.to_string()).unwrap(), typed);
+    assert_eq!(TypedUuid::<UserType>::parse_lenient(&typed.to_string()).unwrap(), typed);
+
+    let wrong = format!("retail_{}", typed);
+    assert!(TypedUuid::<UserType>::parse_lenient(&wrong).unwrap_err().is_unknown_prefix());
+}
+
+#[test]
+fn from_prefix_finds_variant() {
+    assert_eq!(UserType::from_prefix("org"), Some(UserType::Organization));
+    assert_eq!(UserType::from_prefix("business"), Some(UserType::Business));
+    assert_eq!(UserType::from_prefix("organization"), None);
+}
+
+#[test]
+fn parse_prefix_authoritative_accepts_plain_uuids() {
+    let v4 = Uuid::new_v4();
+    let parsed = UserFriendlyUuid::<UserType>::parse_prefix_authoritative(&format!("org_{}", v4)).unwrap();
+    assert_eq!(parsed.variant_type(), UserType::Organization);
+    assert_eq!(*parsed.as_uuid(), v4);
+    assert_eq!(parsed.discriminant_matched(), v4.as_bytes()[0] == 2);
+
+    let friendly = UserFriendlyUuid::new(UserType::Business);
+    let parsed = UserFriendlyUuid::<UserType>::parse_prefix_authoritative(&friendly.to_string()).unwrap();
+    assert!(parsed.discriminant_matched());
+    assert_eq!(parsed.to_typed_uuid(), Some(friendly.into_typed_uuid()));
+    assert_eq!(parsed.to_string(), friendly.to_string());
+
+    let err = UserFriendlyUuid::<UserType>::parse_prefix_authoritative(&format!("user_{}", v4)).unwrap_err();
+    assert!(err.is_unknown_prefix());
+}
+
+#[test]
+fn generate_batch_makes_distinct_ids_of_the_variant() {
+    // Spans several RNG chunks and ends partway through one
+    let ids = TypedUuid::generate_batch(UserType::Business, 1_000);
+    assert_eq!(ids.len(), 1_000);
+    assert!(ids.iter().all(|id| id.is_variant(UserType::Business) && id.layout() == smart_uuid::Layout::V8));
+    assert_eq!(ids.iter().collect::<std::collections::HashSet<_>>().len(), 1_000);
+
+    assert!(TypedUuid::generate_batch(UserType::Retail, 0).is_empty());
+    let streamed: Vec<_> = TypedUuid::generate_iter(UserType::Organization).take(130).collect();
+    assert_eq!(streamed.iter().collect::<std::collections::HashSet<_>>().len(), 130);
+}
+
+#[test]
+fn parse_all_returns_ids_in_order() {
+    let ids: Vec<_> = (0..3).map(|_| UserFriendlyUuid::new(UserType::Retail)).collect();
+    let inputs: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+
+    assert_eq!(smart_uuid::parse_all::<UserType, _, _>(&inputs).unwrap(), ids);
+}
+
+#[test]
+fn parse_all_reports_every_failure() {
+    let good = UserFriendlyUuid::new(UserType::Business).to_string();
+    let wrong_prefix = format!("retail_{}", UserFriendlyUuid::new(UserType::Business).as_typed_uuid());
+    let inputs = [good.as_str(), "nope", wrong_prefix.as_str(), good.as_str()];
+
+    let err = smart_uuid::parse_all::<UserType, _, _>(inputs).unwrap_err();
+    assert_eq!(err.total(), 4);
+
+    let failures = err.failures();
+    assert_eq!(failures.len(), 2);
+    assert_eq!(failures[0].index, 1);
+    assert_eq!(failures[0].input, "nope");
+    assert!(failures[0].error.is_invalid_format());
+    assert_eq!(failures[1].index, 2);
+    assert!(failures[1].error.is_unknown_prefix());
+
+    assert!(err.to_string().starts_with("2 of 4 inputs failed to parse; first at index 1 ('nope')"));
+}
+
+#[test]
+fn parse_allowed_rejects_other_variants() {
+    let org = UserFriendlyUuid::new(UserType::Organization);
+    let retail = UserFriendlyUuid::new(UserType::Retail);
+    let allowed = [UserType::Business, UserType::Organization];
+
+    assert_eq!(UserFriendlyUuid::parse_allowed(&org.to_string(), &allowed).unwrap(), org);
+    assert_eq!(
+        TypedUuid::parse_allowed(&org.as_typed_uuid().to_string(), &allowed).unwrap(),
+        *org.as_typed_uuid()
+    );
+
+    let err = UserFriendlyUuid::parse_allowed(&retail.to_string(), &allowed).unwrap_err();
+    assert!(err.is_variant_not_allowed());
+    assert!(matches!(
+        &err,
+        TypedUuidError::VariantNotAllowed { found: "retail", allowed, .. } if allowed == &["business", "org"]
+    ));
+    assert!(err.to_string().ends_with("are not allowed here, expected one of: business, org"));
+    assert!(TypedUuid::parse_allowed(&retail.as_typed_uuid().to_string(), &allowed)
+        .unwrap_err()
+        .is_variant_not_allowed());
+
+    // Syntax errors are reported as before
+    assert!(UserFriendlyUuid::<UserType>::parse_allowed("org_nope", &allowed).unwrap_err().is_invalid_format());
+}
+
+#[test]
+fn error_codes_are_stable() {
+    use smart_uuid::ErrorKind;
+
+    assert_eq!(ErrorKind::InvalidDiscriminant.code(), 1);
+    assert_eq!(ErrorKind::Parse.code(), 2);
+    assert_eq!(ErrorKind::UnknownPrefix.code(), 3);
+    assert_eq!(ErrorKind::InvalidFormat.code(), 4);
+    assert_eq!(ErrorKind::UnexpectedPrefix.code(), 5);
+    assert_eq!(ErrorKind::VariantNotAllowed.code(), 7);
+    assert_eq!(ErrorKind::UnknownPrefix.name(), "unknown_prefix");
+
+    let err = "nope".parse::<UserFriendlyUuid<UserType>>().unwrap_err();
+    assert_eq!(err.code(), 4);
+}
+
 // ============================================================================
 // Conversion Tests
 // ============================================================================
@@ -207,6 +1304,141 @@ fn roundtrip_typed_to_friendly_and_back() {
     assert_eq!(back.variant_type(), UserType::Organization);
 }
 
+#[test]
+fn typed_uuid_as_ref_uuid() {
+    fn takes_uuid(id: impl AsRef<Uuid>) -> Uuid {
+        *id.as_ref()
+    }
+
+    let typed = TypedUuid::new(UserType::Retail);
+    assert_eq!(takes_uuid(typed), *typed.as_uuid());
+}
+
+#[test]
+fn typed_uuid_map_lookup_by_plain_uuid() {
+    use std::collections::{HashMap, HashSet};
+
+    let typed = TypedUuid::new(UserType::Business);
+    let plain = typed.into_uuid();
+
+    let mut names = HashMap::new();
+    names.insert(typed, "acme");
+    assert_eq!(names.get(&plain), Some(&"acme"));
+
+    let set: HashSet<UserFriendlyUuid<UserType>> = [typed.into()].into_iter().collect();
+    assert!(set.contains(&UserFriendlyUuid::from(typed)));
+}
+
+#[test]
+fn typed_uuid_derefs_to_uuid() {
+    let typed = TypedUuid::new(UserType::Retail);
+
+    assert_eq!(typed.as_bytes(), typed.as_uuid().as_bytes());
+    assert_eq!(typed.get_version_num(), 8);
+}
+
+#[test]
+fn user_friendly_as_ref_and_map_lookup() {
+    use std::collections::HashMap;
+
+    fn takes_uuid(id: impl AsRef<Uuid>) -> Uuid {
+        *id.as_ref()
+    }
+
+    let typed = TypedUuid::new(UserType::Business);
+    let friendly = UserFriendlyUuid::from(typed);
+    assert_eq!(takes_uuid(friendly), typed.into_uuid());
+
+    let mut names = HashMap::new();
+    names.insert(friendly, "acme");
+    assert_eq!(names.get(&typed.into_uuid()), Some(&"acme"));
+    assert_eq!(names.get(&typed), Some(&"acme"));
+}
+
+#[test]
+fn typed_ids_convert_into_strings() {
+    fn takes_into_string(s: impl Into<String>) -> String {
+        s.into()
+    }
+
+    let typed = TypedUuid::new(UserType::Retail);
+    let friendly = UserFriendlyUuid::from(typed);
+
+    assert_eq!(takes_into_string(typed), typed.to_string());
+    assert_eq!(takes_into_string(friendly), friendly.to_string());
+    assert_eq!(typed.to_cow(), typed.to_string());
+    assert_eq!(friendly.to_cow(), friendly.to_string());
+}
+
+// ============================================================================
+// TryFrom Conversion Tests
+// ============================================================================
+
+#[test]
+fn typed_uuid_try_from_strings() {
+    let original = TypedUuid::new(UserType::Business);
+
+    let from_str = TypedUuid::<UserType>::try_from(original.to_string().as_str()).unwrap();
+    let from_string = TypedUuid::<UserType>::try_from(original.to_string()).unwrap();
+
+    assert_eq!(from_str, original);
+    assert_eq!(from_string, original);
+    assert!(TypedUuid::<UserType>::try_from("not-a-uuid").is_err());
+}
+
+#[test]
+fn typed_uuid_try_from_bytes() {
+    let original = TypedUuid::new(UserType::Organization);
+    let bytes = *original.as_bytes();
+
+    assert_eq!(TypedUuid::<UserType>::try_from(bytes).unwrap(), original);
+    assert_eq!(TypedUuid::<UserType>::try_from(&bytes[..]).unwrap(), original);
+    assert!(matches!(
+        TypedUuid::<UserType>::try_from(&bytes[..15]),
+        Err(TypedUuidError::ParseError(_))
+    ));
+
+    let mut invalid = bytes;
+    invalid[0] = 255;
+    assert!(matches!(
+        TypedUuid::<UserType>::try_from(invalid),
+        Err(TypedUuidError::InvalidDiscriminant { found: 255, .. })
+    ));
+}
+
+#[test]
+fn ids_compare_with_plain_uuids_and_strings() {
+    let id = UserFriendlyUuid::<UserType>::parse_str("org_02123456-789a-8bcd-8ef0-123456789abc").unwrap();
+    let uuid = *id.as_typed_uuid().as_uuid();
+    assert!(*id.as_typed_uuid() == uuid && uuid == *id.as_typed_uuid());
+    assert!(*id.as_typed_uuid() != Uuid::nil());
+
+    assert_eq!(id, "org_02123456-789a-8bcd-8ef0-123456789abc");
+    assert_eq!("org_02123456-789a-8bcd-8ef0-123456789abc", id);
+    assert!(id == *"org_02123456-789a-8bcd-8ef0-123456789abc");
+    // Only the canonical spelling is equal
+    for other in [
+        "org_02123456-789A-8BCD-8EF0-123456789ABC",
+        "org_02123456789a8bcd8ef0123456789abc",
+        "business_02123456-789a-8bcd-8ef0-123456789abc",
+        "org02123456-789a-8bcd-8ef0-123456789abc",
+        "org_",
+    ] {
+        assert_ne!(id, other);
+    }
+}
+
+#[test]
+fn user_friendly_uuid_try_from_strings() {
+    let original = UserFriendlyUuid::new(UserType::Retail);
+
+    let from_str = UserFriendlyUuid::<UserType>::try_from(original.to_string().as_str()).unwrap();
+    let from_string = UserFriendlyUuid::<UserType>::try_from(original.to_string()).unwrap();
+
+    assert_eq!(from_str, original);
+    assert_eq!(from_string, original);
+}
+
 // ============================================================================
 // Serde Tests
 // ============================================================================
@@ -243,3 +1475,75 @@ fn user_friendly_uuid_serializes_as_prefixed_string() {
     assert!(json.starts_with("\"org_"));
     assert!(json.ends_with("\""));
 }
+
+// ============================================================================
+// Fixture Factory Tests
+// ============================================================================
+
+#[test]
+fn fixture_factory_counts_per_variant() {
+    let mut fixtures = FixtureFactory::new();
+
+    let first = fixtures.next_friendly(UserType::Retail);
+    let second = fixtures.next_friendly(UserType::Retail);
+    let business = fixtures.next_friendly(UserType::Business);
+
+    assert_eq!(first.to_string(), "retail_00000000-0000-8000-8000-000000000001");
+    assert_eq!(second.to_string(), "retail_00000000-0000-8000-8000-000000000002");
+    assert_eq!(business.to_string(), "business_01000000-0000-8000-8000-000000000001");
+    assert_eq!(fixtures.count(UserType::Retail), 2);
+    assert_eq!(fixtures.count(UserType::Organization), 0);
+}
+
+#[test]
+fn fixture_factory_ids_are_valid_and_stable() {
+    let mut fixtures = FixtureFactory::new();
+    let id = fixtures.next(UserType::Organization);
+
+    assert_eq!(id, FixtureFactory::fixture(UserType::Organization, 1));
+    assert_eq!(id.as_uuid().get_version_num(), 8);
+
+    let parsed: TypedUuid<UserType> = id.to_string().parse().unwrap();
+    assert_eq!(parsed, id);
+
+    fixtures.reset();
+    assert_eq!(fixtures.next(UserType::Organization), id);
+}
+
+// ============================================================================
+// Assertion Macro Tests
+// ============================================================================
+
+fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> String {
+    let err = std::panic::catch_unwind(f).unwrap_err();
+    err.downcast_ref::<String>().cloned().unwrap_or_default()
+}
+
+#[test]
+fn assert_variant_passes_for_matching_variant() {
+    let typed = TypedUuid::new(UserType::Business);
+    assert_variant!(typed, UserType::Business);
+    assert_variant!(UserFriendlyUuid::from(typed), UserType::Business, "friendly form");
+}
+
+#[test]
+fn assert_variant_reports_expected_and_actual() {
+    let id = FixtureFactory::fixture(UserType::Retail, 7);
+    let message = panic_message(move || assert_variant!(id, UserType::Organization));
+
+    assert!(message.contains("expected variant: Organization (prefix `org`)"));
+    assert!(message.contains("actual variant: Retail (prefix `retail`)"));
+    assert!(message.contains("00000000-0000-8000-8000-000000000007"));
+}
+
+#[test]
+fn assert_same_id_reports_both_sides() {
+    let left = UserFriendlyUuid::from(FixtureFactory::fixture(UserType::Retail, 1));
+    let right = UserFriendlyUuid::from(FixtureFactory::fixture(UserType::Business, 1));
+    assert_same_id!(left, left);
+
+    let message = panic_message(move || assert_same_id!(left, right, "ids differ"));
+    assert!(message.contains("ids differ"));
+    assert!(message.contains("left: retail_00000000-0000-8000-8000-000000000001 (Retail, prefix `retail`)"));
+    assert!(message.contains("right: business_01000000-0000-8000-8000-000000000001 (Business, prefix `business`)"));
+}
@@ -0,0 +1,54 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use smart_uuid::{TaggedV7, TypedUuid, TypedUuidError, UuidType};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum RowType {
+    #[uuid_type(prefix = "row")]
+    Row,
+    #[uuid_type(prefix = "batch")]
+    Batch,
+}
+
+// 2024-11-14T18:55:19.496Z, as returned by uuidv7()
+const V7: &str = "01932c07-a7c8-7b3e-9f1a-3c5d7e9f1b2d";
+
+#[test]
+fn wrap_keeps_v7_timestamp_and_version() {
+    let v7 = Uuid::parse_str(V7).unwrap();
+    let tagged = TypedUuid::wrap_v7(RowType::Batch, v7).unwrap();
+
+    assert_eq!(tagged.variant_type(), RowType::Batch);
+    assert_eq!(tagged.as_uuid().get_version_num(), 7);
+    assert_eq!(tagged.as_uuid().as_bytes()[..15], v7.as_bytes()[..15]);
+    assert_eq!(tagged.timestamp(), UNIX_EPOCH + Duration::from_millis(0x01932c07a7c8));
+
+    assert_eq!(TaggedV7::<RowType>::from_uuid(tagged.into_uuid()).unwrap(), tagged);
+}
+
+#[test]
+fn displays_and_parses_with_prefix() {
+    let tagged = TypedUuid::wrap_v7(RowType::Row, Uuid::parse_str(V7).unwrap()).unwrap();
+    let s = tagged.to_string();
+    assert!(s.starts_with("row_01932c07-a7c8-7b3e-9f1a-3c5d7e9f1b"), "{}", s);
+    assert_eq!(s.parse::<TaggedV7<RowType>>().unwrap(), tagged);
+
+    let wrong_prefix = s.replacen("row", "batch", 1);
+    assert!(wrong_prefix.parse::<TaggedV7<RowType>>().unwrap_err().is_unknown_prefix());
+}
+
+#[test]
+fn rejects_non_v7_and_untagged_uuids() {
+    let v4 = Uuid::new_v4();
+    assert!(matches!(
+        TypedUuid::wrap_v7(RowType::Row, v4),
+        Err(TypedUuidError::InvalidFormat(_))
+    ));
+
+    let mut bytes = Uuid::parse_str(V7).unwrap().into_bytes();
+    bytes[15] = 0xFF;
+    assert!(TaggedV7::<RowType>::from_uuid(Uuid::from_bytes(bytes))
+        .unwrap_err()
+        .is_invalid_discriminant());
+}
@@ -0,0 +1,93 @@
+//! Tests for the `validator` and `garde` features.
+//! Run with: cargo test -p smart_uuid --features validator,garde
+
+#![cfg(any(feature = "validator", feature = "garde"))]
+
+use smart_uuid::{TypedUuid, UserFriendlyUuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+fn friendly() -> String {
+    UserFriendlyUuid::from(TypedUuid::new(UserType::Organization)).to_string()
+}
+
+#[cfg(feature = "validator")]
+mod with_validator {
+    use super::*;
+    use validator::Validate;
+
+    #[derive(Validate)]
+    struct CreateOrder {
+        #[validate(custom(function = smart_uuid::validate::validator::friendly_uuid::<UserType>))]
+        customer_id: String,
+        #[validate(custom(function = smart_uuid::validate::validator::typed_uuid::<UserType>))]
+        account_id: String,
+    }
+
+    #[test]
+    fn accepts_valid_ids() {
+        let order = CreateOrder {
+            customer_id: friendly(),
+            account_id: TypedUuid::new(UserType::Retail).to_string(),
+        };
+        assert!(order.validate().is_ok());
+    }
+
+    #[test]
+    fn reports_the_parser_error_kind() {
+        let order = CreateOrder {
+            customer_id: friendly().replacen("org", "admin", 1),
+            account_id: friendly(),
+        };
+        let errors = order.validate().unwrap_err();
+        let fields = errors.field_errors();
+
+        let customer = &fields["customer_id"][0];
+        assert_eq!(customer.code, "smart_uuid");
+        assert_eq!(customer.params["kind"], "unknown_prefix");
+        assert!(customer.message.as_ref().unwrap().contains("unknown prefix 'admin'"));
+
+        // The bare-UUID rules reject the user-friendly form
+        assert_eq!(fields["account_id"][0].params["kind"], "unexpected_prefix");
+    }
+}
+
+#[cfg(feature = "garde")]
+mod with_garde {
+    use super::*;
+    use garde::Validate;
+
+    #[derive(Validate)]
+    struct CreateOrder {
+        #[garde(custom(smart_uuid::validate::garde::friendly_uuid::<UserType>))]
+        customer_id: String,
+        #[garde(custom(smart_uuid::validate::garde::typed_uuid::<UserType>))]
+        account_id: String,
+    }
+
+    #[test]
+    fn accepts_valid_ids() {
+        let order = CreateOrder {
+            customer_id: friendly(),
+            account_id: TypedUuid::new(UserType::Retail).to_string(),
+        };
+        assert!(order.validate().is_ok());
+    }
+
+    #[test]
+    fn reports_the_parser_message() {
+        let order = CreateOrder {
+            customer_id: "org_not-a-uuid".to_string(),
+            account_id: TypedUuid::new(UserType::Retail).to_string(),
+        };
+        let report = order.validate().unwrap_err();
+        let (path, error) = report.iter().next().unwrap();
+        assert_eq!(path.to_string(), "customer_id");
+        assert!(error.message().starts_with("invalid format"), "{}", error.message());
+    }
+}
@@ -0,0 +1,78 @@
+//! Tests for `VariantUuid`, the typed ID restricted to one variant.
+
+use std::collections::HashSet;
+
+use smart_uuid::{TypedUuid, TypedUuidError, UserFriendlyUuid, Uuid, UuidType, VariantUuid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(variant_ids)]
+enum UserType {
+    Retail,
+    Business,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+#[test]
+fn new_ids_hold_the_variant() {
+    let id = UserTypeOrganizationId::new();
+    assert_eq!(id.variant_type(), UserType::Organization);
+    assert_eq!(id.as_uuid().as_bytes()[0], 2);
+    assert_eq!(UserTypeOrganizationId::prefix(), "org");
+    assert_ne!(id, UserTypeOrganizationId::new());
+}
+
+#[test]
+fn aliases_name_the_generic_type() {
+    let id: VariantUuid<UserType, 1> = UserTypeBusinessId::new();
+    assert_eq!(UserTypeBusinessId::variant(), UserType::Business);
+    assert_eq!(id.variant_type(), UserType::Business);
+}
+
+#[test]
+fn narrows_typed_ids_of_the_variant_only() {
+    let typed = TypedUuid::new(UserType::Retail);
+    let retail = UserTypeRetailId::from_typed(typed).unwrap();
+    assert_eq!(retail, typed);
+    assert_eq!(TypedUuid::from(retail), typed);
+    assert_eq!(UserTypeRetailId::try_from(typed.into_uuid()).unwrap(), retail);
+
+    let err = UserTypeBusinessId::try_from(typed).unwrap_err();
+    assert!(err.is_variant_not_allowed());
+    assert!(matches!(
+        err,
+        TypedUuidError::VariantNotAllowed { found: "retail", ref allowed, .. } if allowed == &["business"]
+    ));
+
+    let friendly = UserFriendlyUuid::from(typed);
+    assert_eq!(UserTypeRetailId::try_from(friendly).unwrap(), retail);
+    assert_eq!(UserFriendlyUuid::from(retail), friendly);
+}
+
+#[test]
+fn displays_parses_and_serializes_like_typed_uuid() {
+    let id = UserTypeRetailId::new();
+    let typed = id.into_typed_uuid();
+
+    assert_eq!(id.to_string(), typed.to_string());
+    assert_eq!(id.to_string().parse::<UserTypeRetailId>().unwrap(), id);
+    assert!(typed.to_string().parse::<UserTypeBusinessId>().unwrap_err().is_variant_not_allowed());
+
+    let json = serde_json::to_string(&id).unwrap();
+    assert_eq!(json, serde_json::to_string(&typed).unwrap());
+    assert_eq!(serde_json::from_str::<UserTypeRetailId>(&json).unwrap(), id);
+    assert!(serde_json::from_str::<UserTypeOrganizationId>(&json).is_err());
+}
+
+#[test]
+fn hashes_and_converts_like_the_uuid() {
+    fn takes_uuid(id: impl AsRef<Uuid>) -> Uuid {
+        *id.as_ref()
+    }
+
+    let id = UserTypeRetailId::new();
+    assert_eq!(takes_uuid(id), Uuid::from(id));
+
+    let set: HashSet<_> = [id, id].into_iter().collect();
+    assert_eq!(set.len(), 1);
+}
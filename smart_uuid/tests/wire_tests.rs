@@ -0,0 +1,52 @@
+use smart_uuid::{TypedUuid, Uuid, UuidType, WIRE_FORMAT_VERSION};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+const GOLDEN: &str = "01123456-789a-8bcd-8ef0-123456789abc";
+
+#[test]
+fn wire_format_v1_is_the_rfc_bytes() {
+    assert_eq!(WIRE_FORMAT_VERSION, 1);
+
+    // Pinned: changing these bytes breaks deployed decoders
+    let typed = TypedUuid::<UserType>::from_uuid(Uuid::parse_str(GOLDEN).unwrap()).unwrap();
+    assert_eq!(
+        typed.to_wire_bytes(),
+        [0x01, 0x12, 0x34, 0x56, 0x78, 0x9a, 0x8b, 0xcd, 0x8e, 0xf0, 0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc]
+    );
+    assert_eq!(TypedUuid::from_wire_bytes(typed.to_wire_bytes()).unwrap(), typed);
+}
+
+#[test]
+fn from_wire_bytes_validates_the_discriminant() {
+    let mut bytes = TypedUuid::new(UserType::Retail).to_wire_bytes();
+    bytes[0] = 0xff;
+    assert!(TypedUuid::<UserType>::from_wire_bytes(bytes).unwrap_err().is_invalid_discriminant());
+}
+
+#[test]
+fn wire_bytes_round_trip_through_postcard() {
+    let typed = TypedUuid::new(UserType::Organization);
+    let encoded = postcard::to_allocvec(&typed.to_wire_bytes()).unwrap();
+
+    // Fixed-size arrays are written as-is, with no length prefix
+    assert_eq!(encoded, typed.to_wire_bytes());
+
+    let decoded: [u8; 16] = postcard::from_bytes(&encoded).unwrap();
+    assert_eq!(TypedUuid::<UserType>::from_wire_bytes(decoded).unwrap(), typed);
+}
+
+#[test]
+fn wire_bytes_round_trip_through_bincode() {
+    let typed = TypedUuid::new(UserType::Retail);
+    let encoded = bincode::serialize(&typed.to_wire_bytes()).unwrap();
+    assert_eq!(encoded, typed.to_wire_bytes());
+
+    let decoded: [u8; 16] = bincode::deserialize(&encoded).unwrap();
+    assert_eq!(TypedUuid::<UserType>::from_wire_bytes(decoded).unwrap(), typed);
+}
@@ -0,0 +1,65 @@
+//! Tests for zero-copy slice casts and the `bytemuck` and `zerocopy` features.
+//! Run with: cargo test -p smart_uuid --features bytemuck
+//! and: RUSTFLAGS="--cfg uuid_unstable" cargo test -p smart_uuid --features zerocopy
+
+use smart_uuid::{TypedUuid, Uuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum UserType {
+    Retail,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+fn ids() -> Vec<TypedUuid<UserType>> {
+    vec![TypedUuid::new(UserType::Retail), TypedUuid::new(UserType::Organization)]
+}
+
+#[test]
+fn has_the_layout_of_uuid() {
+    assert_eq!(std::mem::size_of::<TypedUuid<UserType>>(), std::mem::size_of::<Uuid>());
+    assert_eq!(std::mem::align_of::<TypedUuid<UserType>>(), std::mem::align_of::<Uuid>());
+    assert_eq!(std::mem::size_of_val(ids().as_slice()), 32);
+}
+
+#[test]
+fn casts_slices_without_copying() {
+    let ids = ids();
+
+    let uuids = TypedUuid::as_uuid_slice(&ids);
+    assert_eq!(uuids.as_ptr() as usize, ids.as_ptr() as usize);
+    assert_eq!(uuids, [*ids[0].as_uuid(), *ids[1].as_uuid()]);
+
+    let bytes = TypedUuid::as_byte_slice(&ids);
+    assert_eq!(bytes.as_ptr() as usize, ids.as_ptr() as usize);
+    assert_eq!(bytes, [*ids[0].as_bytes(), *ids[1].as_bytes()]);
+
+    assert!(TypedUuid::<UserType>::as_uuid_slice(&[]).is_empty());
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn bytemuck_casts_slices_without_copying() {
+    use bytemuck::TransparentWrapper;
+
+    let ids = ids();
+
+    let uuids: &[Uuid] = TypedUuid::peel_slice(&ids);
+    assert_eq!(uuids.as_ptr() as usize, ids.as_ptr() as usize);
+    assert_eq!(uuids[1], *ids[1].as_uuid());
+
+    let bytes: &[[u8; 16]] = bytemuck::cast_slice(&ids);
+    assert_eq!(bytes[0], *ids[0].as_bytes());
+    assert_eq!(bytemuck::cast_slice::<_, u8>(&ids).len(), 32);
+}
+
+#[cfg(all(feature = "zerocopy", uuid_unstable))]
+#[test]
+fn zerocopy_views_ids_as_bytes() {
+    use zerocopy::IntoBytes;
+
+    let ids = ids();
+    let bytes = ids.as_slice().as_bytes();
+    assert_eq!(bytes.len(), 32);
+    assert_eq!(&bytes[16..], ids[1].as_bytes());
+}
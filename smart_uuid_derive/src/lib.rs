@@ -1,6 +1,6 @@
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{parse_macro_input, Data, DeriveInput, Fields};
 
 /// Derive macro for implementing the `UuidType` trait.
@@ -9,6 +9,26 @@ use syn::{parse_macro_input, Data, DeriveInput, Fields};
 /// - `discriminant()` - returns a unique byte for each variant (0, 1, 2, ...)
 /// - `from_discriminant()` - reconstructs the variant from a byte
 /// - `prefix()` - returns a snake_case string prefix for the variant
+/// - `from_prefix()` - looks up the variant using a prefix
+/// - `prefixes()` - returns every variant's prefix, in discriminant order
+/// - `type_info()` - returns static metadata (enum name, variant name, prefix)
+///
+/// Adding `#[uuid_type(register)]` to the enum also submits it for
+/// `UuidRegistry::from_registered()` (requires the `inventory` feature of `smart_uuid`).
+/// `#[uuid_type(migrator = PATH)]` opts the enum into schema versioning with the given
+/// `smart_uuid::Migrator`. `#[uuid_type(format = EXPR)]` fixes how the enum's
+/// user-friendly IDs display, overriding the process-wide `smart_uuid::FormatConfig`.
+/// `#[uuid_type(id_space = "...")]` names the ID space the enum shares with others, whose
+/// discriminants `UuidRegistry::verify()` checks for overlap.
+/// `#[uuid_type(variant_ids)]` also emits a type alias per variant naming its
+/// `smart_uuid::VariantUuid`, e.g. `UserTypeRetailId` for `UserType::Retail`, with the
+/// enum's visibility.
+///
+/// By default discriminants follow declaration order, so reordering variants changes
+/// the meaning of stored IDs. `#[uuid_type(discriminant = "hash")]` derives each
+/// discriminant from the variant's prefix instead (32-bit FNV-1a, XOR-folded to a
+/// byte), making them independent of order; two prefixes hashing to the same byte
+/// fail to compile.
 ///
 /// # Example
 /// ```ignore
@@ -29,6 +49,88 @@ pub fn derive_uuid_type(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+/// Attribute form of `#[derive(UuidType)]`, for enums where the derive cannot be used,
+/// e.g. ones emitted by other macros.
+///
+/// Takes the same enum-level options as arguments and the same
+/// `#[uuid_type(prefix = "...")]` on variants. It implements `UuidType` like the
+/// derive, and also derives the traits `UuidType` requires (`Debug`, `Clone`, `Copy`,
+/// `PartialEq`, `Eq`) unless the enum already derives them below the attribute. Put it
+/// above any `#[derive(...)]` so it can see them.
+///
+/// # Example
+/// ```ignore
+/// #[uuid_type(discriminant = "hash")]
+/// enum UserType {
+///     Retail,
+///     #[uuid_type(prefix = "org")]
+///     Organization,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn uuid_type(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = TokenStream2::from(args);
+    let mut input = parse_macro_input!(input as DeriveInput);
+
+    // Treat the arguments as one more enum-level attribute, so the derive's parsing
+    // and checks apply unchanged
+    if !args.is_empty() {
+        input.attrs.push(syn::parse_quote!(#[uuid_type(#args)]));
+    }
+    let implementation = impl_uuid_type(&input);
+
+    // No derive registers `uuid_type` as a helper attribute here, so remove every use
+    input.attrs.retain(|attr| !attr.path().is_ident("uuid_type"));
+    if let Data::Enum(data) = &mut input.data {
+        for variant in data.variants.iter_mut() {
+            variant.attrs.retain(|attr| !attr.path().is_ident("uuid_type"));
+        }
+    }
+
+    let derived = match derived_traits(&input.attrs) {
+        Ok(derived) => derived,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let required = [
+        ("Debug", quote! { ::core::fmt::Debug }),
+        ("Clone", quote! { ::core::clone::Clone }),
+        ("Copy", quote! { ::core::marker::Copy }),
+        ("PartialEq", quote! { ::core::cmp::PartialEq }),
+        ("Eq", quote! { ::core::cmp::Eq }),
+    ];
+    let missing: Vec<_> = required
+        .into_iter()
+        .filter(|(name, _)| !derived.iter().any(|d| d == name))
+        .map(|(_, path)| path)
+        .collect();
+    let derives = (!missing.is_empty()).then(|| quote! { #[derive(#(#missing),*)] });
+
+    TokenStream::from(quote! {
+        #derives
+        #input
+        #implementation
+    })
+}
+
+/// Returns the last path segment of every trait in the item's `#[derive(...)]` attributes.
+fn derived_traits(attrs: &[syn::Attribute]) -> Result<Vec<String>, syn::Error> {
+    let mut derived = Vec::new();
+    for attr in attrs {
+        if !attr.path().is_ident("derive") {
+            continue;
+        }
+        let paths = attr.parse_args_with(
+            syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+        )?;
+        derived.extend(
+            paths
+                .iter()
+                .filter_map(|path| path.segments.last().map(|segment| segment.ident.to_string())),
+        );
+    }
+    Ok(derived)
+}
+
 fn impl_uuid_type(input: &DeriveInput) -> TokenStream2 {
     let name = &input.ident;
 
@@ -69,13 +171,45 @@ fn impl_uuid_type(input: &DeriveInput) -> TokenStream2 {
         .to_compile_error();
     }
 
+    let options = match EnumOptions::from_attrs(&input.attrs) {
+        Ok(options) => options,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    // Generate prefix match arms
+    let mut prefixes = Vec::new();
+    for v in variants.iter() {
+        let prefix = match get_prefix_from_attrs(&v.attrs) {
+            Ok(Some(p)) => p,
+            Ok(None) => to_snake_case(&v.ident.to_string()),
+            Err(e) => return e.to_compile_error(),
+        };
+        prefixes.push(prefix);
+    }
+
+    let discriminants: Vec<u8> = match options.discriminant {
+        DiscriminantMode::Position => (0..variants.len()).map(|i| i as u8).collect(),
+        DiscriminantMode::Hash => prefixes.iter().map(|prefix| hash_discriminant(prefix)).collect(),
+    };
+    for (i, v) in variants.iter().enumerate() {
+        if let Some(j) = discriminants[..i].iter().position(|d| *d == discriminants[i]) {
+            return syn::Error::new_spanned(
+                &v.ident,
+                format!(
+                    "prefix `{}` hashes to discriminant {}, already used by `{}` (prefix `{}`); change one of the prefixes",
+                    prefixes[i], discriminants[i], variants[j].ident, prefixes[j]
+                ),
+            )
+            .to_compile_error();
+        }
+    }
+
     // Generate discriminant match arms
     let discriminant_arms: Vec<_> = variants
         .iter()
-        .enumerate()
-        .map(|(i, v)| {
+        .zip(&discriminants)
+        .map(|(v, discriminant)| {
             let variant_name = &v.ident;
-            let discriminant = i as u8;
             quote! { Self::#variant_name => #discriminant }
         })
         .collect();
@@ -83,28 +217,106 @@ fn impl_uuid_type(input: &DeriveInput) -> TokenStream2 {
     // Generate from_discriminant match arms
     let from_discriminant_arms: Vec<_> = variants
         .iter()
-        .enumerate()
-        .map(|(i, v)| {
+        .zip(&discriminants)
+        .map(|(v, discriminant)| {
             let variant_name = &v.ident;
-            let discriminant = i as u8;
             quote! { #discriminant => ::core::option::Option::Some(Self::#variant_name) }
         })
         .collect();
 
-    // Generate prefix match arms
-    let mut prefix_arms = Vec::new();
-    for v in variants.iter() {
-        let variant_name = &v.ident;
-        let prefix = match get_prefix_from_attrs(&v.attrs) {
-            Ok(Some(p)) => p,
-            Ok(None) => to_snake_case(&variant_name.to_string()),
-            Err(e) => return e.to_compile_error(),
-        };
-        prefix_arms.push(quote! { Self::#variant_name => #prefix });
-    }
+    let prefix_arms: Vec<_> = variants
+        .iter()
+        .zip(&prefixes)
+        .map(|(v, prefix)| {
+            let variant_name = &v.ident;
+            quote! { Self::#variant_name => #prefix }
+        })
+        .collect();
+
+    let from_prefix_body = from_prefix_lookup(variants.iter().map(|v| &v.ident).zip(&prefixes));
+
+    // Generate one static TypeInfo per variant, in discriminant order
+    let mut by_discriminant: Vec<usize> = (0..variants.len()).collect();
+    by_discriminant.sort_by_key(|&i| discriminants[i]);
+    let sorted_prefixes: Vec<_> = by_discriminant.iter().map(|&i| &prefixes[i]).collect();
+    let sorted_discriminants: Vec<_> = by_discriminant.iter().map(|&i| discriminants[i]).collect();
+
+    let enum_name = name.to_string();
+    let variant_count = variants.len();
+    let type_infos: Vec<_> = by_discriminant
+        .iter()
+        .map(|&i| {
+            let variant_name = variants[i].ident.to_string();
+            let prefix = &prefixes[i];
+            let discriminant = discriminants[i];
+            quote! {
+                smart_uuid::TypeInfo::new::<#name>(#enum_name, #variant_name, #prefix, #discriminant)
+            }
+        })
+        .collect();
+    let type_info_arms: Vec<_> = by_discriminant
+        .iter()
+        .enumerate()
+        .map(|(index, &i)| {
+            let variant_name = &variants[i].ident;
+            quote! { Self::#variant_name => #index }
+        })
+        .collect();
+
+    let registration = if options.register {
+        quote! { smart_uuid::__register_uuid_type!(#name); }
+    } else {
+        quote! {}
+    };
+
+    let migrator = options.migrator.as_ref().map(|migrator| {
+        quote! {
+            fn migrator<'a>() -> ::core::option::Option<&'a dyn smart_uuid::Migrator<Self>>
+            where
+                Self: 'a,
+            {
+                ::core::option::Option::Some(&#migrator)
+            }
+        }
+    });
+
+    let variant_ids = options.variant_ids.then(|| {
+        let vis = &input.vis;
+        let aliases = variants.iter().zip(&discriminants).map(|(v, discriminant)| {
+            let alias = format_ident!("{}{}Id", name, v.ident);
+            let doc = format!("A `{}` ID that can only hold `{}::{}`.", name, name, v.ident);
+            quote! {
+                #[doc = #doc]
+                #vis type #alias = smart_uuid::VariantUuid<#name, #discriminant>;
+            }
+        });
+        quote! { #(#aliases)* }
+    });
+
+    let id_space = options.id_space.as_ref().map(|id_space| {
+        quote! {
+            fn id_space() -> ::core::option::Option<&'static str> {
+                ::core::option::Option::Some(#id_space)
+            }
+        }
+    });
+
+    let format_config = options.format.as_ref().map(|format| {
+        quote! {
+            fn format_config() -> ::core::option::Option<smart_uuid::FormatConfig> {
+                ::core::option::Option::Some(#format)
+            }
+        }
+    });
 
     quote! {
+        #registration
+
+        #variant_ids
+
         impl smart_uuid::UuidType for #name {
+            const DISCRIMINANTS: &'static [u8] = &[#(#sorted_discriminants,)*];
+
             fn discriminant(&self) -> u8 {
                 match self {
                     #(#discriminant_arms,)*
@@ -123,10 +335,178 @@ fn impl_uuid_type(input: &DeriveInput) -> TokenStream2 {
                     #(#prefix_arms,)*
                 }
             }
+
+            fn from_prefix(prefix: &str) -> ::core::option::Option<Self> {
+                #from_prefix_body
+            }
+
+            fn prefixes() -> &'static [&'static str] {
+                &[#(#sorted_prefixes,)*]
+            }
+
+            fn type_info(&self) -> &'static smart_uuid::TypeInfo {
+                static TYPE_INFOS: [smart_uuid::TypeInfo; #variant_count] = [
+                    #(#type_infos,)*
+                ];
+                &TYPE_INFOS[match self {
+                    #(#type_info_arms,)*
+                }]
+            }
+
+            #migrator
+
+            #id_space
+
+            #format_config
+        }
+    }
+}
+
+/// Generates the body of `from_prefix`: a `match` on the prefix length, then on its
+/// first byte, then on the full string. Each lookup only compares against the few
+/// prefixes sharing both, instead of every prefix in turn.
+///
+/// If two variants share a prefix, the first one wins.
+fn from_prefix_lookup<'a>(entries: impl Iterator<Item = (&'a syn::Ident, &'a String)>) -> TokenStream2 {
+    use std::collections::BTreeMap;
+
+    // Prefixes sharing a length and first byte
+    type Bucket<'a> = Vec<(&'a syn::Ident, &'a String)>;
+
+    let mut buckets: BTreeMap<usize, BTreeMap<Option<u8>, Bucket>> = BTreeMap::new();
+    for (ident, prefix) in entries {
+        let bucket = buckets
+            .entry(prefix.len())
+            .or_default()
+            .entry(prefix.as_bytes().first().copied())
+            .or_default();
+        if bucket.iter().all(|(_, existing)| *existing != prefix) {
+            bucket.push((ident, prefix));
+        }
+    }
+
+    let length_arms = buckets.into_iter().map(|(len, by_first)| {
+        let first_arms = by_first.into_iter().map(|(first, bucket)| {
+            let string_arms = bucket.iter().map(|(ident, prefix)| {
+                quote! { #prefix => ::core::option::Option::Some(Self::#ident) }
+            });
+            let pattern = match first {
+                Some(byte) => quote! { ::core::option::Option::Some(#byte) },
+                None => quote! { ::core::option::Option::None },
+            };
+            quote! {
+                #pattern => match prefix {
+                    #(#string_arms,)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        });
+        quote! {
+            #len => match prefix.as_bytes().first() {
+                #(#first_arms,)*
+                _ => ::core::option::Option::None,
+            }
+        }
+    });
+
+    quote! {
+        match prefix.len() {
+            #(#length_arms,)*
+            _ => ::core::option::Option::None,
+        }
+    }
+}
+
+/// Enum-level options from `#[uuid_type(...)]` on the enum itself.
+#[derive(Default)]
+struct EnumOptions {
+    /// `#[uuid_type(register)]`: submit the enum for automatic registry collection.
+    register: bool,
+    /// `#[uuid_type(migrator = PATH)]`: the `Migrator` returned by `UuidType::migrator()`.
+    migrator: Option<syn::Expr>,
+    /// `#[uuid_type(format = EXPR)]`: the `FormatConfig` returned by `UuidType::format_config()`.
+    format: Option<syn::Expr>,
+    /// `#[uuid_type(id_space = "...")]`: the name returned by `UuidType::id_space()`.
+    id_space: Option<syn::LitStr>,
+    /// `#[uuid_type(variant_ids)]`: emit a `VariantUuid` alias per variant.
+    variant_ids: bool,
+    /// `#[uuid_type(discriminant = "...")]`: how discriminants are assigned.
+    discriminant: DiscriminantMode,
+}
+
+/// How variants get their discriminants.
+#[derive(Default)]
+enum DiscriminantMode {
+    /// `"position"`, the default: 0, 1, 2, ... in declaration order.
+    #[default]
+    Position,
+    /// `"hash"`: [`hash_discriminant`] of the prefix.
+    Hash,
+}
+
+impl EnumOptions {
+    fn from_attrs(attrs: &[syn::Attribute]) -> Result<Self, syn::Error> {
+        let mut options = Self::default();
+        for attr in attrs {
+            if !attr.path().is_ident("uuid_type") {
+                continue;
+            }
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("register") {
+                    options.register = true;
+                    Ok(())
+                } else if meta.path.is_ident("variant_ids") {
+                    options.variant_ids = true;
+                    Ok(())
+                } else if meta.path.is_ident("id_space") {
+                    options.id_space = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("migrator") {
+                    options.migrator = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("format") {
+                    options.format = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("discriminant") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    options.discriminant = match value.value().as_str() {
+                        "position" => DiscriminantMode::Position,
+                        "hash" => DiscriminantMode::Hash,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                &value,
+                                "uuid_type discriminant must be \"position\" or \"hash\"",
+                            ))
+                        }
+                    };
+                    Ok(())
+                } else {
+                    let path = meta.path.get_ident()
+                        .map(|i| i.to_string())
+                        .unwrap_or_else(|| "unknown".to_string());
+                    Err(syn::Error::new_spanned(
+                        &meta.path,
+                        format!("unknown uuid_type attribute `{}` on enum. Expected `register`, `variant_ids`, `id_space = \"...\"`, `migrator = ...`, `format = ...` or `discriminant = \"...\"`", path),
+                    ))
+                }
+            })?;
         }
+        Ok(options)
     }
 }
 
+/// The discriminant for `prefix` under `#[uuid_type(discriminant = "hash")]`: the
+/// 32-bit FNV-1a hash of its bytes, XOR-folded to one byte.
+///
+/// Persisted IDs depend on this value; it must never change.
+fn hash_discriminant(prefix: &str) -> u8 {
+    let hash = prefix
+        .bytes()
+        .fold(0x811c_9dc5u32, |hash, b| (hash ^ b as u32).wrapping_mul(0x0100_0193));
+    hash.to_be_bytes().iter().fold(0, |folded, b| folded ^ b)
+}
+
 /// Extract custom prefix from #[uuid_type(prefix = "...")] attribute.
 /// Returns Ok(Some(prefix)) if found, Ok(None) if no uuid_type attr, or Err for invalid syntax.
 fn get_prefix_from_attrs(attrs: &[syn::Attribute]) -> Result<Option<String>, syn::Error> {
@@ -142,6 +522,13 @@ fn get_prefix_from_attrs(attrs: &[syn::Attribute]) -> Result<Option<String>, syn
         let result = attr.parse_nested_meta(|meta| {
             if meta.path.is_ident("prefix") {
                 let value: syn::LitStr = meta.value()?.parse()?;
+                // Keep in sync with smart_uuid::MAX_PREFIX_LEN
+                if value.value().len() > 64 {
+                    return Err(syn::Error::new_spanned(
+                        &value,
+                        "uuid_type prefix is longer than 64 bytes, the maximum smart_uuid will parse",
+                    ));
+                }
                 prefix = Some(value.value());
                 Ok(())
             } else {
@@ -163,9 +550,7 @@ fn get_prefix_from_attrs(attrs: &[syn::Attribute]) -> Result<Option<String>, syn
         });
 
         // Propagate parse errors
-        if let Err(e) = result {
-            return Err(e);
-        }
+        result?;
 
         // Propagate unknown attribute errors
         if let Some(e) = had_error {
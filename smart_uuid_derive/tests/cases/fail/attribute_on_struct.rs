@@ -0,0 +1,10 @@
+//! The attribute form only applies to enums
+
+use smart_uuid::uuid_type;
+
+#[uuid_type]
+struct UserType {
+    id: u8,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+//! Fail case: Two prefixes hash to the same discriminant
+
+use smart_uuid::UuidType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(discriminant = "hash")]
+enum EntityType {
+    User,
+    #[uuid_type(prefix = "usergw")]
+    Gateway,
+}
+
+fn main() {}
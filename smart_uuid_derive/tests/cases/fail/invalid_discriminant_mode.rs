@@ -0,0 +1,12 @@
+//! Fail case: Unknown discriminant mode
+
+use smart_uuid::UuidType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(discriminant = "random")]
+enum EntityType {
+    User,
+    Admin,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+//! Fail case: Unknown uuid_type attribute on the enum itself
+
+use smart_uuid::UuidType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(prefix = "user")]
+enum EntityType {
+    User,
+    Admin,
+}
+
+fn main() {}
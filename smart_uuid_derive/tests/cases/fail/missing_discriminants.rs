@@ -0,0 +1,26 @@
+//! Test: const checks name the missing DISCRIMINANTS list of a hand-written impl
+
+use smart_uuid::{UuidType, VariantUuid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LegacyKind {
+    Customer,
+}
+
+impl UuidType for LegacyKind {
+    fn discriminant(&self) -> u8 {
+        0
+    }
+
+    fn from_discriminant(value: u8) -> Option<Self> {
+        (value == 0).then_some(LegacyKind::Customer)
+    }
+
+    fn prefix(&self) -> &'static str {
+        "customer"
+    }
+}
+
+fn main() {
+    let _ = VariantUuid::<LegacyKind, 0>::new();
+}
@@ -0,0 +1,11 @@
+//! Fail case: Prefix longer than the parser accepts
+
+use smart_uuid::UuidType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum EntityType {
+    #[uuid_type(prefix = "an_extremely_long_prefix_that_nobody_could_ever_parse_back_out_again")]
+    User,
+}
+
+fn main() {}
@@ -0,0 +1,52 @@
+//! Test the attribute form of the macro
+
+use smart_uuid::{uuid_type, TypedUuid, UserFriendlyUuid, UuidType};
+
+// Adds every derive UuidType needs
+#[uuid_type]
+enum UserType {
+    Retail,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+// Enum-level options as arguments, keeping the derives already present
+#[uuid_type(discriminant = "hash")]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd)]
+enum AccountType {
+    Checking,
+    Savings,
+}
+
+// Enums emitted by another macro
+macro_rules! id_kinds {
+    ($name:ident { $($variant:ident),+ }) => {
+        #[uuid_type]
+        pub enum $name { $($variant),+ }
+    };
+}
+
+id_kinds!(EventType { Created, Deleted });
+
+// The derive still works alongside the imported attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+enum DocumentType {
+    #[uuid_type(prefix = "rcpt")]
+    Receipt,
+}
+
+fn main() {
+    assert_eq!(UserType::Organization.prefix(), "org");
+    assert_eq!(UserType::from_discriminant(0), Some(UserType::Retail));
+    let friendly = UserFriendlyUuid::new(UserType::Organization);
+    assert!(friendly.to_string().starts_with("org_"));
+
+    assert_ne!(AccountType::Checking.discriminant(), 0);
+    assert!(AccountType::Checking < AccountType::Savings);
+
+    let id = TypedUuid::new(EventType::Deleted);
+    assert_eq!(id.variant_type(), EventType::Deleted);
+    assert_eq!(EventType::prefixes(), &["created", "deleted"]);
+
+    assert_eq!(DocumentType::Receipt.prefix(), "rcpt");
+}
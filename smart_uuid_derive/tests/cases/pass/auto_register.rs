@@ -0,0 +1,33 @@
+//! Test: #[uuid_type(register)] submits the enum for automatic registry collection
+
+use smart_uuid::{UserFriendlyUuid, UuidRegistry, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(register, id_space = "records")]
+enum UserType {
+    Retail,
+    Business,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(register, id_space = "records")]
+enum DocumentType {
+    #[uuid_type(prefix = "inv")]
+    Invoice,
+}
+
+fn main() {
+    let registry = UuidRegistry::from_registered().unwrap();
+    assert!(registry.contains_prefix("retail"));
+    assert!(registry.contains_prefix("business"));
+    assert!(registry.contains_prefix("inv"));
+
+    let invoice = UserFriendlyUuid::new(DocumentType::Invoice);
+    let parsed = registry.parse(&invoice.to_string()).unwrap();
+    assert_eq!(parsed.downcast::<DocumentType>(), Some(invoice.into_typed_uuid()));
+
+    // Both enums share an ID space but start at discriminant 0, so raw UUIDs are not attributable
+    assert!(registry.verify().is_err());
+
+    println!("Auto-registration tests passed!");
+}
@@ -29,6 +29,12 @@ fn main() {
     assert_eq!(DocumentType::PurchaseOrder.prefix(), "purchase_order");
     assert_eq!(DocumentType::Quote.prefix(), "q");
 
+    // Test prefix lookup uses the custom prefixes
+    assert_eq!(DocumentType::from_prefix("rcpt"), Some(DocumentType::Receipt));
+    assert_eq!(DocumentType::from_prefix("purchase_order"), Some(DocumentType::PurchaseOrder));
+    assert_eq!(DocumentType::from_prefix("q"), Some(DocumentType::Quote));
+    assert_eq!(DocumentType::from_prefix("receipt"), None);
+
     // Verify in UserFriendlyUuid output
     let typed = TypedUuid::new(DocumentType::Receipt);
     let friendly: UserFriendlyUuid<DocumentType> = typed.into();
@@ -0,0 +1,46 @@
+//! Test discriminants derived from prefix hashes
+
+use smart_uuid::{TypedUuid, UuidType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(discriminant = "hash")]
+enum UserType {
+    Retail,
+    Business,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+// Same prefixes, declared in another order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(discriminant = "hash")]
+enum ReorderedUserType {
+    #[uuid_type(prefix = "org")]
+    Organization,
+    Retail,
+    Business,
+}
+
+fn main() {
+    // Pinned: changing these would reinterpret persisted IDs
+    assert_eq!(UserType::Retail.discriminant(), 92);
+    assert_eq!(UserType::Business.discriminant(), 157);
+    assert_eq!(UserType::Organization.discriminant(), 40);
+
+    assert_eq!(ReorderedUserType::Retail.discriminant(), UserType::Retail.discriminant());
+    assert_eq!(ReorderedUserType::Organization.discriminant(), UserType::Organization.discriminant());
+
+    assert_eq!(UserType::from_discriminant(157), Some(UserType::Business));
+    assert_eq!(UserType::from_discriminant(0), None);
+
+    // Prefixes and type info are listed in discriminant order
+    assert_eq!(UserType::prefixes(), &["org", "retail", "business"]);
+    assert_eq!(ReorderedUserType::prefixes(), UserType::prefixes());
+    assert_eq!(UserType::Business.type_info().variant_name(), "Business");
+    assert_eq!(UserType::Business.type_info().discriminant(), 157);
+
+    let id = TypedUuid::new(UserType::Organization);
+    assert_eq!(id.as_bytes()[0], 40);
+    let parsed: TypedUuid<UserType> = id.to_string().parse().unwrap();
+    assert_eq!(parsed.variant_type(), UserType::Organization);
+}
@@ -39,5 +39,14 @@ fn main() {
     assert_eq!(ManyVariants::V000.prefix(), "v000");
     assert_eq!(ManyVariants::V099.prefix(), "v099");
 
+    // Test prefix lookup for every variant
+    for d in 0..100 {
+        let variant = ManyVariants::from_discriminant(d).unwrap();
+        assert_eq!(ManyVariants::from_prefix(variant.prefix()), Some(variant));
+    }
+    assert_eq!(ManyVariants::from_prefix("v100"), None);
+    assert_eq!(ManyVariants::from_prefix("v00"), None);
+    assert_eq!(ManyVariants::from_prefix(""), None);
+
     println!("Many variants test passed!");
 }
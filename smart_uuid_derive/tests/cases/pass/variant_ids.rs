@@ -0,0 +1,35 @@
+//! Test the per-variant `VariantUuid` aliases
+
+use smart_uuid::{TypedUuid, UuidType, VariantUuid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(variant_ids)]
+pub enum UserType {
+    Retail,
+    Business,
+    #[uuid_type(prefix = "org")]
+    Organization,
+}
+
+// Hash discriminants are picked up too
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(variant_ids, discriminant = "hash")]
+enum DocumentType {
+    Invoice,
+    Receipt,
+}
+
+fn close_retail_account(id: UserTypeRetailId) -> TypedUuid<UserType> {
+    id.into()
+}
+
+fn main() {
+    let retail: UserTypeRetailId = VariantUuid::<UserType, 0>::new();
+    assert_eq!(close_retail_account(retail).variant_type(), UserType::Retail);
+    assert_eq!(UserTypeOrganizationId::variant(), UserType::Organization);
+    assert_eq!(UserTypeBusinessId::DISCRIMINANT, 1);
+
+    let receipt = DocumentTypeReceiptId::new();
+    assert_eq!(receipt.variant_type(), DocumentType::Receipt);
+    assert_eq!(DocumentTypeInvoiceId::DISCRIMINANT, DocumentType::Invoice.discriminant());
+}
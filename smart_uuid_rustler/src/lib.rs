@@ -0,0 +1,107 @@
+//! # smart_uuid_rustler
+//!
+//! Elixir NIF functions for generating and validating smart_uuid IDs, so BEAM
+//! services share the canonical Rust implementation instead of re-implementing it.
+//!
+//! The NIFs work on every enum marked `#[uuid_type(register)]` in the final binary
+//! (see [`UuidRegistry::from_registered`]). Prefixes are exchanged as atoms; they
+//! come from a fixed set compiled into the NIF, so they cannot exhaust the atom table.
+//!
+//! ## Usage
+//!
+//! In the NIF crate built by Mix, depend on this crate and the crate defining the
+//! enums, then initialize the module. The NIFs below are picked up automatically:
+//!
+//! ```text
+//! use my_ids as _; // defines #[derive(UuidType)] #[uuid_type(register)] enums
+//! use smart_uuid_rustler as _;
+//!
+//! rustler::init!("Elixir.MyApp.Ids");
+//! ```
+//!
+//! ```text
+//! defmodule MyApp.Ids do
+//!   use Rustler, otp_app: :my_app, crate: "my_app_ids"
+//!
+//!   def generate(_prefix), do: :erlang.nif_error(:nif_not_loaded)
+//!   def parse(_string), do: :erlang.nif_error(:nif_not_loaded)
+//!   def valid(_string), do: :erlang.nif_error(:nif_not_loaded)
+//!   def prefixes(), do: :erlang.nif_error(:nif_not_loaded)
+//!
+//!   def valid?(string), do: valid(string)
+//! end
+//! ```
+//!
+//! | NIF | Returns |
+//! |-----|---------|
+//! | `generate(:org)` | `{:ok, "org_..."}` or `{:error, :unknown_prefix}` |
+//! | `parse("org_...")` | `{:ok, {:org, "0a1b..."}}` or `{:error, kind}`, e.g. `:invalid_format` |
+//! | `valid("org_...")` | `true` or `false` |
+//! | `prefixes()` | every registered prefix as an atom |
+
+use std::sync::OnceLock;
+
+use rustler::{Atom, Encoder, Env, Term};
+use smart_uuid::{RegistryError, UuidRegistry};
+
+mod atoms {
+    rustler::atoms! {
+        unknown_prefix,
+        registry_conflict,
+    }
+}
+
+/// The registry of every `#[uuid_type(register)]` enum, built on first use.
+///
+/// Two registered enums claiming one prefix is a build mistake; every NIF then
+/// returns `{:error, :registry_conflict}` rather than guessing.
+fn registry() -> Result<&'static UuidRegistry, Atom> {
+    static REGISTRY: OnceLock<Result<UuidRegistry, RegistryError>> = OnceLock::new();
+    REGISTRY
+        .get_or_init(UuidRegistry::from_registered)
+        .as_ref()
+        .map_err(|_| atoms::registry_conflict())
+}
+
+/// Creates a new ID for the variant with this prefix, in user-friendly form.
+#[rustler::nif]
+fn generate(env: Env<'_>, prefix: Atom) -> Result<String, Atom> {
+    let prefix = prefix.encode(env).atom_to_string().map_err(|_| atoms::unknown_prefix())?;
+    registry()?
+        .generate(&prefix)
+        .map(|id| id.to_string())
+        .ok_or_else(atoms::unknown_prefix)
+}
+
+/// Parses a user-friendly ID into its prefix atom and hyphenated UUID.
+///
+/// Errors are the snake_case [`ErrorKind`](smart_uuid::ErrorKind) name as an atom.
+#[rustler::nif]
+fn parse<'a>(env: Env<'a>, s: &str) -> Result<(Term<'a>, String), Term<'a>> {
+    let id = registry()
+        .map_err(|conflict| conflict.encode(env))?
+        .parse(s)
+        .map_err(|err| atom(env, err.kind().name()))?;
+    Ok((atom(env, id.prefix()), id.as_uuid().to_string()))
+}
+
+/// Returns `true` if the string is a valid ID of some registered enum.
+#[rustler::nif]
+fn valid(s: &str) -> bool {
+    registry().is_ok_and(|registry| registry.parse(s).is_ok())
+}
+
+/// Returns every registered prefix as an atom, in no particular order.
+#[rustler::nif]
+fn prefixes(env: Env<'_>) -> Vec<Term<'_>> {
+    registry()
+        .map(|registry| registry.prefixes().map(|prefix| atom(env, prefix)).collect())
+        .unwrap_or_default()
+}
+
+/// Encodes a prefix or error name as an atom. Both come from a fixed set of statics.
+fn atom<'a>(env: Env<'a>, name: &str) -> Term<'a> {
+    Atom::from_str(env, name)
+        .expect("prefixes and error kind names are valid atoms")
+        .encode(env)
+}
@@ -0,0 +1,102 @@
+//! # smart_uuid_wit
+//!
+//! A WebAssembly component exporting smart_uuid generation and parsing through the
+//! WIT interface in `wit/smart-uuid.wit`, so hosts embedding Wasm plugins can pass
+//! typed IDs across the component boundary with a defined ABI.
+//!
+//! The component serves every enum marked `#[uuid_type(register)]` in the final
+//! binary (see [`UuidRegistry::from_registered`]).
+//!
+//! ## Usage
+//!
+//! In a `cdylib` crate that also links the crate defining the enums:
+//!
+//! ```text
+//! use my_ids as _; // defines #[derive(UuidType)] #[uuid_type(register)] enums
+//!
+//! smart_uuid_wit::export_smart_uuid!(smart_uuid_wit::Component with_types_in smart_uuid_wit);
+//! ```
+//!
+//! Then build with `cargo build --target wasm32-wasip2`.
+
+use std::sync::OnceLock;
+
+use smart_uuid::{AnyTypedUuid, ErrorKind, RegistryError, Uuid, UuidRegistry};
+
+wit_bindgen::generate!({
+    world: "smart-uuid",
+    path: "wit",
+    pub_export_macro: true,
+    export_macro_name: "export_smart_uuid",
+});
+
+use exports::smart_uuid::ids::ids::{ErrorKind as WitErrorKind, Guest, TypedId};
+
+/// Implements the exported `ids` interface. Pass it to [`export_smart_uuid!`].
+pub struct Component;
+
+impl Guest for Component {
+    fn generate(prefix: String) -> Result<TypedId, WitErrorKind> {
+        registry()?
+            .generate(&prefix)
+            .map(to_wit)
+            .ok_or(WitErrorKind::UnknownPrefix)
+    }
+
+    fn parse(input: String) -> Result<TypedId, WitErrorKind> {
+        registry()?.parse(&input).map(to_wit).map_err(|err| err.kind().into())
+    }
+
+    fn to_string(id: TypedId) -> Result<String, WitErrorKind> {
+        let uuid = Uuid::from_u64_pair(id.high, id.low);
+        // Round-trip through the parser so the prefix is checked against the discriminant
+        registry()?
+            .parse(&format!("{}_{}", id.prefix, uuid))
+            .map(|id| id.to_string())
+            .map_err(|err| err.kind().into())
+    }
+
+    fn prefixes() -> Vec<String> {
+        registry()
+            .map(|registry| registry.prefixes().map(String::from).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// The registry of every `#[uuid_type(register)]` enum, built on first use.
+///
+/// A prefix claimed by two enums is a build mistake; no prefix is served then, and every
+/// call fails with `registry-conflict`.
+fn registry() -> Result<&'static UuidRegistry, WitErrorKind> {
+    static REGISTRY: OnceLock<Result<UuidRegistry, RegistryError>> = OnceLock::new();
+    REGISTRY
+        .get_or_init(UuidRegistry::from_registered)
+        .as_ref()
+        .map_err(|_| WitErrorKind::RegistryConflict)
+}
+
+fn to_wit(id: AnyTypedUuid) -> TypedId {
+    let (high, low) = id.as_uuid().as_u64_pair();
+    TypedId {
+        prefix: id.prefix().to_string(),
+        high,
+        low,
+    }
+}
+
+impl From<ErrorKind> for WitErrorKind {
+    fn from(kind: ErrorKind) -> Self {
+        match kind {
+            ErrorKind::InvalidDiscriminant => Self::InvalidDiscriminant,
+            ErrorKind::Parse => Self::Parse,
+            ErrorKind::UnknownPrefix => Self::UnknownPrefix,
+            ErrorKind::InvalidFormat => Self::InvalidFormat,
+            ErrorKind::UnexpectedPrefix => Self::UnexpectedPrefix,
+            ErrorKind::UnsupportedSchemaVersion => Self::UnsupportedSchemaVersion,
+            ErrorKind::VariantNotAllowed => Self::VariantNotAllowed,
+            // ErrorKind is non-exhaustive; a new kind needs its own WIT case and an
+            // interface version bump, this arm only keeps the build going until then
+            _ => Self::InvalidFormat,
+        }
+    }
+}
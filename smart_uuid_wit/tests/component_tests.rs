@@ -0,0 +1,51 @@
+//! Calls the exported interface natively, without a Wasm runtime.
+
+use smart_uuid::{ErrorKind as CoreErrorKind, TypedUuid, UserFriendlyUuid, UuidType};
+use smart_uuid_wit::exports::smart_uuid::ids::ids::{ErrorKind, Guest, TypedId};
+use smart_uuid_wit::Component;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(register)]
+enum WitUserType {
+    #[uuid_type(prefix = "wit_retail")]
+    Retail,
+    #[uuid_type(prefix = "wit_org")]
+    Organization,
+}
+
+#[test]
+fn generate_parse_and_render_round_trip() {
+    let id = Component::generate("wit_org".to_string()).unwrap();
+    assert_eq!(id.prefix, "wit_org");
+    assert_eq!(id.high >> 56, WitUserType::Organization.discriminant() as u64);
+
+    let rendered = Component::to_string(id.clone()).unwrap();
+    let parsed = Component::parse(rendered.clone()).unwrap();
+    assert_eq!((parsed.prefix, parsed.high, parsed.low), (id.prefix, id.high, id.low));
+
+    let friendly = UserFriendlyUuid::<WitUserType>::parse_str(&rendered).unwrap();
+    assert_eq!(friendly.variant_type(), WitUserType::Organization);
+}
+
+#[test]
+fn errors_map_to_error_kinds() {
+    assert_eq!(Component::generate("nope".to_string()).unwrap_err(), ErrorKind::UnknownPrefix);
+    assert_eq!(Component::parse("wit_org_zzz".to_string()).unwrap_err(), ErrorKind::InvalidFormat);
+
+    // A prefix that disagrees with the discriminant is rejected
+    let (high, low) = TypedUuid::new(WitUserType::Retail).as_uuid().as_u64_pair();
+    let mismatched = TypedId { prefix: "wit_org".to_string(), high, low };
+    assert_eq!(Component::to_string(mismatched).unwrap_err(), ErrorKind::UnknownPrefix);
+
+    assert!(Component::prefixes().contains(&"wit_retail".to_string()));
+}
+
+#[test]
+fn every_core_error_kind_has_its_own_case() {
+    assert_eq!(ErrorKind::from(CoreErrorKind::VariantNotAllowed), ErrorKind::VariantNotAllowed);
+    assert_eq!(ErrorKind::from(CoreErrorKind::UnexpectedPrefix), ErrorKind::UnexpectedPrefix);
+    assert_eq!(
+        ErrorKind::from(CoreErrorKind::UnsupportedSchemaVersion),
+        ErrorKind::UnsupportedSchemaVersion
+    );
+}
@@ -0,0 +1,29 @@
+//! A component whose registered enums conflict. Its own test binary, since the registry
+//! is built once per process.
+
+use smart_uuid::UuidType;
+use smart_uuid_wit::exports::smart_uuid::ids::ids::{ErrorKind, Guest, TypedId};
+use smart_uuid_wit::Component;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(register)]
+enum WitCustomerType {
+    #[uuid_type(prefix = "wit_member")]
+    Member,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UuidType)]
+#[uuid_type(register)]
+enum WitStaffType {
+    #[uuid_type(prefix = "wit_member")]
+    Member,
+}
+
+#[test]
+fn conflicting_registrations_are_reported_as_such() {
+    assert_eq!(Component::generate("wit_member".to_string()).unwrap_err(), ErrorKind::RegistryConflict);
+    assert_eq!(Component::parse("wit_member_x".to_string()).unwrap_err(), ErrorKind::RegistryConflict);
+    let id = TypedId { prefix: "wit_member".to_string(), high: 0, low: 0 };
+    assert_eq!(Component::to_string(id).unwrap_err(), ErrorKind::RegistryConflict);
+    assert!(Component::prefixes().is_empty());
+}